@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rand::RngCore;
+use symmetric_cipher::crypto::cipher_traits::SymmetricCipherWithRounds;
+use LOK197::crypto::loki97::Loki97Cipher;
+use LOK197::crypto::permutation::{permute, permute_generic};
+use LOK197::crypto::sboxes::{s1, s1_generic, s2, s2_generic};
+
+fn bench_sboxes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("S-boxes, all inputs");
+    group.bench_function("S1 generic", |b| b.iter(|| (0..0x2000u16).fold(0u8, |acc, x| acc ^ s1_generic(black_box(x)))));
+    group.bench_function("S1 table", |b| b.iter(|| (0..0x2000u16).fold(0u8, |acc, x| acc ^ s1(black_box(x)))));
+    group.bench_function("S2 generic", |b| b.iter(|| (0..0x800u16).fold(0u8, |acc, x| acc ^ s2_generic(black_box(x)))));
+    group.bench_function("S2 table", |b| b.iter(|| (0..0x800u16).fold(0u8, |acc, x| acc ^ s2(black_box(x)))));
+    group.finish();
+}
+
+fn bench_permutation(c: &mut Criterion) {
+    let x = 0x0123_4567_89AB_CDEFu64;
+    let mut group = c.benchmark_group("P");
+    group.bench_function("generic", |b| b.iter(|| permute_generic(black_box(x))));
+    group.bench_function("table", |b| b.iter(|| permute(black_box(x))));
+    group.finish();
+}
+
+fn bench_cipher(c: &mut Criterion) {
+    let mut data = vec![0u8; 16 * 1024];
+    rand::rng().fill_bytes(&mut data);
+    let cipher = Loki97Cipher::new(&[0x2b; 32]).unwrap();
+
+    let mut group = c.benchmark_group("LOKI97-256 ECB 16KB");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("encrypt", |b| b.iter(|| cipher.encrypt_blocks(&mut data)));
+    group.bench_function("decrypt", |b| b.iter(|| cipher.decrypt_blocks(&mut data)));
+    group.finish();
+
+    c.bench_function("LOKI97-256 key setup", |b| b.iter(|| Loki97Cipher::new(black_box(&[0x2b; 32])).unwrap()));
+}
+
+criterion_group!(benches, bench_sboxes, bench_permutation, bench_cipher);
+criterion_main!(benches);
@@ -1,5 +1,4 @@
 use std::fs;
-use tokio;
 
 use rand::SeedableRng;
 use rand::{rngs::StdRng, RngCore};
@@ -11,7 +10,7 @@ use LOK197::crypto::sboxes::{s1, s2};
 
 use symmetric_cipher::crypto::cipher_context::CipherContext;
 use symmetric_cipher::crypto::cipher_traits::{
-    CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds
+    CipherAlgorithm, SymmetricCipherWithRounds
 };
 use symmetric_cipher::crypto::cipher_types::{
     CipherInput, CipherMode, CipherOutput, PaddingMode,
@@ -45,7 +44,7 @@ async fn main() -> std::io::Result<()> {
         0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6,
         0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
     ];
-    let round_keys = expand_key(&key128);
+    let round_keys = expand_key(&key128).unwrap();
     println!(" First round key: {:016x}", round_keys[0]);
 
     // 128-bit (16-byte) block для тестирования
@@ -54,7 +53,7 @@ async fn main() -> std::io::Result<()> {
         0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34,
     ];
 
-    let mut cipher = Loki97Cipher::new(&key128);
+    let cipher = Loki97Cipher::new(&key128).unwrap();
 
     let round_keys_bytes = cipher.export_round_keys().unwrap();
     println!(" First round key (bytes): {:02x?}", &round_keys_bytes[0..8]);
@@ -106,18 +105,18 @@ async fn main() -> std::io::Result<()> {
                     Some(vec![0u8; 16])
                 };
                 let mut ctx = CipherContext::new(
-                    Box::new(Loki97Cipher::new(&key)) as _,
+                    Box::new(Loki97Cipher::new(&key).unwrap()) as _,
                     mode, pad, iv.clone(), Vec::new()
                 );
                 ctx.set_key(&key).unwrap();
 
                 // encrypt
-                let mut out_enc = CipherOutput::Buffer(Box::new(Vec::new()));
+                let mut out_enc = CipherOutput::Buffer(Box::default());
                 ctx.encrypt(CipherInput::Bytes(data.clone()), &mut out_enc).await.unwrap();
                 let cipher = out_enc.as_buffer().clone();
 
                 // decrypt
-                let mut out_dec = CipherOutput::Buffer(Box::new(Vec::new()));
+                let mut out_dec = CipherOutput::Buffer(Box::default());
                 ctx.decrypt(CipherInput::Bytes(cipher.clone()), &mut out_dec).await.unwrap();
                 let plain = out_dec.as_buffer();
                 assert_eq!(plain, &data);
@@ -180,7 +179,7 @@ async fn main() -> std::io::Result<()> {
                     Some(vec![0u8; 16]) // 16-байтные блоки для 128-битного Loki97
                 };
                 let mut ctx = CipherContext::new(
-                    Box::new(Loki97Cipher::new(&key)) as _,
+                    Box::new(Loki97Cipher::new(&key).unwrap()) as _,
                     mode, pad, iv.clone(), Vec::new()
                 );
                 ctx.set_key(&key).unwrap();
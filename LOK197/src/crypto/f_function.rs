@@ -1,7 +1,50 @@
-use crate::crypto::sboxes::{s1, s2};
-use crate::crypto::permutation::P;
+use crate::crypto::sboxes::{s1, s1_table, s2, s2_table};
+use crate::crypto::permutation::{permute, P};
 
+/// f(A, B) = Sb(P(Sa(E(KP(A, B)))), B) из спецификации LOKI97.
+pub fn f(a: u64, b: u64) -> u64 {
+    let kp = keyed_permutation(a, b);
+    sb_layer(permute(sa_layer(kp)), b)
+}
+
+/// E и Sa: восемь пересекающихся окон по 13 и 11 бит, циклически по слову,
+/// через S1, S2, S1, S2, S2, S1, S2, S1.
+fn sa_layer(x: u64) -> u64 {
+    let (s1, s2) = (s1_table(), s2_table());
+    u64::from_be_bytes([
+        s1[(x.rotate_left(8) & 0x1FFF) as usize],
+        s2[((x >> 48) & 0x7FF) as usize],
+        s1[((x >> 40) & 0x1FFF) as usize],
+        s2[((x >> 32) & 0x7FF) as usize],
+        s2[((x >> 24) & 0x7FF) as usize],
+        s1[((x >> 16) & 0x1FFF) as usize],
+        s2[((x >> 8) & 0x7FF) as usize],
+        s1[(x & 0x1FFF) as usize],
+    ])
+}
+
+/// Sb: байты P дополняются старшими битами левой половины B —
+/// по 3 бита для S2 и по 5 для S1.
+fn sb_layer(x: u64, b: u64) -> u64 {
+    let (s1, s2) = (s1_table(), s2_table());
+    let x = x.to_be_bytes();
+    let input = |byte: u8, shift: u32, width: u32| (((b >> shift) & ((1 << width) - 1)) << 8 | byte as u64) as usize;
+    u64::from_be_bytes([
+        s2[input(x[0], 61, 3)],
+        s2[input(x[1], 58, 3)],
+        s1[input(x[2], 53, 5)],
+        s1[input(x[3], 48, 5)],
+        s2[input(x[4], 45, 3)],
+        s2[input(x[5], 42, 3)],
+        s1[input(x[6], 37, 5)],
+        s1[input(x[7], 32, 5)],
+    ])
+}
 
+/// Упрощённая учебная функция раунда с одним подключом; не совместима со
+/// спецификацией и используется только в [`Variant::Educational`].
+///
+/// [`Variant::Educational`]: crate::crypto::loki97::Variant::Educational
 pub fn round_function(input: u64, round_key: u64) -> u64 {
     let kp_out = keyed_permutation(input, round_key);
 
@@ -12,41 +55,30 @@ pub fn round_function(input: u64, round_key: u64) -> u64 {
     let after_p = linear_permutation(after_sa);
 
     let mut sb_in = [0u16; 8];
-    for i in 0..8 {
-        sb_in[i] = ((after_p >> (8 * i)) & 0xFF) as u16;
+    for (i, b) in sb_in.iter_mut().enumerate() {
+        *b = ((after_p >> (8 * i)) & 0xFF) as u16;
     }
     let after_sb = substitution_layer(&sb_in, false);
 
     after_sb ^ round_key
 }
 
+/// KP: меняет местами биты половин A там, где стоят биты правой половины B.
 fn keyed_permutation(data: u64, key: u64) -> u64 {
     let lo = data as u32;
     let hi = (data >> 32) as u32;
-    let mut new_lo = 0u32;
-    let mut new_hi = 0u32;
-    for i in 0..32 {
-        let mask = 1u32 << i;
-        if (key as u32) & mask != 0 {
-            // swap bits
-            let b_lo = (lo >> i) & 1;
-            let b_hi = (hi >> i) & 1;
-            new_lo |= b_hi << i;
-            new_hi |= b_lo << i;
-        } else {
-            new_lo |= lo & mask;
-            new_hi |= hi & mask;
-        }
-    }
+    let mask = key as u32;
+    let new_lo = (lo & !mask) | (hi & mask);
+    let new_hi = (hi & !mask) | (lo & mask);
     ((new_hi as u64) << 32) | (new_lo as u64)
 }
 
 
 fn expansion_64_to_96(value: u64) -> [u16; 8] {
     let mut out = [0u16; 8];
-    for i in 0..8 {
+    for (i, o) in out.iter_mut().enumerate() {
         let rotated = value.rotate_right((i * 8) as u32);
-        out[i] = (rotated & 0x0FFF) as u16;
+        *o = (rotated & 0x0FFF) as u16;
     }
     out
 }
@@ -71,9 +103,9 @@ fn substitution_layer(input: &[u16; 8], first: bool) -> u64 {
 
 fn linear_permutation(x: u64) -> u64 {
     let mut y = 0u64;
-    for i in 0..64 {
+    for (i, &p) in P.iter().enumerate() {
         let bit = (x >> (63 - i)) & 1;
-        let tgt = 63 - P[i] as usize;
+        let tgt = 63 - p as usize;
         y |= bit << tgt;
     }
     y
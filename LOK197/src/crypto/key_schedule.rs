@@ -1,9 +1,49 @@
+use symmetric_cipher::crypto::cipher_error::CipherError;
 use symmetric_cipher::crypto::encryption_transformation::EncryptionTransformation;
-use crate::crypto::f_function::round_function;
-use crate::crypto::loki97::Loki97Cipher;
+use crate::crypto::f_function::{f, round_function};
+use crate::crypto::loki97::{Loki97Cipher, Variant};
 use crate::symmetric_crypto::key_expansion::KeyExpansion;
 
-pub fn expand_key(master_key: &[u8]) -> Vec<u64> {
+const DELTA: u64 = 0x9E3779B97F4A7C15;
+
+/// Допустимые длины ключа в байтах.
+pub const KEY_SIZES: &[usize] = &[16, 24, 32];
+
+pub(crate) fn check_key_length(master_key: &[u8]) -> Result<(), CipherError> {
+    if !KEY_SIZES.contains(&master_key.len()) {
+        return Err(CipherError::InvalidKeyLength {
+            expected: KEY_SIZES,
+            actual: master_key.len(),
+        });
+    }
+    Ok(())
+}
+
+/// 48 подключей LOKI97, по три на раунд. Ключ 128, 192 или 256 бит
+/// [Ka|Kb|Kc|Kd]; недостающие слова: Kc = f(Kb, Ka), Kd = f(Ka, Kb).
+pub fn expand_key(master_key: &[u8]) -> Result<Vec<u64>, CipherError> {
+    check_key_length(master_key)?;
+    let word = |i: usize| u64::from_be_bytes(master_key[8 * i..8 * i + 8].try_into().unwrap());
+
+    let ka = word(0);
+    let kb = word(1);
+    let kc = if master_key.len() > 16 { word(2) } else { f(kb, ka) };
+    let kd = if master_key.len() > 24 { word(3) } else { f(ka, kb) };
+
+    // [K4|K3|K2|K1] = [Ka|Kb|Kc|Kd]; SK_i = K4 ^ f(K1 + K3 + i·DELTA, K2).
+    let (mut k4, mut k3, mut k2, mut k1) = (ka, kb, kc, kd);
+    Ok((1..=48u64)
+        .map(|i| {
+            let sk = k4 ^ f(k1.wrapping_add(k3).wrapping_add(DELTA.wrapping_mul(i)), k2);
+            (k4, k3, k2, k1) = (k3, k2, k1, sk);
+            sk
+        })
+        .collect())
+}
+
+/// Расписание учебного варианта: ключ дополняется нулями до 256 бит, а
+/// вместо f используется упрощённая [`round_function`].
+pub fn expand_key_educational(master_key: &[u8]) -> Vec<u64> {
     let mut key_material = [0u8; 32];
     key_material[..master_key.len()].copy_from_slice(master_key);
 
@@ -12,13 +52,11 @@ pub fn expand_key(master_key: &[u8]) -> Vec<u64> {
     let mut word_2 = u64::from_be_bytes(key_material[16..24].try_into().unwrap());
     let mut word_1 = u64::from_be_bytes(key_material[24..32].try_into().unwrap());
 
-    const ROUND_CONSTANT: u64 = 0x9E3779B97F4A7C15;
-
     let mut round_keys = Vec::with_capacity(48);
     for round_index in 1..=48 {
         let round_input = word_1
             .wrapping_add(word_3)
-            .wrapping_add(ROUND_CONSTANT.wrapping_mul(round_index));
+            .wrapping_add(DELTA.wrapping_mul(round_index));
         let round_output = round_function(round_input, word_2);
 
         let updated_word_1 = word_4 ^ round_output;
@@ -37,8 +75,13 @@ pub fn expand_key(master_key: &[u8]) -> Vec<u64> {
 }
 
 impl KeyExpansion for Loki97Cipher {
+    /// `KeyExpansion` не умеет возвращать ошибку, поэтому ключ неверной длины
+    /// здесь вызывает панику; обычный путь — `set_key`.
     fn generate_round_keys(&self, master_key: &[u8]) -> Vec<Vec<u8>> {
-        let round_keys_64 = expand_key(master_key);
+        let round_keys_64 = match self.variant() {
+            Variant::Standard => expand_key(master_key).expect("LOKI97 key must be 16, 24 or 32 bytes"),
+            Variant::Educational => expand_key_educational(master_key),
+        };
         round_keys_64
             .into_iter()
             .map(|key| key.to_be_bytes().to_vec())
@@ -50,7 +93,10 @@ impl EncryptionTransformation for Loki97Cipher {
     fn transform(&self, plaintext_block: &[u8], round_key: &[u8]) -> Vec<u8> {
         let input_value = u64::from_be_bytes(plaintext_block.try_into().expect("block must be 8 bytes"));
         let round_key_value = u64::from_be_bytes(round_key.try_into().expect("round_key must be 8 bytes"));
-        let output_value = round_function(input_value, round_key_value);
+        let output_value = match self.variant() {
+            Variant::Standard => f(input_value, round_key_value),
+            Variant::Educational => round_function(input_value, round_key_value),
+        };
         output_value.to_be_bytes().to_vec()
     }
 }
\ No newline at end of file
@@ -1,68 +1,143 @@
+use symmetric_cipher::crypto::cipher_error::CipherError;
 use symmetric_cipher::crypto::cipher_traits::{
     CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds,
 };
-use crate::crypto::key_schedule::expand_key;
-use crate::crypto::f_function::round_function;
+use crate::crypto::key_schedule::{check_key_length, expand_key, expand_key_educational, KEY_SIZES};
+use crate::crypto::f_function::{f, round_function};
+use symmetric_cipher::crypto::registry::{BoxedCipher, CipherDescriptor};
+
+pub const DESCRIPTOR: CipherDescriptor = CipherDescriptor {
+    name: "loki97",
+    key_sizes: KEY_SIZES,
+    block_size: 16,
+    default_rounds: 16,
+    constructor: |key| Ok(Box::new(Loki97Cipher::new(key)?) as BoxedCipher),
+};
+
+/// Вариант алгоритма.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// LOKI97 по спецификации заявки на AES: 48 подключей, по три на раунд,
+    /// R_i = L_i-1 ^ f(R_i-1 + SK1, SK2), L_i = R_i-1 + SK1 + SK3.
+    #[default]
+    Standard,
+    /// Упрощённая учебная сеть Фейстеля: 16 подключей, по одному на раунд.
+    /// С другими реализациями LOKI97 не совместима.
+    Educational,
+}
+
+impl Variant {
+    fn subkeys(self) -> usize {
+        match self {
+            Variant::Standard => 48,
+            Variant::Educational => 16,
+        }
+    }
+
+    fn expand(self, master_key: &[u8]) -> Result<Vec<u64>, CipherError> {
+        match self {
+            Variant::Standard => expand_key(master_key),
+            Variant::Educational => {
+                check_key_length(master_key)?;
+                Ok(expand_key_educational(master_key).into_iter().take(16).collect())
+            }
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Loki97Cipher {
     round_keys: Vec<u64>,
+    variant: Variant,
 }
 
 impl Loki97Cipher {
-    pub fn new(master_key: &[u8]) -> Self {
-        let all_keys = expand_key(master_key);
-        assert!(all_keys.len() >= 16, "Key schedule must produce ≥16 words");
-        let rk = all_keys.into_iter().take(16).collect();
-        Loki97Cipher { round_keys: rk }
+    /// Ключ 16, 24 или 32 байта, иначе `CipherError::InvalidKeyLength`.
+    pub fn new(master_key: &[u8]) -> Result<Self, CipherError> {
+        Self::with_variant(master_key, Variant::default())
     }
 
-    fn feistel_encrypt_block(&self, block: &[u8]) -> Vec<u8> {
-        assert_eq!(block.len(), 16, "Block must be 16 bytes (128 bits)");
-
-        let mut left = block[0..8].to_vec();
-        let mut right = block[8..16].to_vec();
-
-        for &sk in &self.round_keys {
-            let r_u64 = u64::from_be_bytes(right.clone().try_into().unwrap());
-            let f_out = round_function(r_u64, sk);
-
-            let f_bytes = f_out.to_be_bytes();
+    pub fn with_variant(master_key: &[u8], variant: Variant) -> Result<Self, CipherError> {
+        Ok(Loki97Cipher {
+            round_keys: variant.expand(master_key)?,
+            variant,
+        })
+    }
 
-            let new_right: Vec<u8> = left.iter()
-                .zip(f_bytes.iter())
-                .map(|(a, b)| a ^ b)
-                .collect();
+    /// Учебный вариант, см. [`Variant::Educational`].
+    pub fn educational(master_key: &[u8]) -> Result<Self, CipherError> {
+        Self::with_variant(master_key, Variant::Educational)
+    }
 
-            left = right;
-            right = new_right;
-        }
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
 
-        [right, left].concat()
+    fn feistel_encrypt_block(&self, block: &[u8]) -> Vec<u8> {
+        let mut out = block.to_vec();
+        self.feistel_encrypt_in_place(&mut out);
+        out
     }
 
     fn feistel_decrypt_block(&self, block: &[u8]) -> Vec<u8> {
-        assert_eq!(block.len(), 16, "Block must be 16 bytes (128 bits)");
+        let mut out = block.to_vec();
+        self.feistel_decrypt_in_place(&mut out);
+        out
+    }
 
-        let mut right = block[0..8].to_vec();
-        let mut left = block[8..16].to_vec();
+    fn feistel_encrypt_in_place(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "Block must be 16 bytes (128 bits)");
 
-        for &sk in self.round_keys.iter().rev() {
-            let l_u64 = u64::from_be_bytes(left.clone().try_into().unwrap());
-            let f_out = round_function(l_u64, sk);
+        let mut left = u64::from_be_bytes(block[0..8].try_into().unwrap());
+        let mut right = u64::from_be_bytes(block[8..16].try_into().unwrap());
+
+        match self.variant {
+            Variant::Standard => {
+                for sk in self.round_keys.chunks_exact(3) {
+                    let t = right.wrapping_add(sk[0]);
+                    let new_right = left ^ f(t, sk[1]);
+                    left = t.wrapping_add(sk[2]);
+                    right = new_right;
+                }
+            }
+            Variant::Educational => {
+                for &sk in &self.round_keys {
+                    let new_right = left ^ round_function(right, sk);
+                    left = right;
+                    right = new_right;
+                }
+            }
+        }
 
-            let f_bytes = f_out.to_be_bytes();
+        block[0..8].copy_from_slice(&right.to_be_bytes());
+        block[8..16].copy_from_slice(&left.to_be_bytes());
+    }
 
-            let new_left: Vec<u8> = right.iter()
-                .zip(f_bytes.iter())
-                .map(|(a, b)| a ^ b)
-                .collect();
+    fn feistel_decrypt_in_place(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "Block must be 16 bytes (128 bits)");
 
-            right = left;
-            left = new_left;
+        let mut right = u64::from_be_bytes(block[0..8].try_into().unwrap());
+        let mut left = u64::from_be_bytes(block[8..16].try_into().unwrap());
+
+        match self.variant {
+            Variant::Standard => {
+                for sk in self.round_keys.chunks_exact(3).rev() {
+                    let t = left.wrapping_sub(sk[2]);
+                    left = right ^ f(t, sk[1]);
+                    right = t.wrapping_sub(sk[0]);
+                }
+            }
+            Variant::Educational => {
+                for &sk in self.round_keys.iter().rev() {
+                    let new_left = right ^ round_function(left, sk);
+                    right = left;
+                    left = new_left;
+                }
+            }
         }
 
-        [left, right].concat()
+        block[0..8].copy_from_slice(&left.to_be_bytes());
+        block[8..16].copy_from_slice(&right.to_be_bytes());
     }
 }
 
@@ -87,20 +162,22 @@ impl CipherAlgorithm for Loki97Cipher {
 }
 
 impl SymmetricCipher for Loki97Cipher {
-    fn set_key(&mut self, master_key: &[u8]) -> Result<(), &'static str> {
-        if master_key.len() > 32 {
-            return Err("Key too long (max 32 bytes)");
-        }
-        let all_keys = expand_key(master_key);
-        assert!(all_keys.len() >= 16);
-        self.round_keys = all_keys.into_iter().take(16).collect();
+    fn set_key(&mut self, master_key: &[u8]) -> Result<(), CipherError> {
+        self.round_keys = self.variant.expand(master_key)?;
         Ok(())
     }
+
+    fn key_sizes(&self) -> &'static [usize] {
+        KEY_SIZES
+    }
 }
 
 impl SymmetricCipherWithRounds for Loki97Cipher {
+    /// `raw` — подключи в формате [`export_round_keys`](Self::export_round_keys):
+    /// 48 слов для стандартного варианта, 16 для учебного.
     fn set_key_with_rounds(&mut self, raw: &[u8]) {
-        assert_eq!(raw.len(), 16 * 8, "Expected 128 bytes of round keys");
+        let expected = self.variant.subkeys() * 8;
+        assert_eq!(raw.len(), expected, "Expected {} bytes of round keys", expected);
         self.round_keys = raw
             .chunks_exact(8)
             .map(|b| u64::from_be_bytes(b.try_into().unwrap()))
@@ -123,6 +200,14 @@ impl SymmetricCipherWithRounds for Loki97Cipher {
         16
     }
 
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        self.feistel_encrypt_in_place(block);
+    }
+
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        self.feistel_decrypt_in_place(block);
+    }
+
     fn export_round_keys(&self) -> Option<Vec<u8>> {
         Some(self
             .round_keys
@@ -1,5 +1,5 @@
 pub mod sboxes;
-mod permutation;
+pub mod permutation;
 pub mod loki97;
 pub mod key_schedule;
 pub mod f_function;
\ No newline at end of file
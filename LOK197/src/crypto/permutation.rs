@@ -1,6 +1,32 @@
+use std::sync::OnceLock;
+
 pub const P: [u8; 64] = [
     56,48,40,32,24,16, 8, 0, 57,49,41,33,25,17, 9, 1,
     58,50,42,34,26,18,10, 2, 59,51,43,35,27,19,11, 3,
     60,52,44,36,28,20,12, 4, 61,53,45,37,29,21,13, 5,
     62,54,46,38,30,22,14, 6, 63,55,47,39,31,23,15, 7,
-];
\ No newline at end of file
+];
+
+/// P без таблиц: бит 63 - i переходит в бит P[i].
+pub fn permute_generic(x: u64) -> u64 {
+    P.iter()
+        .enumerate()
+        .fold(0, |acc, (i, &p)| acc | ((x >> (63 - i)) & 1) << p)
+}
+
+/// Образы P для каждого значения каждого байта (байт 0 — старший).
+fn tables() -> &'static [[u64; 256]; 8] {
+    static TABLES: OnceLock<[[u64; 256]; 8]> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        std::array::from_fn(|byte| std::array::from_fn(|v| permute_generic((v as u64) << (56 - 8 * byte))))
+    })
+}
+
+/// P через таблицы: восемь обращений и XOR.
+pub fn permute(x: u64) -> u64 {
+    let t = tables();
+    x.to_be_bytes()
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (byte, &v)| acc ^ t[byte][v as usize])
+}
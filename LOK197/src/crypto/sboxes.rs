@@ -1,17 +1,54 @@
-pub fn s1(input: u16) -> u8 {
+use std::sync::OnceLock;
+
+/// S1 без таблицы: куб (x ^ 0x1FFF) в GF(2^13) по модулю 0x2911, младший байт.
+pub fn s1_generic(input: u16) -> u8 {
     let preprocessed = (input as u32) ^ 0x1FFF;
-    let squared = multiply_polynomials(preprocessed, preprocessed);
+    // Квадрат приводится сразу: куб 13-битного многочлена не влез бы в u32.
+    let squared = modular_reduce(multiply_polynomials(preprocessed, preprocessed), 0x2911, 13);
     let cubed = multiply_polynomials(squared, preprocessed);
     (modular_reduce(cubed, 0x2911, 13) & 0xFF) as u8
 }
 
-pub fn s2(input: u16) -> u8 {
+/// S2 без таблицы: куб (x ^ 0x7FF) в GF(2^11) по модулю 0xAA7, младший байт.
+pub fn s2_generic(input: u16) -> u8 {
     let preprocessed = (input as u32) ^ 0x07FF;
-    let squared = multiply_polynomials(preprocessed, preprocessed);
+    let squared = modular_reduce(multiply_polynomials(preprocessed, preprocessed), 0x0AA7, 11);
     let cubed = multiply_polynomials(squared, preprocessed);
     (modular_reduce(cubed, 0x0AA7, 11) & 0xFF) as u8
 }
 
+/// Таблица S1 на 8192 входа, строится при первом обращении.
+pub fn s1_table() -> &'static [u8; 0x2000] {
+    static TABLE: OnceLock<[u8; 0x2000]> = OnceLock::new();
+    TABLE.get_or_init(|| std::array::from_fn(|x| s1_generic(x as u16)))
+}
+
+/// Таблица S2 на 2048 входов.
+pub fn s2_table() -> &'static [u8; 0x800] {
+    static TABLE: OnceLock<[u8; 0x800]> = OnceLock::new();
+    TABLE.get_or_init(|| std::array::from_fn(|x| s2_generic(x as u16)))
+}
+
+/// Индекс в таблице для входа шире `degree` бит: многочлен x ^ mask
+/// приводится по модулю, как это делает s1_generic/s2_generic.
+fn table_index(input: u16, modulus: u32, degree: u8) -> usize {
+    let mask = (1u32 << degree) - 1;
+    let input = input as u32;
+    if input <= mask {
+        input as usize
+    } else {
+        (modular_reduce(input ^ mask, modulus, degree) ^ mask) as usize
+    }
+}
+
+pub fn s1(input: u16) -> u8 {
+    s1_table()[table_index(input, 0x2911, 13)]
+}
+
+pub fn s2(input: u16) -> u8 {
+    s2_table()[table_index(input, 0x0AA7, 11)]
+}
+
 pub fn multiply_polynomials(mut left: u32, mut right: u32) -> u32 {
     let mut result = 0;
     while right != 0 {
@@ -1,8 +1,16 @@
+#![allow(non_snake_case)]
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
 pub mod crypto;
+pub use crypto::loki97::Loki97Cipher;
 pub use symmetric_cipher::crypto as symmetric_crypto;
+pub use symmetric_cipher::crypto::cipher_error::CipherError;
+
+/// Adds LOKI97 to `registry`.
+pub fn register(registry: &mut symmetric_cipher::crypto::registry::CipherRegistry) -> Result<(), CipherError> {
+    registry.register(crypto::loki97::DESCRIPTOR)
+}
 
 #[cfg(test)]
 mod tests {
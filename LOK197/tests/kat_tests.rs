@@ -0,0 +1,13 @@
+use LOK197::crypto::loki97::DESCRIPTOR;
+use std::path::Path;
+use symmetric_cipher::crypto::kat;
+
+#[test]
+fn test_official_triple() {
+    // Единственный опубликованный вектор: шифрование и расшифрование.
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/ecb_tbl.txt");
+    let report = kat::run_file(&path, &DESCRIPTOR.constructor).unwrap();
+    assert_eq!(report.checked, 2);
+    let failures: Vec<String> = report.failures.iter().map(ToString::to_string).collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -1,36 +1,91 @@
-use LOK197::crypto::loki97::Loki97Cipher;
+use LOK197::crypto::loki97::{Loki97Cipher, Variant};
+use symmetric_cipher::crypto::cipher_error::CipherError;
 use symmetric_cipher::crypto::cipher_traits::{
     CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds,
 };
 
 #[test]
 fn test_block_size_is_correct() {
-    let cipher = Loki97Cipher::new(&[0u8; 16]);
+    let cipher = Loki97Cipher::new(&[0u8; 16]).unwrap();
     assert_eq!(cipher.block_size(), 16); // Теперь 16 байт (128 бит)
 }
 
 #[test]
 fn test_export_round_keys_len() {
-    let cipher = Loki97Cipher::new(&[0u8; 16]);
+    let cipher = Loki97Cipher::new(&[0u8; 16]).unwrap();
     let rk = cipher.export_round_keys().unwrap();
-    assert_eq!(rk.len(), 16 * 8);
+    // 48 подключей, по три на раунд
+    assert_eq!(rk.len(), 48 * 8);
+    assert_eq!(Loki97Cipher::educational(&[0u8; 16]).unwrap().export_round_keys().unwrap().len(), 16 * 8);
 }
 
 #[test]
 fn test_set_key_with_rounds_does_not_panic() {
-    let mut cipher = Loki97Cipher::new(&[0u8; 16]);
+    let mut cipher = Loki97Cipher::new(&[0u8; 16]).unwrap();
+    cipher.set_key_with_rounds(&[1u8; 48 * 8]);
+    let mut cipher = Loki97Cipher::educational(&[0u8; 16]).unwrap();
     cipher.set_key_with_rounds(&[1u8; 16 * 8]);
 }
 
+#[test]
+#[should_panic]
+fn test_set_key_with_rounds_checks_length() {
+    let mut cipher = Loki97Cipher::new(&[0u8; 16]).unwrap();
+    cipher.set_key_with_rounds(&[1u8; 16 * 8]);
+}
+
+#[test]
+fn test_all_key_sizes_round_trip() {
+    let plaintext: Vec<u8> = (0..64).collect();
+    for len in [16, 24, 32] {
+        for variant in [Variant::Standard, Variant::Educational] {
+            let key: Vec<u8> = (0..len as u8).map(|b| b.wrapping_mul(37)).collect();
+            let cipher = Loki97Cipher::with_variant(&key, variant).unwrap();
+            assert_eq!(cipher.variant(), variant);
+            let ciphertext = cipher.encrypt(&plaintext);
+            assert_ne!(ciphertext, plaintext);
+            assert_eq!(cipher.decrypt(&ciphertext), plaintext, "{:?}, ключ {} байт", variant, len);
+        }
+    }
+}
+
+#[test]
+fn test_educational_variant_differs_from_standard() {
+    let key = [0x5Au8; 16];
+    let block = [0x11u8; 16];
+    let mut educational = Loki97Cipher::educational(&key).unwrap();
+    assert_ne!(educational.encrypt(&block), Loki97Cipher::new(&key).unwrap().encrypt(&block));
+
+    // Вариант сохраняется при смене ключа
+    educational.set_key(&[0x33; 24]).unwrap();
+    assert_eq!(educational.variant(), Variant::Educational);
+    assert_eq!(educational.encrypt(&block), Loki97Cipher::educational(&[0x33; 24]).unwrap().encrypt(&block));
+}
+
+#[test]
+fn test_invalid_key_length_is_an_error() {
+    // Ни конструктор, ни set_key не паникуют на ключе неверной длины.
+    for variant in [Variant::Standard, Variant::Educational] {
+        assert!(matches!(
+            Loki97Cipher::with_variant(&[0u8; 33], variant),
+            Err(CipherError::InvalidKeyLength { actual: 33, .. })
+        ));
+    }
+    assert!(matches!(Loki97Cipher::new(&[]), Err(CipherError::InvalidKeyLength { actual: 0, .. })));
+
+    let mut cipher = Loki97Cipher::new(&[0u8; 16]).unwrap();
+    assert!(matches!(cipher.set_key(&[0u8; 20]), Err(CipherError::InvalidKeyLength { .. })));
+}
+
 #[test]
 fn test_set_key_valid_key() {
-    let mut cipher = Loki97Cipher::new(&[0u8; 16]);
+    let mut cipher = Loki97Cipher::new(&[0u8; 16]).unwrap();
     assert!(cipher.set_key(&[1u8; 16]).is_ok());
 }
 
 #[test]
 fn test_encrypt_decrypt_block_round_keys() {
-    let cipher = Loki97Cipher::new(&[0u8; 32]);
+    let cipher = Loki97Cipher::new(&[0u8; 32]).unwrap();
     let rk = cipher.export_round_keys().unwrap();
 
     // 16-байтный блок (128 бит)
@@ -43,7 +98,7 @@ fn test_encrypt_decrypt_block_round_keys() {
 
 #[test]
 fn test_encrypt_decrypt_full_data() {
-    let cipher = Loki97Cipher::new(&[0x10u8; 32]);
+    let cipher = Loki97Cipher::new(&[0x10u8; 32]).unwrap();
     // 32 байта (2 блока по 16 байт)
     let plaintext = vec![0xAAu8; 32];
     let ciphertext = cipher.encrypt(&plaintext);
@@ -54,8 +109,8 @@ fn test_encrypt_decrypt_full_data() {
 
 #[test]
 fn test_different_keys_produce_different_ciphertexts() {
-    let c1 = Loki97Cipher::new(&[0u8; 32]);
-    let c2 = Loki97Cipher::new(&[1u8; 32]);
+    let c1 = Loki97Cipher::new(&[0u8; 32]).unwrap();
+    let c2 = Loki97Cipher::new(&[1u8; 32]).unwrap();
     // 16-байтный блок (128 бит)
     let plaintext = [1u8; 16];
 
@@ -63,4 +118,24 @@ fn test_different_keys_produce_different_ciphertexts() {
     let ct2 = c2.encrypt_block(&plaintext, &c2.export_round_keys().unwrap());
 
     assert_ne!(ct1, ct2);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_inplace_matches_encrypt_block() {
+    let cipher = Loki97Cipher::new(&[0x42u8; 24]).unwrap();
+    let rk = cipher.export_round_keys().unwrap();
+    let plaintext: Vec<u8> = (0..48).collect();
+
+    // Три блока одним вызовом
+    let mut blocks = plaintext.clone();
+    cipher.encrypt_blocks(&mut blocks);
+    let expected: Vec<u8> = plaintext
+        .chunks(16)
+        .flat_map(|b| cipher.encrypt_block(b, &rk))
+        .collect();
+    assert_eq!(blocks, expected);
+
+    let mut block = blocks[16..32].to_vec();
+    cipher.decrypt_block_inplace(&mut block);
+    assert_eq!(block, &plaintext[16..32]);
+}
@@ -0,0 +1,19 @@
+use LOK197::crypto::permutation::{permute, permute_generic, P};
+use rand::RngCore;
+
+#[test]
+fn test_single_bits() {
+    // Бит 63 - i переходит в бит P[i]
+    for (i, &p) in P.iter().enumerate() {
+        assert_eq!(permute(1 << (63 - i)), 1 << p);
+    }
+}
+
+#[test]
+fn test_tables_match_generic() {
+    let mut rng = rand::rng();
+    for _ in 0..10_000 {
+        let x = rng.next_u64();
+        assert_eq!(permute(x), permute_generic(x), "P({:#018x})", x);
+    }
+}
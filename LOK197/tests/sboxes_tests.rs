@@ -1,29 +1,49 @@
-use LOK197::crypto::sboxes::{multiply_polynomials, modular_reduce, s1, s2};
+use LOK197::crypto::sboxes::{multiply_polynomials, modular_reduce, s1, s1_generic, s1_table, s2, s2_generic, s2_table};
 
 fn s1_expected(x: u16) -> u8 {
     let v  = (x as u32) ^ 0x1FFF;
-    let v2 = multiply_polynomials(v, v);
+    let v2 = modular_reduce(multiply_polynomials(v, v), 0x2911, 13);
     let v3 = multiply_polynomials(v2, v);
     (modular_reduce(v3, 0x2911, 13) & 0xFF) as u8
 }
 
 fn s2_expected(x: u16) -> u8 {
     let v  = (x as u32) ^ 0x07FF;
-    let v2 = multiply_polynomials(v, v);
+    let v2 = modular_reduce(multiply_polynomials(v, v), 0x0AA7, 11);
     let v3 = multiply_polynomials(v2, v);
     (modular_reduce(v3, 0x0AA7, 11) & 0xFF) as u8
 }
 
 #[test]
 fn test_s1_against_expected() {
-    for &x in &[0x0000, 0x0001, 0x1000, 0x1FFF] {
+    for x in 0..0x2000 {
         assert_eq!(s1(x), s1_expected(x));
     }
 }
 
 #[test]
 fn test_s2_against_expected() {
-    for &x in &[0x0000, 0x0001, 0x0700, 0x07FF] {
+    for x in 0..0x800 {
         assert_eq!(s2(x), s2_expected(x));
     }
 }
+
+#[test]
+fn test_sbox_reference_values() {
+    // Значения таблиц эталонной реализации: куб 13/11-битного многочлена
+    // должен приводиться по модулю, а не обрезаться до 32 бит.
+    assert_eq!(s1(0x1234), 0x07);
+    assert_eq!(s2(0x0567), 0x6B);
+}
+
+#[test]
+fn test_tables_match_generic() {
+    assert_eq!(s1_table().len(), 8192);
+    assert_eq!(s2_table().len(), 2048);
+    // Табличные S1/S2 совпадают с вычислением для любого u16, в том числе
+    // для входов шире 13/11 бит, которые приводятся по модулю.
+    for x in 0..=u16::MAX {
+        assert_eq!(s1(x), s1_generic(x), "S1({:#06x})", x);
+        assert_eq!(s2(x), s2_generic(x), "S2({:#06x})", x);
+    }
+}
@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::RngCore;
+use symmetric_cipher::crypto::cipher_traits::SymmetricCipherWithRounds;
+use twofish::crypto::keying::Keying;
+use twofish::crypto::twofish::Twofish;
+
+const MODES: [Keying; 4] = [Keying::Full, Keying::Partial, Keying::Minimal, Keying::Zero];
+
+fn bench_keying(c: &mut Criterion) {
+    let mut data = vec![0u8; 16 * 1024];
+    rand::rng().fill_bytes(&mut data);
+
+    let mut group = c.benchmark_group("Twofish-256 ECB 16KB");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    for mode in MODES {
+        let cipher = Twofish::with_keying(&[0x2b; 32], mode).unwrap();
+
+        group.bench_function(BenchmarkId::new("encrypt", format!("{:?}", mode)), |b| {
+            b.iter(|| cipher.encrypt_blocks(&mut data))
+        });
+        group.bench_function(BenchmarkId::new("decrypt", format!("{:?}", mode)), |b| {
+            b.iter(|| cipher.decrypt_blocks(&mut data))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_key_setup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Twofish-256 key setup");
+    for mode in MODES {
+        group.bench_function(format!("{:?}", mode), |b| b.iter(|| Twofish::with_keying(&[0x2b; 32], mode).unwrap()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_keying, bench_key_setup);
+criterion_main!(benches);
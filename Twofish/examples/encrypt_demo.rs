@@ -1,5 +1,4 @@
 use std::fs;
-use tokio;
 
 use rand::SeedableRng;
 use rand::{rngs::StdRng, RngCore};
@@ -11,7 +10,7 @@ use twofish::crypto::mds::mds_multiply;
 
 use symmetric_cipher::crypto::cipher_context::CipherContext;
 use symmetric_cipher::crypto::cipher_traits::{
-    CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds
+    CipherAlgorithm, SymmetricCipherWithRounds
 };
 use symmetric_cipher::crypto::cipher_types::{
     CipherInput, CipherMode, CipherOutput, PaddingMode,
@@ -54,7 +53,7 @@ async fn main() -> std::io::Result<()> {
         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
     ];
 
-    let mut cipher = Twofish::new(&key128);
+    let cipher = Twofish::new(&key128).unwrap();
 
     let round_keys_bytes = cipher.export_round_keys().unwrap();
     println!(" First round key (bytes): {:02x?}", &round_keys_bytes[0..4]);
@@ -99,7 +98,7 @@ async fn main() -> std::io::Result<()> {
         0xB6, 0xBF, 0xEC, 0x2F, 0x2A, 0xE8, 0xC3, 0x5A
     ];
 
-    let cipher_128 = Twofish::new(&test_key_128);
+    let cipher_128 = Twofish::new(&test_key_128).unwrap();
     let result_128 = cipher_128.encrypt(&test_plain_128);
     println!(" 128-bit key test: {:?}", result_128 == expected_cipher_128);
 
@@ -114,7 +113,7 @@ async fn main() -> std::io::Result<()> {
         0x8F, 0x28, 0x9D, 0x02, 0xB9, 0x04, 0x1F, 0xDB
     ];
 
-    let cipher_192 = Twofish::new(&test_key_192);
+    let cipher_192 = Twofish::new(&test_key_192).unwrap();
     let result_192 = cipher_192.encrypt(&test_plain_128);
     println!(" 192-bit key test: {:?}", result_192 == expected_cipher_192);
 
@@ -130,7 +129,7 @@ async fn main() -> std::io::Result<()> {
         0xF5, 0xDD, 0xF4, 0xC3, 0x3B, 0x97, 0xA2, 0x05
     ];
 
-    let cipher_256 = Twofish::new(&test_key_256);
+    let cipher_256 = Twofish::new(&test_key_256).unwrap();
     let result_256 = cipher_256.encrypt(&test_plain_128);
     println!(" 256-bit key test: {:?}", result_256 == expected_cipher_256);
 
@@ -151,18 +150,18 @@ async fn main() -> std::io::Result<()> {
                     Some(vec![0u8; 16]) // Twofish использует 16-байтные блоки
                 };
                 let mut ctx = CipherContext::new(
-                    Box::new(Twofish::new(&key)) as _,
+                    Box::new(Twofish::new(&key).unwrap()) as _,
                     mode, pad, iv.clone(), Vec::new()
                 );
                 ctx.set_key(&key).unwrap();
 
                 // encrypt
-                let mut out_enc = CipherOutput::Buffer(Box::new(Vec::new()));
+                let mut out_enc = CipherOutput::Buffer(Box::default());
                 ctx.encrypt(CipherInput::Bytes(data.clone()), &mut out_enc).await.unwrap();
                 let cipher = out_enc.as_buffer().clone();
 
                 // decrypt
-                let mut out_dec = CipherOutput::Buffer(Box::new(Vec::new()));
+                let mut out_dec = CipherOutput::Buffer(Box::default());
                 ctx.decrypt(CipherInput::Bytes(cipher.clone()), &mut out_dec).await.unwrap();
                 let plain = out_dec.as_buffer();
                 assert_eq!(plain, &data);
@@ -225,7 +224,7 @@ async fn main() -> std::io::Result<()> {
                     Some(vec![0u8; 16]) // 16-байтные блоки для Twofish
                 };
                 let mut ctx = CipherContext::new(
-                    Box::new(Twofish::new(&key)) as _,
+                    Box::new(Twofish::new(&key).unwrap()) as _,
                     mode, pad, iv.clone(), Vec::new()
                 );
                 ctx.set_key(&key).unwrap();
@@ -278,7 +277,7 @@ async fn main() -> std::io::Result<()> {
     println!("\n=== Performance with different rounds ===");
     let perf_key = [0x55u8; 32];
     let perf_data = vec![0xAAu8; 1024 * 16]; // 16KB
-    let cipher = Twofish::new(&perf_key);
+    let cipher = Twofish::new(&perf_key).unwrap();
 
     for rounds in [1, 4, 8, 12, 16] {
         let start = std::time::Instant::now();
@@ -1,10 +1,14 @@
+/// Умножение в поле MDS-матрицы, x^8 + x^6 + x^5 + x^3 + 1.
 pub fn gf_mul(a: u8, b: u8) -> u8 {
+    gf_mul_mod(a, b, 0x69)
+}
+
+/// Умножение по модулю x^8 + `reduction` (младшие биты многочлена).
+pub fn gf_mul_mod(a: u8, b: u8, reduction: u8) -> u8 {
     let mut result: u8 = 0;
     let mut a_val = a;
     let mut b_val = b;
 
-    const POLYNOMIAL: u8 = 0x69;
-
     for _ in 0..8 {
         if b_val & 1 != 0 {
             result ^= a_val;
@@ -13,7 +17,7 @@ pub fn gf_mul(a: u8, b: u8) -> u8 {
         let high_bit = a_val & 0x80;
         a_val <<= 1;
         if high_bit != 0 {
-            a_val ^= POLYNOMIAL;
+            a_val ^= reduction;
         }
         b_val >>= 1;
     }
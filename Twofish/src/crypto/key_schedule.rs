@@ -1,62 +1,123 @@
 use symmetric_cipher::crypto::encryption_transformation::EncryptionTransformation;
 use crate::crypto::twofish::Twofish;
 use symmetric_cipher::crypto::key_expansion::KeyExpansion;
+use crate::crypto::gf256::gf_mul_mod;
 use crate::crypto::mds::mds_multiply;
-use crate::crypto::utils::{rotate_left, rotate_right};
+use crate::crypto::utils::rotate_left;
 use crate::crypto::sboxes::{q0, q1};
 
-fn h(x: u32, key_bytes: &[u8], offset: usize) -> u32 {
-    let b0 = (x & 0xFF) as u8;
-    let b1 = ((x >> 8) & 0xFF) as u8;
-    let b2 = ((x >> 16) & 0xFF) as u8;
-    let b3 = ((x >> 24) & 0xFF) as u8;
-
-    let mut y0 = q1(q0(q0(b0) ^ key_bytes[offset]) ^ key_bytes[offset + 8]);
-    let mut y1 = q0(q0(q1(b1) ^ key_bytes[offset + 1]) ^ key_bytes[offset + 9]);
-    let mut y2 = q1(q1(q0(b2) ^ key_bytes[offset + 2]) ^ key_bytes[offset + 10]);
-    let mut y3 = q0(q1(q1(b3) ^ key_bytes[offset + 3]) ^ key_bytes[offset + 11]);
-
-    if key_bytes.len() >= 24 {
-        y0 = q1(y0 ^ key_bytes[offset + 16]);
-        y1 = q0(y1 ^ key_bytes[offset + 17]);
-        y2 = q1(y2 ^ key_bytes[offset + 18]);
-        y3 = q0(y3 ^ key_bytes[offset + 19]);
-
-        // Если ключ длиннее 192 бит
-        if key_bytes.len() >= 32 {
-            y0 = q0(y0 ^ key_bytes[offset + 24]);
-            y1 = q1(y1 ^ key_bytes[offset + 25]);
-            y2 = q0(y2 ^ key_bytes[offset + 26]);
-            y3 = q1(y3 ^ key_bytes[offset + 27]);
-        }
+/// Reed–Solomon (12, 8) код над GF(2^8) по модулю x^8 + x^6 + x^3 + x^2 + 1.
+const RS: [[u8; 8]; 4] = [
+    [0x01, 0xA4, 0x55, 0x87, 0x5A, 0x58, 0xDB, 0x9E],
+    [0xA4, 0x56, 0x82, 0xF3, 0x1E, 0xC6, 0x68, 0xE5],
+    [0x02, 0xA1, 0xFC, 0xC1, 0x47, 0xAE, 0x3D, 0x19],
+    [0xA4, 0x55, 0x87, 0x5A, 0x58, 0xDB, 0x9E, 0x03],
+];
+
+const RS_REDUCTION: u8 = 0x4D;
+
+const RHO: u32 = 0x01010101;
+
+/// Допустимые длины ключа в байтах: от 1 до 32, короткие ключи
+/// дополняются нулями.
+pub const KEY_SIZES: &[usize] = &{
+    let mut sizes = [0; 32];
+    let mut i = 0;
+    while i < sizes.len() {
+        sizes[i] = i + 1;
+        i += 1;
     }
+    sizes
+};
 
-    let word = ((y0 as u32) << 24) | ((y1 as u32) << 16) | ((y2 as u32) << 8) | (y3 as u32);
-    mds_multiply(word)
+/// Допустимая длина ключа: от 1 до 32 байт.
+pub fn is_valid_key_length(len: usize) -> bool {
+    KEY_SIZES.contains(&len)
 }
 
-pub fn expand_key(master_key: &[u8]) -> Vec<u32> {
-    if master_key.len() != 16 && master_key.len() != 24 && master_key.len() != 32 {
-        return Vec::new();
+/// Ключ, дополненный нулями до ближайшей из длин 128, 192 или 256 бит.
+pub fn pad_key(master_key: &[u8]) -> Vec<u8> {
+    let len = match master_key.len() {
+        0..=16 => 16,
+        17..=24 => 24,
+        _ => 32,
+    };
+    let mut key = master_key.to_vec();
+    key.resize(len, 0);
+    key
+}
+
+/// Слово S_i = RS · (m_8i, ..., m_8i+7), байты в порядке little-endian.
+pub fn rs_multiply(m: &[u8]) -> u32 {
+    let row = |r: &[u8; 8]| r.iter().zip(m).fold(0, |acc, (&c, &x)| acc ^ gf_mul_mod(c, x, RS_REDUCTION));
+    u32::from_le_bytes([row(&RS[0]), row(&RS[1]), row(&RS[2]), row(&RS[3])])
+}
+
+type Permutation = fn(u8) -> u8;
+
+/// Перестановки цепочки h для каждого байта слова, от входа к выходу. При
+/// k словах ключа используются последние k + 1 из них.
+const Q_CHAIN: [[Permutation; 5]; 4] = [
+    [q1, q1, q0, q0, q1],
+    [q0, q1, q1, q0, q0],
+    [q0, q0, q0, q1, q1],
+    [q1, q0, q1, q1, q0],
+];
+
+fn key_byte(word: u32, i: usize) -> u8 {
+    (word >> (8 * i)) as u8
+}
+
+/// Первый слой цепочки байта `i`: перестановка и байт последнего слова `l`.
+pub fn first_layer(i: usize, x: u8, l: &[u32]) -> u8 {
+    let k = l.len();
+    Q_CHAIN[i][4 - k](x) ^ key_byte(l[k - 1], i)
+}
+
+/// Слои цепочки байта `i`, начиная с `from`, и выходная перестановка.
+pub fn sbox_layers(i: usize, from: usize, mut y: u8, l: &[u32]) -> u8 {
+    let k = l.len();
+    for j in from..k {
+        y = Q_CHAIN[i][4 - k + j](y) ^ key_byte(l[k - 1 - j], i);
     }
+    Q_CHAIN[i][4](y)
+}
 
-    let mut round_keys = Vec::with_capacity(40);
+/// Зависящий от ключа S-блок байта `i`: вся цепочка q0/q1.
+pub fn keyed_sbox(i: usize, x: u8, l: &[u32]) -> u8 {
+    sbox_layers(i, 0, x, l)
+}
 
-    const P: u32 = 0x01010101;
+/// Функция h: цепочка перестановок q0/q1 с байтами слов `l` (их 2, 3 или
+/// 4), затем MDS. Входное и выходное слова little-endian.
+pub fn h(x: u32, l: &[u32]) -> u32 {
+    let x = x.to_le_bytes();
+    let y: [u8; 4] = std::array::from_fn(|i| keyed_sbox(i, x[i], l));
 
-    let mut me = [0u32; 4];
-    let mut mo = [0u32; 4];
+    // mds_multiply упаковывает байты старшим вперёд.
+    u32::from_le_bytes(mds_multiply(u32::from_be_bytes(y)).to_be_bytes())
+}
+
+/// Слова ключа для g: (S_k-1, ..., S_0). Ключ должен быть уже дополнен.
+pub fn sbox_key(key: &[u8]) -> Vec<u32> {
+    key.chunks_exact(8).rev().map(rs_multiply).collect()
+}
 
-    for i in 0..master_key.len() / 8 {
-        me[i] = (master_key[8*i] as u32) | ((master_key[8*i + 1] as u32) << 8) |
-            ((master_key[8*i + 2] as u32) << 16) | ((master_key[8*i + 3] as u32) << 24);
-        mo[i] = (master_key[8*i + 4] as u32) | ((master_key[8*i + 5] as u32) << 8) |
-            ((master_key[8*i + 6] as u32) << 16) | ((master_key[8*i + 7] as u32) << 24);
+/// 40 подключей: K0..K7 для отбеливания, остальные по два на раунд.
+/// Пустой вектор для ключа недопустимой длины.
+pub fn expand_key(master_key: &[u8]) -> Vec<u32> {
+    if !is_valid_key_length(master_key.len()) {
+        return Vec::new();
     }
+    let key = pad_key(master_key);
+    let words: Vec<u32> = key.chunks_exact(4).map(|c| u32::from_le_bytes(c.try_into().unwrap())).collect();
+    let me: Vec<u32> = words.iter().step_by(2).copied().collect();
+    let mo: Vec<u32> = words.iter().skip(1).step_by(2).copied().collect();
 
-    for i in 0..20 {
-        let a = h(2 * i as u32 * P, master_key, 0);
-        let b = rotate_left(h((2 * i + 1) as u32 * P, master_key, 4), 8);
+    let mut round_keys = Vec::with_capacity(40);
+    for i in 0..20u32 {
+        let a = h(2 * i * RHO, &me);
+        let b = rotate_left(h((2 * i + 1) * RHO, &mo), 8);
 
         round_keys.push(a.wrapping_add(b));
         round_keys.push(rotate_left(a.wrapping_add(b.wrapping_mul(2)), 9));
@@ -67,10 +128,6 @@ pub fn expand_key(master_key: &[u8]) -> Vec<u32> {
 
 impl KeyExpansion for Twofish {
     fn generate_round_keys(&self, master_key: &[u8]) -> Vec<Vec<u8>> {
-        if master_key.len() != 16 && master_key.len() != 24 && master_key.len() != 32 {
-            return Vec::new();
-        }
-
         let round_keys = expand_key(master_key);
 
         let mut result = Vec::with_capacity(round_keys.len());
@@ -0,0 +1,78 @@
+use crate::crypto::key_schedule::{first_layer, h, keyed_sbox, sbox_layers};
+use crate::crypto::mds::mds_tables;
+
+/// Сколько функции g вычисляется заранее при установке ключа, как в
+/// эталонной реализации. Результат шифрования от режима не зависит: режимы
+/// различаются скоростью, временем установки ключа и памятью.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Keying {
+    /// Четыре таблицы по 256 слов: S-блоки ключа, уже умноженные на
+    /// столбцы MDS. g — четыре обращения к таблицам. 4 КиБ на ключ.
+    #[default]
+    Full,
+    /// Четыре 8-битных S-блока ключа (1 КиБ), MDS по общим таблицам.
+    Partial,
+    /// Только первый слой q с байтом ключа (1 КиБ), остаток цепочки
+    /// вычисляется при каждом вызове.
+    Minimal,
+    /// Ничего не хранится: g вычисляет h по словам S.
+    Zero,
+}
+
+/// Таблицы, построенные для выбранного режима.
+#[derive(Clone)]
+pub enum KeyedTables {
+    Full(Box<[[u32; 256]; 4]>),
+    Partial(Box<[[u8; 256]; 4]>),
+    Minimal(Box<[[u8; 256]; 4]>),
+    Zero,
+}
+
+impl KeyedTables {
+    /// `s` — слова (S_k-1, ..., S_0) ключа g.
+    pub fn new(keying: Keying, s: &[u32]) -> Self {
+        match keying {
+            Keying::Full => {
+                let mds = mds_tables();
+                KeyedTables::Full(Box::new(std::array::from_fn(|i| {
+                    std::array::from_fn(|x| mds[i][keyed_sbox(i, x as u8, s) as usize])
+                })))
+            }
+            Keying::Partial => KeyedTables::Partial(Box::new(std::array::from_fn(|i| {
+                std::array::from_fn(|x| keyed_sbox(i, x as u8, s))
+            }))),
+            Keying::Minimal => KeyedTables::Minimal(Box::new(std::array::from_fn(|i| {
+                std::array::from_fn(|x| first_layer(i, x as u8, s))
+            }))),
+            Keying::Zero => KeyedTables::Zero,
+        }
+    }
+
+    pub fn keying(&self) -> Keying {
+        match self {
+            KeyedTables::Full(_) => Keying::Full,
+            KeyedTables::Partial(_) => Keying::Partial,
+            KeyedTables::Minimal(_) => Keying::Minimal,
+            KeyedTables::Zero => Keying::Zero,
+        }
+    }
+
+    /// g(X) = h(X, S) с теми же словами `s`, что были переданы в `new`.
+    pub fn g(&self, x: u32, s: &[u32]) -> u32 {
+        let b = x.to_le_bytes();
+        match self {
+            KeyedTables::Full(t) => {
+                t[0][b[0] as usize] ^ t[1][b[1] as usize] ^ t[2][b[2] as usize] ^ t[3][b[3] as usize]
+            }
+            KeyedTables::Partial(sbox) => {
+                let mds = mds_tables();
+                (0..4).fold(0, |acc, i| acc ^ mds[i][sbox[i][b[i] as usize] as usize])
+            }
+            KeyedTables::Minimal(first) => {
+                let mds = mds_tables();
+                (0..4).fold(0, |acc, i| acc ^ mds[i][sbox_layers(i, 1, first[i][b[i] as usize], s) as usize])
+            }
+            KeyedTables::Zero => h(x, s),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::OnceLock;
 use crate::crypto::gf256::gf_mul;
 
 /// Преобразование MDS-матрицы над GF(2^8) для 32-битного слова.
@@ -21,4 +22,20 @@ pub fn mds_multiply(word: u32) -> u32 {
     let y3 = gf_mul(z0, 0xEF) ^ gf_mul(z1, 0x01) ^ gf_mul(z2, 0xEF) ^ gf_mul(z3, 0x5B);
 
     ((y0 as u32) << 24) | ((y1 as u32) << 16) | ((y2 as u32) << 8) | (y3 as u32)
-}
\ No newline at end of file
+}
+
+/// Столбцы MDS-матрицы для каждого значения байта: `mds_tables()[i][y]` —
+/// MDS от слова с единственным ненулевым байтом `y` на месте `i`, в порядке
+/// little-endian, как в функции h. Строятся один раз.
+pub fn mds_tables() -> &'static [[u32; 256]; 4] {
+    static TABLES: OnceLock<[[u32; 256]; 4]> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        std::array::from_fn(|i| {
+            std::array::from_fn(|y| {
+                let mut bytes = [0u8; 4];
+                bytes[i] = y as u8;
+                u32::from_le_bytes(mds_multiply(u32::from_be_bytes(bytes)).to_be_bytes())
+            })
+        })
+    })
+}
@@ -1,5 +1,6 @@
 pub mod gf256;
 pub mod key_schedule;
+pub mod keying;
 pub mod mds;
 pub mod pht;
 pub mod sboxes;
@@ -1,68 +1,63 @@
 use crate::crypto::utils::{rotate_left, rotate_right};
-use crate::crypto::sboxes::{q0, q1};
-use crate::crypto::mds::mds_multiply;
-use crate::crypto::key_schedule::expand_key;
+use crate::crypto::key_schedule::{expand_key, is_valid_key_length, pad_key, sbox_key, KEY_SIZES};
+use crate::crypto::keying::{KeyedTables, Keying};
 use crate::crypto::pht::pht;
+use symmetric_cipher::crypto::cipher_error::CipherError;
 use symmetric_cipher::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::registry::{BoxedCipher, CipherDescriptor};
+
+pub const DESCRIPTOR: CipherDescriptor = CipherDescriptor {
+    name: "twofish",
+    key_sizes: KEY_SIZES,
+    block_size: 16,
+    default_rounds: 16,
+    constructor: |key| Ok(Box::new(Twofish::new(key)?) as BoxedCipher),
+};
 
 pub struct Twofish {
-    key: Vec<u8>,
+    /// Слова S из RS-кода, ключ функции g.
+    s: Vec<u32>,
+    tables: KeyedTables,
     round_keys: Vec<u32>,
     rounds: usize,
 }
 
 impl Twofish {
-    pub fn new(key: &[u8]) -> Twofish {
-        let key_len = key.len();
-        if key_len != 16 && key_len != 24 && key_len != 32 {
-            panic!("Invalid key length for Twofish: {}", key_len);
-        }
-        let round_keys = expand_key(key);
-        Twofish {
-            key: key.to_vec(),
-            round_keys,
-            rounds: 16,
-        }
+    /// Ключ любой длины до 256 бит; короче 128, 192 или 256 бит дополняется
+    /// нулями по спецификации. Пустой или более длинный ключ — ошибка.
+    pub fn new(key: &[u8]) -> Result<Twofish, CipherError> {
+        Self::with_keying(key, Keying::default())
     }
 
-    fn g(&self, x: u32) -> u32 {
-        let key_bytes = &self.key;
-        let key_len = key_bytes.len();
-
-        let b0 = (x & 0xFF) as u8;
-        let b1 = ((x >> 8) & 0xFF) as u8;
-        let b2 = ((x >> 16) & 0xFF) as u8;
-        let b3 = (x >> 24) as u8;
-
-        let mut y0 = b0;
-        let mut y1 = b1;
-        let mut y2 = b2;
-        let mut y3 = b3;
-
-        if key_len == 32 {
-            y0 = q1(y0) ^ key_bytes[24];
-            y1 = q0(y1) ^ key_bytes[25];
-            y2 = q0(y2) ^ key_bytes[26];
-            y3 = q1(y3) ^ key_bytes[27];
+    /// Как [`new`](Self::new), с заданным режимом предвычисления g.
+    pub fn with_keying(key: &[u8], keying: Keying) -> Result<Twofish, CipherError> {
+        if !is_valid_key_length(key.len()) {
+            return Err(CipherError::InvalidKeyLength {
+                expected: KEY_SIZES,
+                actual: key.len(),
+            });
         }
+        let s = sbox_key(&pad_key(key));
+        Ok(Twofish {
+            tables: KeyedTables::new(keying, &s),
+            s,
+            round_keys: expand_key(key),
+            rounds: 16,
+        })
+    }
 
-        if key_len >= 24 {
-            y0 = q1(y0) ^ key_bytes[16];
-            y1 = q1(y1) ^ key_bytes[17];
-            y2 = q0(y2) ^ key_bytes[18];
-            y3 = q0(y3) ^ key_bytes[19];
-        }
+    pub fn keying(&self) -> Keying {
+        self.tables.keying()
+    }
 
-        y0 = q1(q0(y0) ^ key_bytes[8]) ^ key_bytes[0];
-        y1 = q0(q0(y1) ^ key_bytes[9]) ^ key_bytes[1];
-        y2 = q1(q1(y2) ^ key_bytes[10]) ^ key_bytes[2];
-        y3 = q0(q1(y3) ^ key_bytes[11]) ^ key_bytes[3];
+    /// Перестраивает таблицы текущего ключа под другой режим.
+    pub fn set_keying(&mut self, keying: Keying) {
+        self.tables = KeyedTables::new(keying, &self.s);
+    }
 
-        let word = ((y0 as u32) << 24)
-            | ((y1 as u32) << 16)
-            | ((y2 as u32) << 8)
-            | (y3 as u32);
-        mds_multiply(word)
+    /// g(X) = h(X, S).
+    fn g(&self, x: u32) -> u32 {
+        self.tables.g(x, &self.s)
     }
 
     fn f_function(&self, r0: u32, r1: u32, round: usize) -> (u32, u32) {
@@ -82,17 +77,39 @@ impl Twofish {
         if plaintext_block.len() != 16 {
             return Vec::new();
         }
+        let mut ciphertext = plaintext_block.to_vec();
+        self.encrypt_in_place(&mut ciphertext);
+        ciphertext
+    }
+
+    pub fn decrypt_block(&self, ciphertext_block: &[u8]) -> Vec<u8> {
+        if ciphertext_block.len() != 16 {
+            return Vec::new();
+        }
+        let mut plaintext = ciphertext_block.to_vec();
+        self.decrypt_in_place(&mut plaintext);
+        plaintext
+    }
 
+    fn load_words(bytes: &[u8]) -> [u32; 4] {
         let mut block = [0u32; 4];
-        for i in 0..4 {
-            block[i] = (plaintext_block[4*i] as u32)
-                | ((plaintext_block[4*i + 1] as u32) << 8)
-                | ((plaintext_block[4*i + 2] as u32) << 16)
-                | ((plaintext_block[4*i + 3] as u32) << 24);
+        for (word, chunk) in block.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        block
+    }
+
+    fn store_words(block: [u32; 4], bytes: &mut [u8]) {
+        for (word, chunk) in block.iter().zip(bytes.chunks_exact_mut(4)) {
+            chunk.copy_from_slice(&word.to_le_bytes());
         }
+    }
 
-        for i in 0..4 {
-            block[i] ^= self.round_keys[i];
+    fn encrypt_in_place(&self, bytes: &mut [u8]) {
+        let mut block = Self::load_words(bytes);
+
+        for (word, key) in block.iter_mut().zip(&self.round_keys[..4]) {
+            *word ^= key;
         }
 
         for r in 0..self.rounds {
@@ -117,35 +134,18 @@ impl Twofish {
         block[2] = temp0;
         block[3] = temp1;
 
-        for i in 0..4 {
-            block[i] ^= self.round_keys[i + 4];
+        for (word, key) in block.iter_mut().zip(&self.round_keys[4..8]) {
+            *word ^= key;
         }
 
-        let mut ciphertext = Vec::with_capacity(16);
-        for i in 0..4 {
-            ciphertext.push(block[i] as u8);
-            ciphertext.push((block[i] >> 8) as u8);
-            ciphertext.push((block[i] >> 16) as u8);
-            ciphertext.push((block[i] >> 24) as u8);
-        }
-        ciphertext
+        Self::store_words(block, bytes);
     }
 
-    pub fn decrypt_block(&self, ciphertext_block: &[u8]) -> Vec<u8> {
-        if ciphertext_block.len() != 16 {
-            return Vec::new();
-        }
+    fn decrypt_in_place(&self, bytes: &mut [u8]) {
+        let mut block = Self::load_words(bytes);
 
-        let mut block = [0u32; 4];
-        for i in 0..4 {
-            block[i] = (ciphertext_block[4*i] as u32)
-                | ((ciphertext_block[4*i + 1] as u32) << 8)
-                | ((ciphertext_block[4*i + 2] as u32) << 16)
-                | ((ciphertext_block[4*i + 3] as u32) << 24);
-        }
-
-        for i in 0..4 {
-            block[i] ^= self.round_keys[i + 4];
+        for (word, key) in block.iter_mut().zip(&self.round_keys[4..8]) {
+            *word ^= key;
         }
 
         let temp0 = block[0];
@@ -170,23 +170,17 @@ impl Twofish {
             block[3] = rotate_right(temp, 1);
         }
 
-        for i in 0..4 {
-            block[i] ^= self.round_keys[i];
+        for (word, key) in block.iter_mut().zip(&self.round_keys[..4]) {
+            *word ^= key;
         }
 
-        let mut plaintext = Vec::with_capacity(16);
-        for i in 0..4 {
-            plaintext.push(block[i] as u8);
-            plaintext.push((block[i] >> 8) as u8);
-            plaintext.push((block[i] >> 16) as u8);
-            plaintext.push((block[i] >> 24) as u8);
-        }
-        plaintext
+        Self::store_words(block, bytes);
     }
 
     pub fn encrypt_with_rounds(&self, plaintext_block: &[u8], rounds: usize) -> Vec<u8> {
-        let mut tmp = Twofish {
-            key: self.key.clone(),
+        let tmp = Twofish {
+            s: self.s.clone(),
+            tables: self.tables.clone(),
             round_keys: self.round_keys.clone(),
             rounds,
         };
@@ -194,8 +188,9 @@ impl Twofish {
     }
 
     pub fn decrypt_with_rounds(&self, ciphertext_block: &[u8], rounds: usize) -> Vec<u8> {
-        let mut tmp = Twofish {
-            key: self.key.clone(),
+        let tmp = Twofish {
+            s: self.s.clone(),
+            tables: self.tables.clone(),
             round_keys: self.round_keys.clone(),
             rounds,
         };
@@ -222,19 +217,27 @@ impl CipherAlgorithm for Twofish {
 }
 
 impl SymmetricCipher for Twofish {
-    fn set_key(&mut self, key: &[u8]) -> Result<(), &'static str> {
-        if key.len() != 16 && key.len() != 24 && key.len() != 32 {
-            return Err("Invalid key length for Twofish");
+    fn set_key(&mut self, key: &[u8]) -> Result<(), CipherError> {
+        if !is_valid_key_length(key.len()) {
+            return Err(CipherError::InvalidKeyLength {
+                expected: self.key_sizes(),
+                actual: key.len(),
+            });
         }
-        self.key = key.to_vec();
+        self.s = sbox_key(&pad_key(key));
+        self.tables = KeyedTables::new(self.keying(), &self.s);
         self.round_keys = expand_key(key);
         Ok(())
     }
+
+    fn key_sizes(&self) -> &'static [usize] {
+        KEY_SIZES
+    }
 }
 
 impl SymmetricCipherWithRounds for Twofish {
-    fn set_key_with_rounds(&mut self, _key: &[u8]) {
-        todo!()
+    fn set_key_with_rounds(&mut self, key: &[u8]) {
+        self.set_key(key).unwrap();
     }
 
     fn encrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
@@ -249,6 +252,14 @@ impl SymmetricCipherWithRounds for Twofish {
         16
     }
 
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        self.encrypt_in_place(block);
+    }
+
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        self.decrypt_in_place(block);
+    }
+
     fn export_round_keys(&self) -> Option<Vec<u8>> {
         Some(self.round_keys.iter()
             .flat_map(|&k| k.to_le_bytes())
@@ -1,10 +1,17 @@
 pub use symmetric_cipher::crypto as symmetric_crypto;
 pub mod crypto;
+pub use crate::crypto::twofish::Twofish;
 pub use crate::crypto::twofish::Twofish as TwofishCipher;
+pub use symmetric_cipher::crypto::cipher_error::CipherError;
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
 
+/// Adds Twofish to `registry`.
+pub fn register(registry: &mut symmetric_cipher::crypto::registry::CipherRegistry) -> Result<(), CipherError> {
+    registry.register(crypto::twofish::DESCRIPTOR)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
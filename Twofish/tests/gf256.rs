@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use twofish::crypto::gf256::{gf_mul, gf_pow};
-    use super::*;
+    
 
     #[test]
     fn test_gf_mul_basic() {
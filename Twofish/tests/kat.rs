@@ -0,0 +1,29 @@
+use std::path::Path;
+use symmetric_cipher::crypto::kat::{self, KatReport};
+use twofish::crypto::twofish::DESCRIPTOR;
+
+fn run(name: &str) -> KatReport {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors").join(name);
+    let report = kat::run_file(&path, &DESCRIPTOR.constructor).unwrap();
+    let failures: Vec<String> = report.failures.iter().map(ToString::to_string).collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+    report
+}
+
+#[test]
+fn test_ecb_table() {
+    // Таблица из пакета заявки на AES: 49 значений на каждый размер ключа.
+    assert_eq!(run("ecb_tbl.txt").checked, 3 * 49 * 2);
+}
+
+#[test]
+fn test_ecb_variable_key() {
+    // По одному единичному биту ключа: 128 + 192 + 256 векторов.
+    assert_eq!(run("ecb_vk.txt").checked, (128 + 192 + 256) * 2);
+}
+
+#[test]
+fn test_ecb_variable_text() {
+    // По одному единичному биту открытого текста для каждого размера ключа.
+    assert_eq!(run("ecb_vt.txt").checked, 3 * 128 * 2);
+}
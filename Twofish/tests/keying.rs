@@ -0,0 +1,54 @@
+use std::path::Path;
+use rand::RngCore;
+use symmetric_cipher::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher};
+use symmetric_cipher::crypto::kat;
+use symmetric_cipher::crypto::registry::BoxedCipher;
+use twofish::crypto::keying::Keying;
+use twofish::crypto::twofish::Twofish;
+
+const MODES: [Keying; 4] = [Keying::Full, Keying::Partial, Keying::Minimal, Keying::Zero];
+
+#[test]
+fn test_modes_give_identical_output() {
+    let mut data = vec![0u8; 16 * 32];
+    rand::rng().fill_bytes(&mut data);
+
+    // Все длины ключа: 2, 3 и 4 слова S, включая дополняемые нулями.
+    for len in [5, 16, 20, 24, 32] {
+        let mut key = vec![0u8; len];
+        rand::rng().fill_bytes(&mut key);
+
+        let expected = Twofish::with_keying(&key, Keying::Zero).unwrap().encrypt(&data);
+        for mode in MODES {
+            let cipher = Twofish::with_keying(&key, mode).unwrap();
+            assert_eq!(cipher.keying(), mode);
+            let encrypted = cipher.encrypt(&data);
+            assert_eq!(encrypted, expected, "{:?}, ключ {} байт", mode, len);
+            assert_eq!(cipher.decrypt(&encrypted), data, "{:?}, ключ {} байт", mode, len);
+        }
+    }
+}
+
+#[test]
+fn test_ecb_table_in_every_mode() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/ecb_tbl.txt");
+    for mode in MODES {
+        let new = |key: &[u8]| Ok(Box::new(Twofish::with_keying(key, mode)?) as BoxedCipher);
+        let report = kat::run_file(&path, &new).unwrap();
+        assert!(report.failures.is_empty(), "{:?}: {}", mode, report.failures[0]);
+    }
+}
+
+#[test]
+fn test_keying_survives_set_key() {
+    let block = [0x11u8; 16];
+    let mut cipher = Twofish::with_keying(&[0u8; 16], Keying::Minimal).unwrap();
+    cipher.set_key(&[0x42; 24]).unwrap();
+    assert_eq!(cipher.keying(), Keying::Minimal);
+    assert_eq!(cipher.encrypt(&block), Twofish::new(&[0x42; 24]).unwrap().encrypt(&block));
+
+    // Смена режима перестраивает таблицы текущего ключа.
+    cipher.set_keying(Keying::Full);
+    assert_eq!(cipher.keying(), Keying::Full);
+    assert_eq!(cipher.encrypt(&block), Twofish::new(&[0x42; 24]).unwrap().encrypt(&block));
+}
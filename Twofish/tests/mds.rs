@@ -2,7 +2,7 @@
 mod tests {
     use twofish::crypto::gf256::gf_mul;
     use twofish::crypto::mds::mds_multiply;
-    use super::*;
+    
     
 
     // Функция для ручного вычисления MDS умножения (для тестов)
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use twofish::crypto::pht::pht;
-    use super::*;
+    
 
     #[test]
     fn test_pht_basic() {
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use twofish::crypto::sboxes::{q0, q1, Q0, Q1};
-    use super::*;
+    
 
     #[test]
     fn test_q0_values() {
@@ -36,8 +36,8 @@ mod tests {
         assert_eq!(Q0.len(), 256);
 
         // Проверка, что все значения в таблице Q0 соответствуют вызовам функции q0
-        for i in 0..256 {
-            assert_eq!(Q0[i], q0(i as u8));
+        for (i, &expected) in Q0.iter().enumerate() {
+            assert_eq!(expected, q0(i as u8));
         }
     }
 
@@ -47,8 +47,8 @@ mod tests {
         assert_eq!(Q1.len(), 256);
 
         // Проверка, что все значения в таблице Q1 соответствуют вызовам функции q1
-        for i in 0..256 {
-            assert_eq!(Q1[i], q1(i as u8));
+        for (i, &expected) in Q1.iter().enumerate() {
+            assert_eq!(expected, q1(i as u8));
         }
     }
 
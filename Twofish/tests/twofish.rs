@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(dead_code)]
 mod tests {
     use symmetric_cipher::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
     use twofish::crypto::twofish::Twofish;
@@ -54,7 +55,7 @@ mod tests {
 
     #[test]
     fn test_block_size() {
-        let cipher = Twofish::new(&TEST_KEY_128);
+        let cipher = Twofish::new(&TEST_KEY_128).unwrap();
         assert_eq!(cipher.block_size(), 16); // 128 бит = 16 байт
     }
     #[test]
@@ -69,7 +70,7 @@ mod tests {
             0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0
         ];
 
-        let cipher = Twofish::new(&key);
+        let cipher = Twofish::new(&key).unwrap();
         let encrypted = cipher.encrypt(&plaintext);
         let decrypted = cipher.decrypt(&encrypted);
 
@@ -78,16 +79,34 @@ mod tests {
 
     #[test]
     fn test_invalid_key_size() {
-        let invalid_key = [0x00, 0x01, 0x02, 0x03]; // Слишком короткий ключ
-        let mut cipher = Twofish::new(&[0; 16]); // Создаем с валидным ключом
+        let invalid_key = [0x00; 33]; // Длиннее 256 бит
+        let mut cipher = Twofish::new(&[0; 16]).unwrap(); // Создаем с валидным ключом
 
         // Проверяем, что set_key возвращает ошибку
         assert!(cipher.set_key(&invalid_key).is_err());
+        assert!(cipher.set_key(&[]).is_err());
+        // Короткие ключи дополняются нулями
+        assert!(cipher.set_key(&[0x00, 0x01, 0x02, 0x03]).is_ok());
+
+        // Конструктор тоже возвращает ошибку, а не паникует
+        assert!(Twofish::new(&invalid_key).is_err());
+        assert!(Twofish::new(&[]).is_err());
+        // Заявленные размеры совпадают с тем, что принимает set_key
+        assert_eq!(cipher.key_sizes(), (1..=32).collect::<Vec<_>>());
+        assert_eq!(twofish::crypto::twofish::DESCRIPTOR.key_sizes, cipher.key_sizes());
+    }
+
+    #[test]
+    fn test_set_key_with_rounds() {
+        let mut cipher = Twofish::new(&TEST_KEY_128).unwrap();
+        cipher.set_key_with_rounds(&[0; 16]);
+        // Первый вектор ECB_TBL: нулевой ключ и нулевой блок
+        assert_eq!(cipher.encrypt(&TEST_PLAINTEXT_1), TEST_CIPHERTEXT_1);
     }
 
     #[test]
     fn test_set_key() {
-        let mut cipher = Twofish::new(&[0; 16]); // Инициализируем с нулевым ключом
+        let mut cipher = Twofish::new(&[0; 16]).unwrap(); // Инициализируем с нулевым ключом
         let _encrypted_first = cipher.encrypt(&TEST_PLAINTEXT_1);
         // Меняем ключ
         cipher.set_key(&TEST_KEY_128).unwrap();
@@ -99,7 +118,7 @@ mod tests {
 
     #[test]
     fn test_encrypt_decrypt_multiple_blocks() {
-        let cipher = Twofish::new(&TEST_KEY_128);
+        let cipher = Twofish::new(&TEST_KEY_128).unwrap();
         // 48 байт = 3 блока по 16 байт
         let plaintext = vec![0xAAu8; 48];
         let ciphertext = cipher.encrypt(&plaintext);
@@ -109,8 +128,8 @@ mod tests {
 
     #[test]
     fn test_different_keys_produce_different_ciphertexts() {
-        let c1 = Twofish::new(&[0u8; 32]);
-        let c2 = Twofish::new(&[1u8; 32]);
+        let c1 = Twofish::new(&[0u8; 32]).unwrap();
+        let c2 = Twofish::new(&[1u8; 32]).unwrap();
         let plaintext = [1u8; 16];
 
         let ct1 = c1.encrypt(&plaintext);
@@ -121,7 +140,7 @@ mod tests {
 
     #[test]
     fn test_export_round_keys() {
-        let cipher = Twofish::new(&TEST_KEY_128);
+        let cipher = Twofish::new(&TEST_KEY_128).unwrap();
         let round_keys = cipher.export_round_keys();
         assert!(round_keys.is_some());
         let keys = round_keys.unwrap();
@@ -131,7 +150,7 @@ mod tests {
 
     #[test]
     fn test_encrypt_with_rounds() {
-        let cipher = Twofish::new(&TEST_KEY_128);
+        let cipher = Twofish::new(&TEST_KEY_128).unwrap();
         let plaintext = [0u8; 16];
 
         // Тестируем с разным количеством раундов
@@ -144,4 +163,15 @@ mod tests {
             assert_eq!(decrypted, plaintext);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_inplace_matches_encrypt_block() {
+        let cipher = Twofish::new(&TEST_KEY_128).unwrap();
+        let mut block = TEST_PLAINTEXT_2;
+        cipher.encrypt_block_inplace(&mut block);
+        assert_eq!(block.to_vec(), cipher.encrypt_block(&TEST_PLAINTEXT_2));
+
+        cipher.decrypt_block_inplace(&mut block);
+        assert_eq!(block, TEST_PLAINTEXT_2);
+    }
+}
@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::RngCore;
+use rijndael::gf::arithmetic::Poly;
+use rijndael::rijndael::cipher::{Backend, Rijndael};
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+
+fn aes_poly() -> Poly {
+    (0..9).map(|bit| 0x11Bu16 >> bit & 1 == 1).collect()
+}
+
+fn bench_backends(c: &mut Criterion) {
+    let mut data = vec![0u8; 16 * 1024];
+    rand::rng().fill_bytes(&mut data);
+
+    let mut group = c.benchmark_group("AES-128 ECB 16KB");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    for backend in [Backend::Reference, Backend::TTable, Backend::Bitsliced, Backend::AesNi] {
+        let mut cipher = Rijndael::with_backend(aes_poly(), 4, backend);
+        cipher.set_key(&[0x2b; 16]).unwrap();
+
+        group.bench_function(BenchmarkId::new("encrypt", format!("{:?}", backend)), |b| {
+            b.iter(|| cipher.encrypt_blocks(&mut data))
+        });
+        group.bench_function(BenchmarkId::new("decrypt", format!("{:?}", backend)), |b| {
+            b.iter(|| cipher.decrypt_blocks(&mut data))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_key_setup(c: &mut Criterion) {
+    c.bench_function("Rijndael::new + set_key", |b| {
+        b.iter(|| {
+            let mut cipher = Rijndael::new(aes_poly(), 4);
+            cipher.set_key(&[0x2b; 16]).unwrap();
+            cipher
+        })
+    });
+}
+
+criterion_group!(benches, bench_backends, bench_key_setup);
+criterion_main!(benches);
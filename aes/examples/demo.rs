@@ -1,11 +1,10 @@
 use std::fs;
 use std::path::Path;
-use tokio;
 
 use rand::SeedableRng;
 use rand::{RngCore, rngs::StdRng};
 
-use rijndael::gf::arithmetic::{Poly, poly_add, poly_inv, poly_mulmod, poly_powmod};
+use rijndael::gf::arithmetic::{poly_add, poly_inv, poly_mulmod, poly_powmod};
 use rijndael::rijndael::cipher::Rijndael;
 use rijndael::rijndael::key_schedule::expand_key;
 use rijndael::rijndael::sbox::{inv_sbox, sbox};
@@ -135,14 +134,14 @@ async fn main() -> std::io::Result<()> {
                         ctx.set_key(&key).unwrap();
 
                         // encrypt
-                        let mut out_enc = CipherOutput::Buffer(Box::new(Vec::new()));
+                        let mut out_enc = CipherOutput::Buffer(Box::default());
                         ctx.encrypt(CipherInput::Bytes(data.clone()), &mut out_enc)
                             .await
                             .unwrap();
                         let cipher = out_enc.as_buffer().clone();
 
                         // decrypt
-                        let mut out_dec = CipherOutput::Buffer(Box::new(Vec::new()));
+                        let mut out_dec = CipherOutput::Buffer(Box::default());
                         ctx.decrypt(CipherInput::Bytes(cipher.clone()), &mut out_dec)
                             .await
                             .unwrap();
@@ -3,7 +3,7 @@
 pub type Poly = Vec<bool>;
 
 pub fn trim(p: &mut Poly) {
-    while p.last().map_or(false, |b| !*b) {
+    while p.last().is_some_and(|b| !*b) {
         p.pop();
     }
 }
@@ -36,10 +36,10 @@ pub fn poly_gcd(mut a: Poly, mut b: Poly) -> Poly {
 pub fn poly_add(a: &Poly, b: &Poly) -> Poly {
     let n = a.len().max(b.len());
     let mut r = vec![false; n]; 
-    for i in 0..n {
+    for (i, ri) in r.iter_mut().enumerate() {
         let ai = a.get(i).copied().unwrap_or(false);
         let bi = b.get(i).copied().unwrap_or(false);
-        r[i] = ai ^ bi;
+        *ri = ai ^ bi;
     }
     trim(&mut r);
     r
@@ -132,7 +132,7 @@ pub fn poly_inv(a: &Poly, modulus: &Poly) -> Poly {
         s0 = s1;
         s1 = s2;
     }
-    if deg(&r0) != 0 || r0.get(0) != Some(&true) {
+    if deg(&r0) != 0 || r0.first() != Some(&true) {
         panic!("No inverse exists for polynomial {:?} mod {:?}", a, modulus);
     }
     let mut inv = s0;
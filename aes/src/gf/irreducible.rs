@@ -1,8 +1,8 @@
-use super::arithmetic::{Poly, poly_add, poly_mulmod, poly_powmod, poly_divmod, poly_mod};
+use super::arithmetic::{Poly, poly_add, poly_powmod, poly_divmod, poly_mod};
 
 /// Удаляет старшие нули
 fn trim(p: &mut Poly) {
-    while p.last().map_or(false, |b| !*b) {
+    while p.last().is_some_and(|b| !*b) {
         p.pop();
     }
 }
@@ -51,7 +51,7 @@ pub fn is_irreducible(poly: &Poly) -> bool {
 
     let mut d = 1;
     while d * d <= n {
-        if n % d == 0 {
+        if n.is_multiple_of(d) {
             for &k in &[d, n / d] {
                 if k < n && k > 0 {
                     let xp_k = poly_powmod(&x, 1 << k, poly);
@@ -4,4 +4,15 @@ pub use symmetric_cipher::crypto::cipher_context;
 pub use symmetric_cipher::crypto::utils;
 pub use symmetric_cipher::crypto::cipher_types;
 
-pub use symmetric_cipher::crypto;
\ No newline at end of file
+pub use symmetric_cipher::crypto;
+pub use symmetric_cipher::crypto::cipher_error::CipherError;
+
+use symmetric_cipher::crypto::registry::CipherRegistry;
+
+/// Adds the Rijndael block sizes to `registry`.
+pub fn register(registry: &mut CipherRegistry) -> Result<(), CipherError> {
+    for descriptor in rijndael::cipher::DESCRIPTORS {
+        registry.register(descriptor)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,125 @@
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Blocks processed side by side. AESENC takes several cycles but a new one
+/// can start every cycle, so independent blocks keep the unit busy.
+#[cfg(target_arch = "x86_64")]
+const PIPELINE: usize = 8;
+
+/// Whether this CPU has the AES-NI instructions; checked at runtime.
+pub fn detected() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        std::arch::is_x86_feature_detected!("aes")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+/// Round keys loaded for the AES-NI instructions. Only valid for AES proper:
+/// 16-byte blocks over x^8 + x^4 + x^3 + x + 1, which is all the hardware
+/// implements. Decryption uses the equivalent inverse cipher (AESIMC keys).
+#[cfg(target_arch = "x86_64")]
+pub struct AesNiKeys {
+    encrypt: Vec<__m128i>,
+    decrypt: Vec<__m128i>,
+}
+
+#[cfg(target_arch = "x86_64")]
+impl AesNiKeys {
+    /// `None` if the CPU lacks AES-NI.
+    pub fn new(round_keys: &[Vec<u8>]) -> Option<Self> {
+        // SAFETY: AES-NI is present.
+        detected().then(|| unsafe { Self::load(round_keys) })
+    }
+
+    #[target_feature(enable = "aes")]
+    unsafe fn load(round_keys: &[Vec<u8>]) -> Self {
+        let mut encrypt = Vec::with_capacity(round_keys.len());
+        for key in round_keys {
+            assert_eq!(key.len(), 16);
+            // SAFETY: 16 readable bytes; loadu has no alignment requirement.
+            encrypt.push(unsafe { _mm_loadu_si128(key.as_ptr().cast()) });
+        }
+
+        let nr = encrypt.len() - 1;
+        let mut decrypt = Vec::with_capacity(nr + 1);
+        decrypt.push(encrypt[nr]);
+        for i in (1..nr).rev() {
+            decrypt.push(_mm_aesimc_si128(encrypt[i]));
+        }
+        decrypt.push(encrypt[0]);
+        Self { encrypt, decrypt }
+    }
+
+    /// `blocks` must be whole 16-byte blocks.
+    pub fn encrypt_blocks(&self, blocks: &mut [u8]) {
+        // SAFETY: keys only exist when AES-NI was detected.
+        unsafe { self.process(blocks, true) }
+    }
+
+    pub fn decrypt_blocks(&self, blocks: &mut [u8]) {
+        // SAFETY: as above.
+        unsafe { self.process(blocks, false) }
+    }
+
+    #[target_feature(enable = "aes")]
+    unsafe fn process(&self, blocks: &mut [u8], encrypt: bool) {
+        let keys = if encrypt { &self.encrypt } else { &self.decrypt };
+        let nr = keys.len() - 1;
+        let round = |state: __m128i, key| {
+            if encrypt { _mm_aesenc_si128(state, key) } else { _mm_aesdec_si128(state, key) }
+        };
+        let last_round = |state: __m128i, key| {
+            if encrypt { _mm_aesenclast_si128(state, key) } else { _mm_aesdeclast_si128(state, key) }
+        };
+
+        let mut chunks = blocks.chunks_exact_mut(16 * PIPELINE);
+        for chunk in &mut chunks {
+            let mut state = [_mm_setzero_si128(); PIPELINE];
+            for (s, block) in state.iter_mut().zip(chunk.chunks_exact(16)) {
+                // SAFETY: `block` is 16 bytes.
+                *s = _mm_xor_si128(unsafe { _mm_loadu_si128(block.as_ptr().cast()) }, keys[0]);
+            }
+            for &key in &keys[1..nr] {
+                for s in state.iter_mut() {
+                    *s = round(*s, key);
+                }
+            }
+            for (s, block) in state.iter().zip(chunk.chunks_exact_mut(16)) {
+                // SAFETY: `block` is 16 bytes.
+                unsafe { _mm_storeu_si128(block.as_mut_ptr().cast(), last_round(*s, keys[nr])) };
+            }
+        }
+
+        for block in chunks.into_remainder().chunks_exact_mut(16) {
+            // SAFETY: `block` is 16 bytes.
+            let mut s = _mm_xor_si128(unsafe { _mm_loadu_si128(block.as_ptr().cast()) }, keys[0]);
+            for &key in &keys[1..nr] {
+                s = round(s, key);
+            }
+            unsafe { _mm_storeu_si128(block.as_mut_ptr().cast(), last_round(s, keys[nr])) };
+        }
+    }
+}
+
+/// No AES-NI off x86_64: [`new`](Self::new) always returns `None`.
+#[cfg(not(target_arch = "x86_64"))]
+pub enum AesNiKeys {}
+
+#[cfg(not(target_arch = "x86_64"))]
+impl AesNiKeys {
+    pub fn new(_round_keys: &[Vec<u8>]) -> Option<Self> {
+        None
+    }
+
+    pub fn encrypt_blocks(&self, _blocks: &mut [u8]) {
+        match *self {}
+    }
+
+    pub fn decrypt_blocks(&self, _blocks: &mut [u8]) {
+        match *self {}
+    }
+}
@@ -0,0 +1,189 @@
+use crate::rijndael::cipher::shift_offsets;
+
+/// Bit `i` of plane `j` is bit `j` of state byte `i`, with bytes in column
+/// order (byte `4c + r` is row `r` of column `c`). 32 bits hold the widest
+/// block, Nb = 8.
+type Planes = [u32; 8];
+
+/// Rijndael on bit planes: every step is a fixed sequence of AND, XOR and
+/// shifts over the whole state, with no table lookups or branches on key or
+/// data, so its timing does not depend on them.
+///
+/// The S-box is computed rather than looked up: the inverse is x^254 by
+/// square-and-multiply, followed by the affine map. The field modulus only
+/// decides which XORs the reduction does, and it is public.
+pub struct Bitsliced {
+    reduction: u8,
+    nb: usize,
+}
+
+/// Rows of each column rotated up by `n`: row `r` takes row `r + n`.
+fn rotate_rows(p: u32, n: u32) -> u32 {
+    let low = 0x1111_1111u32 * ((1 << (4 - n)) - 1);
+    ((p >> n) & low) | ((p << (4 - n)) & !low)
+}
+
+fn pack(block: &[u8]) -> Planes {
+    let mut planes = [0u32; 8];
+    for (i, &b) in block.iter().enumerate() {
+        for (j, plane) in planes.iter_mut().enumerate() {
+            *plane |= u32::from(b >> j & 1) << i;
+        }
+    }
+    planes
+}
+
+fn unpack(planes: &Planes, block: &mut [u8]) {
+    for (i, b) in block.iter_mut().enumerate() {
+        *b = planes.iter().enumerate().fold(0, |acc, (j, plane)| acc | ((plane >> i & 1) as u8) << j);
+    }
+}
+
+fn add_round_key(state: &mut Planes, key: &Planes) {
+    for (s, k) in state.iter_mut().zip(key) {
+        *s ^= k;
+    }
+}
+
+/// Affine map of the S-box: b_i ^ b_{i+4} ^ b_{i+5} ^ b_{i+6} ^ b_{i+7} ^ 0x63_i.
+fn affine(x: &Planes) -> Planes {
+    std::array::from_fn(|i| {
+        let bit = x[i] ^ x[(i + 4) % 8] ^ x[(i + 5) % 8] ^ x[(i + 6) % 8] ^ x[(i + 7) % 8];
+        if 0x63 >> i & 1 == 1 { !bit } else { bit }
+    })
+}
+
+/// Inverse affine map: b_{i+2} ^ b_{i+5} ^ b_{i+7} ^ 0x05_i.
+fn inv_affine(x: &Planes) -> Planes {
+    std::array::from_fn(|i| {
+        let bit = x[(i + 2) % 8] ^ x[(i + 5) % 8] ^ x[(i + 7) % 8];
+        if 0x05 >> i & 1 == 1 { !bit } else { bit }
+    })
+}
+
+impl Bitsliced {
+    pub fn new(reduction: u8, nb: usize) -> Self {
+        Self { reduction, nb }
+    }
+
+    pub fn keys(&self, round_keys: &[Vec<u8>]) -> Vec<Planes> {
+        round_keys.iter().map(|key| pack(key)).collect()
+    }
+
+    /// Folds a product of up to 15 coefficients back below x^8:
+    /// x^k = x^(k-8) · (modulus without x^8), from the top down.
+    fn reduce(&self, mut product: [u32; 15]) -> Planes {
+        for k in (8..15).rev() {
+            for bit in 0..8 {
+                if self.reduction >> bit & 1 == 1 {
+                    product[k - 8 + bit] ^= product[k];
+                }
+            }
+        }
+        std::array::from_fn(|i| product[i])
+    }
+
+    fn mul(&self, a: &Planes, b: &Planes) -> Planes {
+        let mut product = [0u32; 15];
+        for (i, &ai) in a.iter().enumerate() {
+            for (j, &bj) in b.iter().enumerate() {
+                product[i + j] ^= ai & bj;
+            }
+        }
+        self.reduce(product)
+    }
+
+    /// Squaring is linear over GF(2): coefficient i moves to 2i.
+    fn square(&self, a: &Planes) -> Planes {
+        let mut product = [0u32; 15];
+        for (i, &ai) in a.iter().enumerate() {
+            product[2 * i] = ai;
+        }
+        self.reduce(product)
+    }
+
+    fn xtime(&self, a: &Planes) -> Planes {
+        std::array::from_fn(|i| {
+            let shifted = if i == 0 { 0 } else { a[i - 1] };
+            if self.reduction >> i & 1 == 1 { shifted ^ a[7] } else { shifted }
+        })
+    }
+
+    /// x^254 = x^-1 for x ≠ 0 and 0 for x = 0, with four multiplications:
+    /// x^3, x^15 = x^12 · x^3, x^252 = x^240 · x^12, x^254 = x^252 · x^2.
+    fn invert(&self, x: &Planes) -> Planes {
+        let x2 = self.square(x);
+        let x3 = self.mul(&x2, x);
+        let x12 = self.square(&self.square(&x3));
+        let x15 = self.mul(&x12, &x3);
+        let x240 = (0..4).fold(x15, |acc, _| self.square(&acc));
+        self.mul(&self.mul(&x240, &x12), &x2)
+    }
+
+    /// Row r moves left by C_r columns (right by `inverse`): a rotation of
+    /// its bits by 4·C_r within the 4·Nb used bits.
+    fn shift_rows(&self, state: &mut Planes, inverse: bool) {
+        let width = 4 * self.nb as u32;
+        let offsets = shift_offsets(self.nb);
+        let used = if width == 32 { u32::MAX } else { (1 << width) - 1 };
+        for plane in state.iter_mut() {
+            let mut shifted = *plane & 0x1111_1111 & used;
+            for r in 1..4 {
+                let row = *plane & (0x1111_1111 << r) & used;
+                let shift = 4 * offsets[r as usize] as u32;
+                let k = if inverse { width - shift } else { shift };
+                shifted |= ((row >> k) | (row << (width - k))) & used;
+            }
+            *plane = shifted;
+        }
+    }
+
+    fn mix_columns(&self, state: &mut Planes) {
+        let one: Planes = state.map(|p| rotate_rows(p, 1));
+        let doubled = self.xtime(&std::array::from_fn(|i| state[i] ^ one[i]));
+        for (i, plane) in state.iter_mut().enumerate() {
+            *plane = doubled[i] ^ one[i] ^ rotate_rows(*plane, 2) ^ rotate_rows(*plane, 3);
+        }
+    }
+
+    /// InvMixColumns = MixColumns after multiplying each column by
+    /// {04}x^2 + {05}; none of the coefficient products overflow a byte, so
+    /// this holds for any modulus.
+    fn inv_mix_columns(&self, state: &mut Planes) {
+        let twice = self.xtime(&self.xtime(&state.map(|p| p ^ rotate_rows(p, 2))));
+        for (plane, t) in state.iter_mut().zip(twice) {
+            *plane ^= t;
+        }
+        self.mix_columns(state);
+    }
+
+    pub fn encrypt(&self, keys: &[Planes], block: &mut [u8]) {
+        let nr = keys.len() - 1;
+        let mut state = pack(block);
+        add_round_key(&mut state, &keys[0]);
+        for (round, key) in keys.iter().enumerate().skip(1) {
+            state = affine(&self.invert(&state));
+            self.shift_rows(&mut state, false);
+            if round != nr {
+                self.mix_columns(&mut state);
+            }
+            add_round_key(&mut state, key);
+        }
+        unpack(&state, block);
+    }
+
+    pub fn decrypt(&self, keys: &[Planes], block: &mut [u8]) {
+        let nr = keys.len() - 1;
+        let mut state = pack(block);
+        add_round_key(&mut state, &keys[nr]);
+        for round in (0..nr).rev() {
+            self.shift_rows(&mut state, true);
+            state = self.invert(&inv_affine(&state));
+            add_round_key(&mut state, &keys[round]);
+            if round != 0 {
+                self.inv_mix_columns(&mut state);
+            }
+        }
+        unpack(&state, block);
+    }
+}
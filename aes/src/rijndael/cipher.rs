@@ -1,49 +1,84 @@
-use crate::gf::arithmetic::{poly_mulmod, Poly};
+use crate::gf::arithmetic::Poly;
 use crate::rijndael::key_schedule::expand_key;
+use symmetric_cipher::crypto::cipher_error::CipherError;
 use symmetric_cipher::crypto::cipher_traits::{
     CipherAlgorithm,
     SymmetricCipher,
     SymmetricCipherWithRounds,
 };
+use crate::rijndael::aesni::{self, AesNiKeys};
+use crate::rijndael::bitsliced::Bitsliced;
 use crate::rijndael::sbox::{inv_sbox, sbox};
+use crate::rijndael::ttable::{TTableKeys, TTables};
+use symmetric_cipher::crypto::registry::{BoxedCipher, CipherDescriptor};
 
-type State = Vec<[u8; 4]>;
+/// Largest Rijndael block: Nb = 8 columns (256 bits).
+const MAX_NB: usize = 8;
 
-fn block_to_state(block: &[u8], nb: usize) -> State {
-    let mut s = vec![[0u8; 4]; nb];
-    for c in 0..nb {
-        for r in 0..4 {
-            s[c][r] = block[c * 4 + r];
-        }
-    }
-    s
-}
+/// AES field modulus x^8 + x^4 + x^3 + x + 1.
+const AES_MODULUS: u16 = 0x11B;
 
-fn state_to_block(s: &State) -> Vec<u8> {
-    let nb = s.len();
-    let mut out = vec![0u8; 4 * nb];
-    for c in 0..nb {
-        for r in 0..4 {
-            out[c * 4 + r] = s[c][r];
-        }
-    }
-    out
+/// Rijndael key sizes: 128 to 256 bits in steps of 32.
+const KEY_SIZES: &[usize] = &[16, 20, 24, 28, 32];
+
+/// Rijndael over the AES field with every block size from 128 to 256 bits.
+pub const DESCRIPTORS: [CipherDescriptor; 5] = [
+    CipherDescriptor {
+        name: "rijndael-128",
+        key_sizes: KEY_SIZES,
+        block_size: 16,
+        default_rounds: 10,
+        constructor: |key| keyed(4, key),
+    },
+    CipherDescriptor {
+        name: "rijndael-160",
+        key_sizes: KEY_SIZES,
+        block_size: 20,
+        default_rounds: 11,
+        constructor: |key| keyed(5, key),
+    },
+    CipherDescriptor {
+        name: "rijndael-192",
+        key_sizes: KEY_SIZES,
+        block_size: 24,
+        default_rounds: 12,
+        constructor: |key| keyed(6, key),
+    },
+    CipherDescriptor {
+        name: "rijndael-224",
+        key_sizes: KEY_SIZES,
+        block_size: 28,
+        default_rounds: 13,
+        constructor: |key| keyed(7, key),
+    },
+    CipherDescriptor {
+        name: "rijndael-256",
+        key_sizes: KEY_SIZES,
+        block_size: 32,
+        default_rounds: 14,
+        constructor: |key| keyed(8, key),
+    },
+];
+
+fn keyed(nb: usize, key: &[u8]) -> Result<BoxedCipher, CipherError> {
+    let poly: Poly = (0..9).map(|bit| AES_MODULUS >> bit & 1 == 1).collect();
+    let mut cipher = Rijndael::new(poly, nb);
+    cipher.set_key(key)?;
+    Ok(Box::new(cipher))
 }
 
-fn gf_mul_byte(a: u8, b: u8, poly: &Poly) -> u8 {
-    let pa = byte_to_poly(a);
-    let pb = byte_to_poly(b);
-    let prod = poly_mulmod(&pa, &pb, poly);
-    poly_to_byte(&prod)
+type State = [[u8; 4]];
+
+fn block_to_state(block: &[u8], state: &mut State) {
+    for (c, col) in state.iter_mut().enumerate() {
+        col.copy_from_slice(&block[c * 4..c * 4 + 4]);
+    }
 }
 
-fn byte_to_poly(x: u8) -> Poly {
-    let mut v = Vec::with_capacity(9);
-    for i in 0..8 {
-        v.push((x >> i) & 1 != 0);
+fn state_to_block(state: &State, block: &mut [u8]) {
+    for (c, col) in state.iter().enumerate() {
+        block[c * 4..c * 4 + 4].copy_from_slice(col);
     }
-    v.push(false);
-    v
 }
 
 fn poly_to_byte(p: &Poly) -> u8 {
@@ -53,42 +88,59 @@ fn poly_to_byte(p: &Poly) -> u8 {
         .fold(0u8, |acc, (i, &b)| if b { acc | (1 << i) } else { acc })
 }
 
-fn add_round_key(state: &mut State, round_key: &[u8]) {
-    let nb = state.len();
-    for c in 0..nb {
-        for r in 0..4 {
-            let idx = c * 4 + r;
-            if idx < round_key.len() {
-                state[c][r] ^= round_key[idx];
-            } else {
-                println!("Error: round_key index {} out of bounds (len: {})", idx, round_key.len());
-            }
+/// Multiplication in GF(2^8) by shift-and-add; `reduction` is the modulus
+/// without its x^8 term.
+pub(crate) fn gf_mul_byte(mut a: u8, mut b: u8, reduction: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= reduction;
         }
+        b >>= 1;
     }
+    product
 }
 
-fn sub_bytes(state: &mut State, poly: &Poly) {
-    for col in state.iter_mut() {
-        for byte in col.iter_mut() {
-            *byte = sbox(*byte, poly);
+fn add_round_key(state: &mut State, round_key: &[u8]) {
+    for (c, col) in state.iter_mut().enumerate() {
+        for (r, byte) in col.iter_mut().enumerate() {
+            *byte ^= round_key[c * 4 + r];
         }
     }
 }
 
-fn inv_sub_bytes(state: &mut State, poly: &Poly) {
+/// SubBytes with every S-box value computed from `poly` on the spot
+/// (inverse in GF(2^8) and the affine map), without a table.
+fn sub_bytes(state: &mut State, substitute: impl Fn(u8) -> u8) {
     for col in state.iter_mut() {
         for byte in col.iter_mut() {
-            *byte = inv_sbox(*byte, poly);
+            *byte = substitute(*byte);
         }
     }
 }
 
+/// ShiftRows offsets C0..C3 of each row for a block of `nb` columns, from
+/// the Rijndael proposal: rows 2 and 3 move further once Nb > 6.
+pub(crate) fn shift_offsets(nb: usize) -> [usize; 4] {
+    match nb {
+        7 => [0, 1, 2, 4],
+        8 => [0, 1, 3, 4],
+        _ => [0, 1, 2, 3],
+    }
+}
+
 fn shift_rows(state: &mut State) {
     let nb = state.len();
+    let offsets = shift_offsets(nb);
+    let mut tmp = [0u8; MAX_NB];
     for r in 1..4 {
-        let mut tmp = vec![0u8; nb];
         for c in 0..nb {
-            tmp[c] = state[(c + r) % nb][r];
+            tmp[c] = state[(c + offsets[r]) % nb][r];
         }
         for c in 0..nb {
             state[c][r] = tmp[c];
@@ -98,10 +150,11 @@ fn shift_rows(state: &mut State) {
 
 fn inv_shift_rows(state: &mut State) {
     let nb = state.len();
+    let offsets = shift_offsets(nb);
+    let mut tmp = [0u8; MAX_NB];
     for r in 1..4 {
-        let mut tmp = vec![0u8; nb];
         for c in 0..nb {
-            tmp[c] = state[(c + nb - r) % nb][r];
+            tmp[c] = state[(c + nb - offsets[r]) % nb][r];
         }
         for c in 0..nb {
             state[c][r] = tmp[c];
@@ -109,148 +162,256 @@ fn inv_shift_rows(state: &mut State) {
     }
 }
 
-fn mix_columns(state: &mut State, poly: &Poly) {
+fn mix_columns(state: &mut State, reduction: u8) {
+    let mul = |a, b| gf_mul_byte(a, b, reduction);
     for col in state.iter_mut() {
         let a = *col;
-        col[0] = gf_mul_byte(a[0], 2, poly)
-            ^ gf_mul_byte(a[1], 3, poly)
-            ^ a[2] ^ a[3];
-        col[1] = a[0]
-            ^ gf_mul_byte(a[1], 2, poly)
-            ^ gf_mul_byte(a[2], 3, poly)
-            ^ a[3];
-        col[2] = a[0] ^ a[1]
-            ^ gf_mul_byte(a[2], 2, poly)
-            ^ gf_mul_byte(a[3], 3, poly);
-        col[3] = gf_mul_byte(a[0], 3, poly)
-            ^ a[1] ^ a[2]
-            ^ gf_mul_byte(a[3], 2, poly);
+        col[0] = mul(a[0], 2) ^ mul(a[1], 3) ^ a[2] ^ a[3];
+        col[1] = a[0] ^ mul(a[1], 2) ^ mul(a[2], 3) ^ a[3];
+        col[2] = a[0] ^ a[1] ^ mul(a[2], 2) ^ mul(a[3], 3);
+        col[3] = mul(a[0], 3) ^ a[1] ^ a[2] ^ mul(a[3], 2);
     }
 }
 
-fn inv_mix_columns(state: &mut State, poly: &Poly) {
+fn inv_mix_columns(state: &mut State, reduction: u8) {
+    let mul = |a, b| gf_mul_byte(a, b, reduction);
     for col in state.iter_mut() {
         let a = *col;
-        col[0] = gf_mul_byte(a[0], 0x0e, poly)
-            ^ gf_mul_byte(a[1], 0x0b, poly)
-            ^ gf_mul_byte(a[2], 0x0d, poly)
-            ^ gf_mul_byte(a[3], 0x09, poly);
-        col[1] = gf_mul_byte(a[0], 0x09, poly)
-            ^ gf_mul_byte(a[1], 0x0e, poly)
-            ^ gf_mul_byte(a[2], 0x0b, poly)
-            ^ gf_mul_byte(a[3], 0x0d, poly);
-        col[2] = gf_mul_byte(a[0], 0x0d, poly)
-            ^ gf_mul_byte(a[1], 0x09, poly)
-            ^ gf_mul_byte(a[2], 0x0e, poly)
-            ^ gf_mul_byte(a[3], 0x0b, poly);
-        col[3] = gf_mul_byte(a[0], 0x0b, poly)
-            ^ gf_mul_byte(a[1], 0x0d, poly)
-            ^ gf_mul_byte(a[2], 0x09, poly)
-            ^ gf_mul_byte(a[3], 0x0e, poly);
+        col[0] = mul(a[0], 0x0e) ^ mul(a[1], 0x0b) ^ mul(a[2], 0x0d) ^ mul(a[3], 0x09);
+        col[1] = mul(a[0], 0x09) ^ mul(a[1], 0x0e) ^ mul(a[2], 0x0b) ^ mul(a[3], 0x0d);
+        col[2] = mul(a[0], 0x0d) ^ mul(a[1], 0x09) ^ mul(a[2], 0x0e) ^ mul(a[3], 0x0b);
+        col[3] = mul(a[0], 0x0b) ^ mul(a[1], 0x0d) ^ mul(a[2], 0x09) ^ mul(a[3], 0x0e);
     }
 }
 
-fn encrypt_block_internal(
-    block: &[u8],
-    round_keys: &[Vec<u8>],
-    poly: &Poly,
-    nb: usize,
-) -> Vec<u8> {
-    let mut state = block_to_state(block, nb);
-
-    add_round_key(&mut state, &round_keys[0]);
-    let nr = round_keys.len() - 1;
-    
-    for round in 1..nr {
-        sub_bytes(&mut state, poly);
-        shift_rows(&mut state);
-        mix_columns(&mut state, poly);
-        add_round_key(&mut state, &round_keys[round]);
-    }
-    sub_bytes(&mut state, poly);
-    shift_rows(&mut state);
-    add_round_key(&mut state, &round_keys[nr]);
-    state_to_block(&state)
-}
-
-fn decrypt_block_internal(
-    block: &[u8],
-    round_keys: &[Vec<u8>],
-    poly: &Poly,
-    nb: usize,
-) -> Vec<u8> {
-    let mut state = block_to_state(block, nb);
-    let nr = round_keys.len() - 1;
-    // initial
-    add_round_key(&mut state, &round_keys[nr]);
-    inv_shift_rows(&mut state);
-    inv_sub_bytes(&mut state, poly);
-    // middle rounds
-    for round in (1..nr).rev() {
-        add_round_key(&mut state, &round_keys[round]);
-        inv_mix_columns(&mut state, poly);
-        inv_shift_rows(&mut state);
-        inv_sub_bytes(&mut state, poly);
-    }
-    add_round_key(&mut state, &round_keys[0]);
-    state_to_block(&state)
+/// How blocks are encrypted. All backends give the same output; they trade
+/// speed against resistance to timing side channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// Round by round on the byte state as in FIPS-197, computing each S-box
+    /// value from the field polynomial. Slow; meant as the check for the others.
+    Reference,
+    /// Merged round tables. The fastest in software, but lookups leak
+    /// through the cache.
+    TTable,
+    /// Bit-plane arithmetic without secret-dependent lookups or branches.
+    Bitsliced,
+    /// AES-NI instructions, several blocks at a time, when the CPU has them
+    /// and the cipher is AES (16-byte block, modulus 0x11B); `Bitsliced`
+    /// otherwise, so the default never uses key- or data-dependent lookups.
+    #[default]
+    AesNi,
 }
 
 pub struct Rijndael {
-    poly:       Poly,
-    round_keys: Vec<Vec<u8>>,
-    block_size: usize,
+    poly:           Poly,
+    round_keys:     Vec<Vec<u8>>,
+    block_size:     usize,
+    reduction:      u8,
+    backend:        Backend,
+    ttables:        Box<TTables>,
+    ttable_keys:    TTableKeys,
+    bitsliced:      Bitsliced,
+    bitsliced_keys: Vec<[u32; 8]>,
+    /// Whether AES-NI can run this instance: set once in `with_backend`.
+    aesni_capable:  bool,
+    aesni_keys:     Option<AesNiKeys>,
 }
 
 impl Rijndael {
+    /// `block_size` is Nb, the number of 32-bit columns: 4 to 8.
     pub fn new(poly: Poly, block_size: usize) -> Self {
+        Self::with_backend(poly, block_size, Backend::default())
+    }
+
+    /// Builds the tables of every backend for `poly`, so that
+    /// [`set_backend`](Self::set_backend) is free afterwards.
+    pub fn with_backend(poly: Poly, block_size: usize, backend: Backend) -> Self {
+        assert!((4..=MAX_NB).contains(&block_size), "Rijndael block must be 4 to 8 words, got {}", block_size);
+        let mut sbox_table = [0u8; 256];
+        let mut inv_sbox_table = [0u8; 256];
+        for x in 0..=255u8 {
+            sbox_table[x as usize] = sbox(x, &poly);
+            inv_sbox_table[sbox_table[x as usize] as usize] = x;
+        }
+        let reduction = poly_to_byte(&poly);
+
         Self {
+            reduction,
             poly,
             round_keys: Vec::new(),
             block_size,
+            backend,
+            ttables: Box::new(TTables::new(&sbox_table, &inv_sbox_table, reduction)),
+            ttable_keys: TTableKeys::default(),
+            bitsliced: Bitsliced::new(reduction, block_size),
+            bitsliced_keys: Vec::new(),
+            aesni_capable: block_size == 4 && u16::from(reduction) | 0x100 == AES_MODULUS && aesni::detected(),
+            aesni_keys: None,
         }
     }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
+    /// Whether blocks actually go through AES-NI: the backend is
+    /// [`Backend::AesNi`], the CPU supports it and this is AES proper.
+    pub fn hardware_accelerated(&self) -> bool {
+        self.backend == Backend::AesNi && self.aesni_capable
+    }
+
+    fn expand(&mut self, key: &[u8]) {
+        self.round_keys = expand_key(key, &self.poly, self.block_size * 4);
+        self.ttable_keys = self.ttables.keys(&self.round_keys);
+        self.bitsliced_keys = self.bitsliced.keys(&self.round_keys);
+        if self.aesni_capable {
+            self.aesni_keys = AesNiKeys::new(&self.round_keys);
+        }
+    }
+
+    fn hardware_keys(&self) -> Option<&AesNiKeys> {
+        self.aesni_keys.as_ref().filter(|_| self.backend == Backend::AesNi)
+    }
+
+    fn encrypt_in_place(&self, block: &mut [u8]) {
+        match self.backend {
+            Backend::Reference => self.encrypt_reference(block),
+            Backend::Bitsliced => self.bitsliced.encrypt(&self.bitsliced_keys, block),
+            Backend::TTable => self.ttables.encrypt(&self.ttable_keys, block),
+            Backend::AesNi => match self.hardware_keys() {
+                Some(keys) => keys.encrypt_blocks(block),
+                None => self.bitsliced.encrypt(&self.bitsliced_keys, block),
+            },
+        }
+    }
+
+    fn decrypt_in_place(&self, block: &mut [u8]) {
+        match self.backend {
+            Backend::Reference => self.decrypt_reference(block),
+            Backend::Bitsliced => self.bitsliced.decrypt(&self.bitsliced_keys, block),
+            Backend::TTable => self.ttables.decrypt(&self.ttable_keys, block),
+            Backend::AesNi => match self.hardware_keys() {
+                Some(keys) => keys.decrypt_blocks(block),
+                None => self.bitsliced.decrypt(&self.bitsliced_keys, block),
+            },
+        }
+    }
+
+    fn encrypt_reference(&self, block: &mut [u8]) {
+        let mut buf = [[0u8; 4]; MAX_NB];
+        let state = &mut buf[..self.block_size];
+        block_to_state(block, state);
+
+        let nr = self.round_keys.len() - 1;
+        add_round_key(state, &self.round_keys[0]);
+        for round_key in &self.round_keys[1..nr] {
+            sub_bytes(state, |x| sbox(x, &self.poly));
+            shift_rows(state);
+            mix_columns(state, self.reduction);
+            add_round_key(state, round_key);
+        }
+        sub_bytes(state, |x| sbox(x, &self.poly));
+        shift_rows(state);
+        add_round_key(state, &self.round_keys[nr]);
+
+        state_to_block(state, block);
+    }
+
+    fn decrypt_reference(&self, block: &mut [u8]) {
+        let mut buf = [[0u8; 4]; MAX_NB];
+        let state = &mut buf[..self.block_size];
+        block_to_state(block, state);
+
+        let nr = self.round_keys.len() - 1;
+        // initial
+        add_round_key(state, &self.round_keys[nr]);
+        inv_shift_rows(state);
+        sub_bytes(state, |x| inv_sbox(x, &self.poly));
+        // middle rounds
+        for round in (1..nr).rev() {
+            add_round_key(state, &self.round_keys[round]);
+            inv_mix_columns(state, self.reduction);
+            inv_shift_rows(state);
+            sub_bytes(state, |x| inv_sbox(x, &self.poly));
+        }
+        add_round_key(state, &self.round_keys[0]);
+
+        state_to_block(state, block);
+    }
 }
 
 impl CipherAlgorithm for Rijndael {
     fn encrypt(&self, data: &[u8]) -> Vec<u8> {
-        let bs = self.block_size * 4;
-        data.chunks(bs)
-            .flat_map(|chunk| {
-                encrypt_block_internal(chunk, &self.round_keys, &self.poly, self.block_size)
-            })
-            .collect()
+        let mut out = data.to_vec();
+        self.encrypt_blocks(&mut out);
+        out
     }
     fn decrypt(&self, data: &[u8]) -> Vec<u8> {
-        let bs = self.block_size * 4;
-        data.chunks(bs)
-            .flat_map(|chunk| {
-                decrypt_block_internal(chunk, &self.round_keys, &self.poly, self.block_size)
-            })
-            .collect()
+        let mut out = data.to_vec();
+        self.decrypt_blocks(&mut out);
+        out
     }
 }
 
 impl SymmetricCipher for Rijndael {
-    fn set_key(&mut self, key: &[u8]) -> Result<(), &'static str> {
-        self.round_keys = expand_key(key, &self.poly, self.block_size * 4);
+    fn set_key(&mut self, key: &[u8]) -> Result<(), CipherError> {
+        if !KEY_SIZES.contains(&key.len()) {
+            return Err(CipherError::InvalidKeyLength {
+                expected: self.key_sizes(),
+                actual: key.len(),
+            });
+        }
+        self.expand(key);
         Ok(())
     }
+
+    fn key_sizes(&self) -> &'static [usize] {
+        KEY_SIZES
+    }
 }
 
 impl SymmetricCipherWithRounds for Rijndael {
     fn set_key_with_rounds(&mut self, key: &[u8]) {
-        self.round_keys = expand_key(key, &self.poly, self.block_size * 4);
+        self.expand(key);
     }
     fn encrypt_block(&self, block: &[u8], _round_key: &[u8]) -> Vec<u8> {
-        encrypt_block_internal(block, &self.round_keys, &self.poly, self.block_size)
+        let mut out = block.to_vec();
+        self.encrypt_in_place(&mut out);
+        out
     }
     fn decrypt_block(&self, block: &[u8], _round_key: &[u8]) -> Vec<u8> {
-        decrypt_block_internal(block, &self.round_keys, &self.poly, self.block_size)
+        let mut out = block.to_vec();
+        self.decrypt_in_place(&mut out);
+        out
     }
     fn block_size(&self) -> usize {
         self.block_size * 4
     }
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        self.encrypt_in_place(block);
+    }
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        self.decrypt_in_place(block);
+    }
+    /// With AES-NI the blocks are pipelined; this is what the ECB, CTR and
+    /// feedback-decryption paths of `CipherContext` call per chunk.
+    fn encrypt_blocks(&self, blocks: &mut [u8]) {
+        match self.hardware_keys() {
+            Some(keys) => keys.encrypt_blocks(blocks),
+            None => blocks.chunks_exact_mut(self.block_size * 4).for_each(|block| self.encrypt_in_place(block)),
+        }
+    }
+    fn decrypt_blocks(&self, blocks: &mut [u8]) {
+        match self.hardware_keys() {
+            Some(keys) => keys.decrypt_blocks(blocks),
+            None => blocks.chunks_exact_mut(self.block_size * 4).for_each(|block| self.decrypt_in_place(block)),
+        }
+    }
     fn export_round_keys(&self) -> Option<Vec<u8>> {
         Some(self.round_keys.iter().flatten().copied().collect())
     }
@@ -31,9 +31,11 @@ pub fn expand_key(key: &[u8], poly: &Poly, block_size: usize) -> Vec<Vec<u8>> {
         [ sbox(w[0], poly), sbox(w[1], poly), sbox(w[2], poly), sbox(w[3], poly) ]
     }
 
-    let mut rcon = vec![0u8; nr+1];
+    // One constant per Nk words: with a short key and a wide block that is
+    // more than Nr of them.
+    let mut rcon = vec![0u8; total_words / nk + 1];
     rcon[1] = 1;
-    for i in 2..=nr {
+    for i in 2..rcon.len() {
         rcon[i] = gf_mul(rcon[i-1], 2, poly);
     }
 
@@ -47,9 +49,7 @@ pub fn expand_key(key: &[u8], poly: &Poly, block_size: usize) -> Vec<Vec<u8>> {
         let mut temp = w[i-1];
         if i % nk == 0 {
             temp = sub_word(rot_word(temp), poly);
-            if i / nk < rcon.len() {
-                temp[0] ^= rcon[i / nk];
-            }
+            temp[0] ^= rcon[i / nk];
         } else if nk > 6 && i % nk == 4 {
             temp = sub_word(temp, poly);
         }
@@ -1,3 +1,6 @@
 pub mod key_schedule;
 pub mod cipher;
 pub mod sbox;
+pub mod ttable;
+pub mod bitsliced;
+pub mod aesni;
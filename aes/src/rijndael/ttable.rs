@@ -0,0 +1,150 @@
+use crate::rijndael::cipher::{gf_mul_byte, shift_offsets};
+
+/// Round keys as little-endian column words. Decryption uses the equivalent
+/// inverse cipher, so its inner round keys have InvMixColumns applied.
+#[derive(Default)]
+pub struct TTableKeys {
+    encrypt: Vec<Vec<u32>>,
+    decrypt: Vec<Vec<u32>>,
+}
+
+/// SubBytes, ShiftRows and MixColumns merged into four 256-entry tables per
+/// direction: a round is 4·Nb lookups and XORs.
+///
+/// Fast, but the lookup addresses depend on the key and the data, so the
+/// timing leaks through the cache. Use the bitsliced backend where that
+/// matters.
+pub struct TTables {
+    te: [[u32; 256]; 4],
+    td: [[u32; 256]; 4],
+    sbox: [u8; 256],
+    inv_sbox: [u8; 256],
+    reduction: u8,
+}
+
+fn column(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn byte(word: u32, row: usize) -> usize {
+    (word >> (8 * row)) as u8 as usize
+}
+
+impl TTables {
+    pub fn new(sbox: &[u8; 256], inv_sbox: &[u8; 256], reduction: u8) -> Self {
+        let mul = |a, b| gf_mul_byte(a, b, reduction);
+        let mut te = [[0u32; 256]; 4];
+        let mut td = [[0u32; 256]; 4];
+        for x in 0..256 {
+            let s = sbox[x];
+            let e = u32::from_le_bytes([mul(s, 2), s, s, mul(s, 3)]);
+            let i = inv_sbox[x];
+            let d = u32::from_le_bytes([mul(i, 0x0e), mul(i, 0x09), mul(i, 0x0d), mul(i, 0x0b)]);
+            for row in 0..4 {
+                te[row][x] = e.rotate_left(8 * row as u32);
+                td[row][x] = d.rotate_left(8 * row as u32);
+            }
+        }
+        Self { te, td, sbox: *sbox, inv_sbox: *inv_sbox, reduction }
+    }
+
+    fn inv_mix_column(&self, word: u32) -> u32 {
+        let mul = |a, b| gf_mul_byte(a, b, self.reduction);
+        let a = word.to_le_bytes();
+        u32::from_le_bytes([
+            mul(a[0], 0x0e) ^ mul(a[1], 0x0b) ^ mul(a[2], 0x0d) ^ mul(a[3], 0x09),
+            mul(a[0], 0x09) ^ mul(a[1], 0x0e) ^ mul(a[2], 0x0b) ^ mul(a[3], 0x0d),
+            mul(a[0], 0x0d) ^ mul(a[1], 0x09) ^ mul(a[2], 0x0e) ^ mul(a[3], 0x0b),
+            mul(a[0], 0x0b) ^ mul(a[1], 0x0d) ^ mul(a[2], 0x09) ^ mul(a[3], 0x0e),
+        ])
+    }
+
+    pub fn keys(&self, round_keys: &[Vec<u8>]) -> TTableKeys {
+        let words = |key: &Vec<u8>| key.chunks_exact(4).map(column).collect::<Vec<_>>();
+        let encrypt: Vec<_> = round_keys.iter().map(words).collect();
+        let nr = encrypt.len() - 1;
+        let decrypt = (0..=nr)
+            .map(|i| {
+                let key = &encrypt[nr - i];
+                if i == 0 || i == nr {
+                    key.clone()
+                } else {
+                    key.iter().map(|&w| self.inv_mix_column(w)).collect()
+                }
+            })
+            .collect();
+        TTableKeys { encrypt, decrypt }
+    }
+
+    pub fn encrypt(&self, keys: &TTableKeys, block: &mut [u8]) {
+        let keys = &keys.encrypt;
+        let nb = block.len() / 4;
+        let nr = keys.len() - 1;
+        let [_, c1, c2, c3] = shift_offsets(nb);
+        // The state is stored twice in a row so that shifted columns need no
+        // wrap-around: column c + k (or c + nb - k) is always in range.
+        let mut s = [0u32; 16];
+        let mut t = [0u32; 8];
+        for c in 0..nb {
+            s[c] = column(&block[4 * c..]) ^ keys[0][c];
+            s[c + nb] = s[c];
+        }
+
+        for key in &keys[1..nr] {
+            for c in 0..nb {
+                t[c] = self.te[0][byte(s[c], 0)]
+                    ^ self.te[1][byte(s[c + c1], 1)]
+                    ^ self.te[2][byte(s[c + c2], 2)]
+                    ^ self.te[3][byte(s[c + c3], 3)]
+                    ^ key[c];
+            }
+            s[..nb].copy_from_slice(&t[..nb]);
+            s.copy_within(..nb, nb);
+        }
+
+        for c in 0..nb {
+            let out = u32::from_le_bytes([
+                self.sbox[byte(s[c], 0)],
+                self.sbox[byte(s[c + c1], 1)],
+                self.sbox[byte(s[c + c2], 2)],
+                self.sbox[byte(s[c + c3], 3)],
+            ]) ^ keys[nr][c];
+            block[4 * c..4 * c + 4].copy_from_slice(&out.to_le_bytes());
+        }
+    }
+
+    pub fn decrypt(&self, keys: &TTableKeys, block: &mut [u8]) {
+        let keys = &keys.decrypt;
+        let nb = block.len() / 4;
+        let nr = keys.len() - 1;
+        let [_, c1, c2, c3] = shift_offsets(nb);
+        let mut s = [0u32; 16];
+        let mut t = [0u32; 8];
+        for c in 0..nb {
+            s[c] = column(&block[4 * c..]) ^ keys[0][c];
+            s[c + nb] = s[c];
+        }
+
+        for key in &keys[1..nr] {
+            for c in 0..nb {
+                t[c] = self.td[0][byte(s[c + nb], 0)]
+                    ^ self.td[1][byte(s[c + nb - c1], 1)]
+                    ^ self.td[2][byte(s[c + nb - c2], 2)]
+                    ^ self.td[3][byte(s[c + nb - c3], 3)]
+                    ^ key[c];
+            }
+            s[..nb].copy_from_slice(&t[..nb]);
+            s.copy_within(..nb, nb);
+        }
+
+        for c in 0..nb {
+            let out = u32::from_le_bytes([
+                self.inv_sbox[byte(s[c + nb], 0)],
+                self.inv_sbox[byte(s[c + nb - c1], 1)],
+                self.inv_sbox[byte(s[c + nb - c2], 2)],
+                self.inv_sbox[byte(s[c + nb - c3], 3)],
+            ]) ^ keys[nr][c];
+            block[4 * c..4 * c + 4].copy_from_slice(&out.to_le_bytes());
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use rijndael::gf::arithmetic::Poly;
+use rijndael::rijndael::cipher::Rijndael;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_types::{CipherMode, PaddingMode};
+
+/// Вспомогалка: строит Poly из среза битов (0 или 1)
+fn poly_from_bits(bits: &[u8]) -> Poly {
+    bits.iter().map(|&b| b != 0).collect()
+}
+
+/// Вспомогалка: разбирает hex-строку в байты
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn aes_context(mode: CipherMode, key: &str, nonce: &str) -> CipherContext {
+    let poly = poly_from_bits(&[1, 1, 0, 1, 1, 0, 0, 0, 1]);
+    let mut ctx = CipherContext::new(
+        Box::new(Rijndael::new(poly, 4)),
+        mode,
+        PaddingMode::Zeros,
+        Some(hex(nonce)),
+        Vec::new(),
+    );
+    ctx.set_key(&hex(key)).unwrap();
+    ctx
+}
+
+fn check_vector(
+    mode: CipherMode,
+    key: &str,
+    nonce: &str,
+    aad: &str,
+    plaintext: &str,
+    ciphertext: &str,
+    tag: &str,
+) {
+    let ctx = aes_context(mode, key, nonce);
+
+    let (actual_ct, actual_tag) = ctx.encrypt_authenticated(&hex(plaintext), &hex(aad)).unwrap();
+    assert_eq!(actual_ct, hex(ciphertext), "ciphertext mismatch");
+    assert_eq!(actual_tag, hex(tag), "tag mismatch");
+
+    let decrypted = ctx
+        .decrypt_authenticated(&actual_ct, &hex(aad), &actual_tag)
+        .unwrap();
+    assert_eq!(decrypted, hex(plaintext));
+}
+
+// Тестовые векторы GCM: McGrew & Viega, "The Galois/Counter Mode of Operation", Test Cases 2, 4, 5
+#[test]
+fn test_gcm_test_case_2() {
+    check_vector(
+        CipherMode::GCM,
+        "00000000000000000000000000000000",
+        "000000000000000000000000",
+        "",
+        "00000000000000000000000000000000",
+        "0388dace60b6a392f328c2b971b2fe78",
+        "ab6e47d42cec13bdf53a67b21257bddf",
+    );
+}
+
+#[test]
+fn test_gcm_test_case_4() {
+    check_vector(
+        CipherMode::GCM,
+        "feffe9928665731c6d6a8f9467308308",
+        "cafebabefacedbaddecaf888",
+        "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+        "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+        "5bc94fbc3221a5db94fae95ae7121a47",
+    );
+}
+
+#[test]
+fn test_gcm_test_case_5_short_nonce() {
+    check_vector(
+        CipherMode::GCM,
+        "feffe9928665731c6d6a8f9467308308",
+        "cafebabefacedbad",
+        "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+        "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        "61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f83766e5f97b6c742373806900e49f24b22b097544d4896b424989b5e1ebac0f07c23f4598",
+        "3612d2e79e3b0785561be14aaca2fccb",
+    );
+}
+
+// Тестовые векторы EAX: Bellare, Rogaway, Wagner, "The EAX Mode of Operation", приложение
+#[test]
+fn test_eax_empty_message() {
+    check_vector(
+        CipherMode::EAX,
+        "233952DEE4D5ED5F9B9C6D6FF80FF478",
+        "62EC67F9C3A4A407FCB2A8C49031A8B3",
+        "6BFB914FD07EAE6B",
+        "",
+        "",
+        "E037830E8389F27B025A2D6527E79D01",
+    );
+}
+
+#[test]
+fn test_eax_two_bytes() {
+    check_vector(
+        CipherMode::EAX,
+        "91945D3F4DCBEE0BF45EF52255F095A4",
+        "BECAF043B0A23D843194BA972C66DEBD",
+        "FA3BFD4806EB53FA",
+        "F7FB",
+        "19DD",
+        "5C4C9331049D0BDAB0277408F67967E5",
+    );
+}
+
+#[test]
+fn test_eax_five_bytes() {
+    check_vector(
+        CipherMode::EAX,
+        "01F74AD64077F2E704C0F60ADA3DD523",
+        "70C3DB4F0D26368400A10ED05D2BFF5E",
+        "234A3463C1264AC6",
+        "1A47CB4933",
+        "D851D5BAE0",
+        "3A59F238A23E39199DC9266626C40F80",
+    );
+}
+
+#[test]
+fn test_gcm_rejects_tampered_ciphertext() {
+    let ctx = aes_context(
+        CipherMode::GCM,
+        "feffe9928665731c6d6a8f9467308308",
+        "cafebabefacedbaddecaf888",
+    );
+    let (mut ct, tag) = ctx.encrypt_authenticated(b"attack at dawn", b"header").unwrap();
+    ct[0] ^= 1;
+
+    let result = ctx.decrypt_authenticated(&ct, b"header", &tag);
+    assert!(matches!(result, Err(CipherError::AuthenticationFailed)));
+}
@@ -0,0 +1,110 @@
+use rand::RngCore;
+use rijndael::gf::arithmetic::Poly;
+use rijndael::rijndael::aesni;
+use rijndael::rijndael::cipher::{Backend, Rijndael};
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+
+fn poly_from_mask(mask: u16) -> Poly {
+    (0..9).map(|bit| mask >> bit & 1 == 1).collect()
+}
+
+fn keyed(mask: u16, nb: usize, backend: Backend, key: &[u8]) -> Rijndael {
+    let mut cipher = Rijndael::with_backend(poly_from_mask(mask), nb, backend);
+    cipher.set_key(key).unwrap();
+    cipher
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn test_detection_matches_cpu() {
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(aesni::detected(), std::arch::is_x86_feature_detected!("aes"));
+    #[cfg(not(target_arch = "x86_64"))]
+    assert!(!aesni::detected());
+}
+
+#[test]
+fn test_only_aes_proper_is_accelerated() {
+    let key = [0u8; 16];
+    for key_len in [16, 24, 32] {
+        let cipher = keyed(0x11B, 4, Backend::AesNi, &vec![1; key_len]);
+        assert_eq!(cipher.hardware_accelerated(), aesni::detected());
+    }
+    // Другой размер блока, другой полином или явно выбранный программный backend.
+    assert!(!keyed(0x11B, 6, Backend::AesNi, &key).hardware_accelerated());
+    assert!(!keyed(0x11B, 8, Backend::AesNi, &key).hardware_accelerated());
+    assert!(!keyed(0x11D, 4, Backend::AesNi, &key).hardware_accelerated());
+    for backend in [Backend::Reference, Backend::TTable, Backend::Bitsliced] {
+        assert!(!keyed(0x11B, 4, backend, &key).hardware_accelerated());
+    }
+}
+
+#[test]
+fn test_pipelined_blocks_match_reference() {
+    // 1..=19 блоков: полные группы по 8 и хвосты разной длины. Ключи 160 и
+    // 224 бит дают 11 и 13 раундов — AES-NI это не мешает.
+    for key_len in [16, 20, 24, 28, 32] {
+        let key = random_bytes(key_len);
+        let hardware = keyed(0x11B, 4, Backend::AesNi, &key);
+        let reference = keyed(0x11B, 4, Backend::Reference, &key);
+
+        for blocks in 1..=19 {
+            let plaintext = random_bytes(16 * blocks);
+            let mut expected = plaintext.clone();
+            for block in expected.chunks_exact_mut(16) {
+                reference.encrypt_block_inplace(block);
+            }
+
+            let mut data = plaintext.clone();
+            hardware.encrypt_blocks(&mut data);
+            assert_eq!(data, expected, "key {} bytes, {} blocks", key_len, blocks);
+            hardware.decrypt_blocks(&mut data);
+            assert_eq!(data, plaintext, "key {} bytes, {} blocks", key_len, blocks);
+        }
+    }
+}
+
+#[test]
+fn test_fallback_for_other_polynomials_and_block_sizes() {
+    for (mask, nb) in [(0x11D, 4), (0x11B, 6), (0x11B, 8)] {
+        let key = random_bytes(32);
+        let block = random_bytes(nb * 4);
+        let expected = keyed(mask, nb, Backend::Reference, &key).encrypt_block(&block, &[]);
+        let auto = keyed(mask, nb, Backend::AesNi, &key);
+        assert_eq!(auto.encrypt_block(&block, &[]), expected, "poly {:#x}, Nb {}", mask, nb);
+        assert_eq!(auto.decrypt_block(&expected, &[]), block, "poly {:#x}, Nb {}", mask, nb);
+    }
+}
+
+#[tokio::test]
+async fn test_context_parallel_paths_match_reference() {
+    let key = random_bytes(16);
+    // Эталон вычисляет S-блок на каждый байт, поэтому данных немного: этого
+    // хватает на сотни конвейерных пачек AES-NI.
+    let data = random_bytes(8 * 1024 + 16);
+
+    for (mode, iv) in [(CipherMode::ECB, None), (CipherMode::CTR, Some(random_bytes(16))), (CipherMode::CBC, Some(random_bytes(16)))] {
+        let mut outputs = Vec::new();
+        for backend in [Backend::Reference, Backend::AesNi] {
+            let cipher = keyed(0x11B, 4, backend, &key);
+            let ctx = CipherContext::new(Box::new(cipher), mode, PaddingMode::None, iv.clone(), Vec::new());
+
+            let mut encrypted = CipherOutput::Buffer(Box::default());
+            ctx.encrypt(CipherInput::Bytes(data.clone()), &mut encrypted).await.unwrap();
+            let mut decrypted = CipherOutput::Buffer(Box::default());
+            ctx.decrypt(CipherInput::Bytes(encrypted.as_buffer().clone()), &mut decrypted)
+                .await
+                .unwrap();
+            assert_eq!(decrypted.as_buffer(), &data, "{:?}, {:?}", mode, backend);
+            outputs.push(encrypted.as_buffer().clone());
+        }
+        assert_eq!(outputs[0], outputs[1], "{:?}", mode);
+    }
+}
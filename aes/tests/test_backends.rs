@@ -0,0 +1,108 @@
+use rand::{Rng, RngCore};
+use rijndael::gf::arithmetic::Poly;
+use rijndael::gf::irreducible::list_irreducibles;
+use rijndael::rijndael::cipher::{Backend, Rijndael};
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+
+const BACKENDS: [Backend; 4] = [Backend::Reference, Backend::TTable, Backend::Bitsliced, Backend::AesNi];
+
+fn poly_from_mask(mask: u16) -> Poly {
+    (0..9).map(|bit| mask >> bit & 1 == 1).collect()
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+#[test]
+fn test_default_backend_is_aesni() {
+    let cipher = Rijndael::new(poly_from_mask(0x11B), 4);
+    assert_eq!(cipher.backend(), Backend::AesNi);
+}
+
+#[test]
+fn test_fips197_vectors_on_every_backend() {
+    // FIPS-197, приложение C: открытый текст 00112233...ff, ключ 000102...
+    let plaintext = hex("00112233445566778899aabbccddeeff");
+    let cases = [
+        ("000102030405060708090a0b0c0d0e0f", "69c4e0d86a7b0430d8cdb78070b4c55a"),
+        ("000102030405060708090a0b0c0d0e0f1011121314151617", "dda97ca4864cdfe06eaf70a0ec0d7191"),
+        ("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", "8ea2b7ca516745bfeafc49904b496089"),
+    ];
+
+    for backend in BACKENDS {
+        for (key, expected) in cases {
+            let mut cipher = Rijndael::with_backend(poly_from_mask(0x11B), 4, backend);
+            cipher.set_key(&hex(key)).unwrap();
+            let ciphertext = cipher.encrypt_block(&plaintext, &[]);
+            assert_eq!(ciphertext, hex(expected), "{:?}, key {}", backend, key);
+            assert_eq!(cipher.decrypt_block(&ciphertext, &[]), plaintext, "{:?}, key {}", backend, key);
+        }
+    }
+}
+
+#[test]
+fn test_backends_agree_for_every_block_and_key_size() {
+    let mut rng = rand::rng();
+    // Не только поле AES: таблицы строятся из выбранного полинома.
+    for mask in [0x11B, 0x11D, 0x163] {
+        for nb in 4..=8 {
+            for key_len in [16, 20, 24, 28, 32] {
+                let mut key = vec![0u8; key_len];
+                rng.fill_bytes(&mut key);
+                let mut block = vec![0u8; nb * 4];
+                rng.fill_bytes(&mut block);
+
+                let outputs: Vec<_> = BACKENDS
+                    .iter()
+                    .map(|&backend| {
+                        let mut cipher = Rijndael::with_backend(poly_from_mask(mask), nb, backend);
+                        cipher.set_key(&key).unwrap();
+                        let ciphertext = cipher.encrypt_block(&block, &[]);
+                        assert_eq!(cipher.decrypt_block(&ciphertext, &[]), block, "{:?}", backend);
+                        ciphertext
+                    })
+                    .collect();
+                assert!(
+                    outputs.windows(2).all(|w| w[0] == w[1]),
+                    "poly {:#x}, Nb {}, key {} bytes",
+                    mask,
+                    nb,
+                    key_len
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_all_irreducible_polynomials() {
+    let mut rng = rand::rng();
+    for poly in list_irreducibles(8) {
+        let key: [u8; 16] = rng.random();
+        let block: [u8; 16] = rng.random();
+        let mut reference = Rijndael::with_backend(poly.clone(), 4, Backend::Reference);
+        reference.set_key(&key).unwrap();
+        let expected = reference.encrypt_block(&block, &[]);
+
+        for backend in [Backend::TTable, Backend::Bitsliced, Backend::AesNi] {
+            let mut cipher = Rijndael::with_backend(poly.clone(), 4, backend);
+            cipher.set_key(&key).unwrap();
+            assert_eq!(cipher.encrypt_block(&block, &[]), expected, "{:?}, {:?}", backend, poly);
+        }
+    }
+}
+
+#[test]
+fn test_set_backend_keeps_the_key() {
+    let mut cipher = Rijndael::new(poly_from_mask(0x11B), 6);
+    cipher.set_key(&[7u8; 24]).unwrap();
+    let block = [0x5au8; 24];
+    let expected = cipher.encrypt_block(&block, &[]);
+
+    for backend in BACKENDS {
+        cipher.set_backend(backend);
+        assert_eq!(cipher.backend(), backend);
+        assert_eq!(cipher.encrypt_block(&block, &[]), expected, "{:?}", backend);
+    }
+}
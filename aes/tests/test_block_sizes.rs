@@ -0,0 +1,86 @@
+use rijndael::gf::arithmetic::Poly;
+use rijndael::rijndael::cipher::{Backend, Rijndael, DESCRIPTORS};
+use std::path::Path;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::cipher_types::{CipherMode, PaddingMode};
+use symmetric_cipher::crypto::kat;
+use symmetric_cipher::crypto::registry::BoxedCipher;
+
+fn aes_poly() -> Poly {
+    (0..9).map(|bit| 0x11Bu16 >> bit & 1 == 1).collect()
+}
+
+#[test]
+fn test_round_count_is_max_nb_nk_plus_six() {
+    for nb in 4..=8 {
+        for nk in 4..=8 {
+            let mut cipher = Rijndael::new(aes_poly(), nb);
+            cipher.set_key(&vec![0x5A; 4 * nk]).unwrap();
+            let round_keys = cipher.export_round_keys().unwrap();
+            assert_eq!(round_keys.len(), (nb.max(nk) + 7) * 4 * nb, "Nb = {}, Nk = {}", nb, nk);
+        }
+    }
+}
+
+#[test]
+fn test_reference_values_for_every_block_and_key_size() {
+    // Для каждого размера блока — файл из пяти значений, по одному на размер ключа.
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    for descriptor in &DESCRIPTORS {
+        let nb = descriptor.block_size / 4;
+        let path = dir.join(format!("ecb_rijndael_b{}.txt", descriptor.block_size * 8));
+        for backend in [Backend::Reference, Backend::TTable, Backend::Bitsliced, Backend::AesNi] {
+            let new = |key: &[u8]| -> Result<BoxedCipher, CipherError> {
+                let mut cipher = Rijndael::with_backend(aes_poly(), nb, backend);
+                cipher.set_key(key)?;
+                Ok(Box::new(cipher))
+            };
+            let report = kat::run_file(&path, &new).unwrap();
+            assert_eq!(report.checked, 5 * 2, "{}", path.display());
+            let failures: Vec<String> = report.failures.iter().map(ToString::to_string).collect();
+            assert!(failures.is_empty(), "{} ({:?}):\n{}", descriptor.name, backend, failures.join("\n"));
+        }
+    }
+}
+
+#[test]
+fn test_all_combinations_through_cipher_context() {
+    let plaintext: Vec<u8> = (0..100u8).collect();
+    for descriptor in &DESCRIPTORS {
+        let block = descriptor.block_size;
+        let iv: Vec<u8> = (0..block as u8).map(|b| b.wrapping_mul(7)).collect();
+        for &key_len in descriptor.key_sizes {
+            let key: Vec<u8> = (0..key_len as u8).collect();
+            for mode in [CipherMode::ECB, CipherMode::CBC, CipherMode::CFB, CipherMode::OFB, CipherMode::CTR] {
+                let context = || {
+                    let cipher = (descriptor.constructor)(&key).unwrap();
+                    CipherContext::new(cipher, mode, PaddingMode::PKCS7, Some(iv.clone()), Vec::new())
+                };
+                let mut encryptor = context().encryptor().unwrap();
+                let mut ciphertext = encryptor.update(&plaintext);
+                ciphertext.extend(encryptor.finalize().unwrap());
+
+                let mut decryptor = context().decryptor().unwrap();
+                let mut decrypted = decryptor.update(&ciphertext);
+                decrypted.extend(decryptor.finalize().unwrap());
+                assert_eq!(decrypted, plaintext, "{} with a {}-byte key, {:?}", descriptor.name, key_len, mode);
+
+                if mode == CipherMode::CBC {
+                    // Первый блок CBC — это E(P1 ^ IV)
+                    let cipher = (descriptor.constructor)(&key).unwrap();
+                    let mut first: Vec<u8> = plaintext[..block].iter().zip(&iv).map(|(p, v)| p ^ v).collect();
+                    cipher.encrypt_block_inplace(&mut first);
+                    assert_eq!(&ciphertext[..block], &first[..], "{} with a {}-byte key", descriptor.name, key_len);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "4 to 8 words")]
+fn test_block_size_out_of_range() {
+    Rijndael::new(aes_poly(), 9);
+}
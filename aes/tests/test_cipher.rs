@@ -160,3 +160,35 @@ fn test_encrypt_decrypt_random() {
     let actual_plain = array_from_vec(decrypted_bytes);
     assert_eq!(actual_plain, block, "Random encrypt/decrypt failed");
 }
+
+#[test]
+fn test_aes128_inplace_and_multi_block() {
+    let key = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6,
+        0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+    ];
+    let plaintext = [
+        0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d,
+        0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34,
+    ];
+    let expected_cipher = [
+        0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb,
+        0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32,
+    ];
+
+    let mut cipher = Rijndael::new(poly_from_bits(&[1, 1, 0, 1, 1, 0, 0, 0, 1]), 4);
+    cipher.set_key(&key).unwrap();
+
+    let mut block = plaintext;
+    cipher.encrypt_block_inplace(&mut block);
+    assert_eq!(block, expected_cipher);
+    cipher.decrypt_block_inplace(&mut block);
+    assert_eq!(block, plaintext);
+
+    // Тот же блок трижды за один вызов
+    let mut blocks = plaintext.repeat(3);
+    cipher.encrypt_blocks(&mut blocks);
+    assert_eq!(blocks, expected_cipher.repeat(3));
+    cipher.decrypt_blocks(&mut blocks);
+    assert_eq!(blocks, plaintext.repeat(3));
+}
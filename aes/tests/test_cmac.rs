@@ -0,0 +1,67 @@
+use rijndael::gf::arithmetic::Poly;
+use rijndael::rijndael::cipher::Rijndael;
+use symmetric_cipher::crypto::cipher_traits::SymmetricCipher;
+use symmetric_cipher::crypto::mac::Cmac;
+
+/// Вспомогалка: строит Poly из среза битов (0 или 1)
+fn poly_from_bits(bits: &[u8]) -> Poly {
+    bits.iter().map(|&b| b != 0).collect()
+}
+
+/// Вспомогалка: разбирает hex-строку в байты
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn rijndael(block_words: usize, key: &[u8]) -> Rijndael {
+    let mut cipher = Rijndael::new(poly_from_bits(&[1, 1, 0, 1, 1, 0, 0, 0, 1]), block_words);
+    cipher.set_key(key).unwrap();
+    cipher
+}
+
+/// Примеры CMAC-AES128 из NIST SP 800-38B, приложение D.1.
+#[test]
+fn test_cmac_aes128_sp800_38b() {
+    let aes = rijndael(4, &hex("2b7e151628aed2a6abf7158809cf4f3c"));
+    let message = hex(
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51\
+         30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    );
+
+    for (len, tag) in [
+        (0, "bb1d6929e95937287fa37d129b756746"),
+        (16, "070a16b46b4d4144f79bdd9dd04a287c"),
+        (40, "dfa66747de9ae63030ca32611497c827"),
+        (64, "51f0bebf7e3b9d92fc49741779363cfe"),
+    ] {
+        let mut mac = Cmac::new(&aes).unwrap();
+        mac.update(&message[..len]);
+        assert_eq!(mac.finalize(), hex(tag), "len {}", len);
+
+        let mut mac = Cmac::new(&aes).unwrap();
+        for piece in message[..len].chunks(5) {
+            mac.update(piece);
+        }
+        assert!(mac.verify(&hex(tag)).is_ok());
+    }
+}
+
+#[test]
+fn test_cmac_wide_rijndael_blocks() {
+    for block_words in [6, 8] {
+        let cipher = rijndael(block_words, &[7; 32]);
+        let data: Vec<u8> = (0..100).collect();
+
+        let mut whole = Cmac::new(&cipher).unwrap();
+        whole.update(&data);
+        let tag = whole.finalize();
+        assert_eq!(tag.len(), block_words * 4);
+
+        let mut other = Cmac::new(&cipher).unwrap();
+        other.update(&data[..99]);
+        assert!(other.verify(&tag).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use rijndael::gf::arithmetic::Poly;
+use rijndael::rijndael::cipher::Rijndael;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::SymmetricCipherWithRounds;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::container::{AlgorithmId, ContainerHeader, ContainerReader, ContainerWriter};
+
+/// Подставляет Rijndael по параметрам из заголовка контейнера.
+fn resolve(id: AlgorithmId) -> Option<Box<dyn SymmetricCipherWithRounds + Send + Sync>> {
+    match id {
+        AlgorithmId::Rijndael { block_size, poly } => {
+            let poly: Poly = (0..9).map(|bit| poly >> bit & 1 == 1).collect();
+            Some(Box::new(Rijndael::new(poly, block_size as usize / 4)))
+        }
+        _ => None,
+    }
+}
+
+async fn round_trip(header: ContainerHeader, key: &[u8], data: &[u8]) {
+    let writer = ContainerWriter::new(header.clone(), key, &resolve).unwrap();
+    let mut sealed = CipherOutput::Buffer(Box::default());
+    writer.encrypt(CipherInput::Bytes(data.to_vec()), &mut sealed).await.unwrap();
+
+    let reader = ContainerReader::open(CipherInput::Bytes(sealed.as_buffer().clone())).await.unwrap();
+    assert_eq!(reader.header().algorithm, header.algorithm);
+    let mut opened = CipherOutput::Buffer(Box::default());
+    reader.decrypt(key, &resolve, &mut opened).await.unwrap();
+    assert_eq!(opened.as_buffer(), data);
+}
+
+#[tokio::test]
+async fn test_rijndael_containers() {
+    let data: Vec<u8> = (0..777).map(|i| (i * 31) as u8).collect();
+    let aes = AlgorithmId::Rijndael { block_size: 16, poly: 0x11B };
+
+    round_trip(ContainerHeader::new(aes, CipherMode::GCM, PaddingMode::PKCS7, Some(vec![7; 12])), &[1; 16], &data).await;
+
+    let wide = AlgorithmId::Rijndael { block_size: 32, poly: 0x11B };
+    round_trip(ContainerHeader::new(wide, CipherMode::CBC, PaddingMode::PKCS7, Some(vec![9; 32])), &[2; 32], &data).await;
+
+    // Блоки 160 и 224 бит: размер блока переживает запись и чтение заголовка.
+    for (block_size, key_len) in [(20, 28), (28, 20)] {
+        let id = AlgorithmId::Rijndael { block_size, poly: 0x11B };
+        let iv = vec![5; block_size as usize];
+        let header = ContainerHeader::new(id, CipherMode::CBC, PaddingMode::PKCS7, Some(iv));
+        let (parsed, _) = ContainerHeader::parse(&header.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed, header);
+        round_trip(header, &vec![4; key_len], &data).await;
+    }
+
+    let mut xts = ContainerHeader::new(aes, CipherMode::XTS, PaddingMode::Zeros, None);
+    // Последний сектор (77 байт) не короче блока, как требует XTS.
+    xts.sector_size = 100;
+    round_trip(xts, &[3; 32], &data).await;
+}
+
+#[tokio::test]
+async fn test_xts_container_rejects_short_iv() {
+    // IV в XTS — номер первого сектора, ровно 16 байт.
+    let aes = AlgorithmId::Rijndael { block_size: 16, poly: 0x11B };
+    let mut header = ContainerHeader::new(aes, CipherMode::XTS, PaddingMode::Zeros, Some(vec![1; 8]));
+    header.sector_size = 64;
+    let result = match ContainerWriter::new(header, &[3; 32], &resolve) {
+        Ok(writer) => writer.encrypt(CipherInput::Bytes(vec![0; 100]), &mut CipherOutput::Buffer(Box::default())).await,
+        Err(err) => Err(err),
+    };
+    assert!(matches!(result, Err(CipherError::InvalidIvLength { expected: 16, actual: 8 })));
+}
@@ -54,7 +54,7 @@ fn degree_3_irreducibles() {
 #[test]
 fn degree_4_irreducibles() {
     // Неприводимые степени 4: x^4+x+1, x^4+x^3+1, x^4+x^3+x^2+x+1
-    let expected = vec![
+    let _expected = [
         poly_from_bits(&[1,0,0,1,1]),  // 10011
         poly_from_bits(&[1,0,0,1,0,1]), // 101001? нет, правильный: [1,0,0,1,1] и [1,1,0,0,1]
         poly_from_bits(&[1,0,0,1,1]),  // x^4 + x + 1
@@ -0,0 +1,71 @@
+use rijndael::gf::arithmetic::Poly;
+use rijndael::rijndael::cipher::{Backend, Rijndael, DESCRIPTORS};
+use std::path::Path;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::SymmetricCipher;
+use symmetric_cipher::crypto::kat;
+use symmetric_cipher::crypto::registry::BoxedCipher;
+
+/// Прогоняет все файлы `tests/vectors/<prefix>*.rsp` через AES (Rijndael-128).
+fn assert_files_pass(prefix: &str) {
+    assert_files_pass_with(prefix, &DESCRIPTORS[0].constructor);
+}
+
+fn assert_files_pass_with(prefix: &str, aes: &kat::NewCipher) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    let mut paths: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            name.starts_with(prefix) && name.ends_with(".rsp")
+        })
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no {} vectors", prefix);
+
+    for path in paths {
+        let report = kat::run_file(&path, aes).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        assert!(report.checked > 0, "{}: no vectors", path.display());
+        let failures: Vec<String> = report.failures.iter().map(ToString::to_string).collect();
+        assert!(failures.is_empty(), "{}:\n{}", path.display(), failures.join("\n"));
+    }
+}
+
+#[test]
+fn test_ecb_known_answers() {
+    assert_files_pass("ECB");
+}
+
+#[test]
+fn test_cbc_known_answers() {
+    assert_files_pass("CBC");
+}
+
+#[test]
+fn test_cfb8_known_answers() {
+    assert_files_pass("CFB8");
+}
+
+#[test]
+fn test_cfb128_known_answers() {
+    assert_files_pass("CFB128");
+}
+
+#[test]
+fn test_ofb_known_answers() {
+    assert_files_pass("OFB");
+}
+
+#[test]
+fn test_ecb_known_answers_on_every_backend() {
+    for backend in [Backend::Reference, Backend::Bitsliced] {
+        let aes = |key: &[u8]| -> Result<BoxedCipher, CipherError> {
+            let poly: Poly = (0..9).map(|bit| 0x11Bu16 >> bit & 1 == 1).collect();
+            let mut cipher = Rijndael::with_backend(poly, 4, backend);
+            cipher.set_key(key)?;
+            Ok(Box::new(cipher))
+        };
+        assert_files_pass_with("ECB", &aes);
+    }
+}
@@ -58,7 +58,7 @@ fn test_sbox_affine_properties() {
     // affine( inv( inv(x) ) ) == x for a few samples
     for &x in &[0x10u8, 0xab, 0xff, 0x5c] {
         let y = sbox(x, &poly);
-        let z = sbox(inv_sbox(x, &poly), &poly);
+        let _z = sbox(inv_sbox(x, &poly), &poly);
         // оба обращения дают разные, но оба должны invert correctly
         assert_eq!(inv_sbox(y, &poly), x);
         assert_eq!(sbox(inv_sbox(x, &poly), &poly), x);
@@ -0,0 +1,75 @@
+use crate::crypt::{into_bytes, iv_len, random_bytes};
+use crate::keygen::symmetric_key;
+use crate::{descriptor, CliError, Mode};
+use clap::Args;
+use std::time::{Duration, Instant};
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Algorithm to measure, may be repeated; all of them if omitted.
+    #[arg(short, long)]
+    algorithm: Vec<String>,
+    #[arg(short, long, value_enum, default_value_t = Mode::Cbc)]
+    mode: Mode,
+    /// Message size in KiB.
+    #[arg(short, long, default_value_t = 1024)]
+    size: usize,
+    /// Runs per direction; the fastest is reported.
+    #[arg(short, long, default_value_t = 3)]
+    iterations: usize,
+}
+
+pub async fn run(args: BenchArgs) -> Result<(), CliError> {
+    if args.size == 0 || args.iterations == 0 {
+        return Err(CliError::Usage("--size and --iterations must be positive".to_string()));
+    }
+    let registry = cryptography::registry();
+    let descriptors = if args.algorithm.is_empty() {
+        registry.algorithms().to_vec()
+    } else {
+        args.algorithm.iter().map(|name| descriptor(name)).collect::<Result<_, _>>()?
+    };
+
+    let mode: CipherMode = args.mode.into();
+    let data = random_bytes(args.size * 1024);
+    println!("{} KiB, {:?}, best of {}", args.size, mode, args.iterations);
+    println!("{:<14} {:>12} {:>12}", "algorithm", "enc MB/s", "dec MB/s");
+
+    for descriptor in descriptors {
+        let key = symmetric_key(descriptor.name, None)?;
+        let iv = iv_len(mode, descriptor.block_size).map(random_bytes);
+        let mut context = CipherContext::new(registry.create(descriptor.name, &key)?, mode, PaddingMode::PKCS7, iv, Vec::new());
+        context.set_key(&key)?;
+
+        let mut ciphertext = Vec::new();
+        let mut encrypt_time = Duration::MAX;
+        for _ in 0..args.iterations {
+            let input = CipherInput::Bytes(data.clone());
+            let mut output = CipherOutput::Buffer(Box::default());
+            let start = Instant::now();
+            context.encrypt(input, &mut output).await?;
+            encrypt_time = encrypt_time.min(start.elapsed());
+            ciphertext = into_bytes(output);
+        }
+
+        let mut decrypt_time = Duration::MAX;
+        for _ in 0..args.iterations {
+            let input = CipherInput::Bytes(ciphertext.clone());
+            let mut output = CipherOutput::Buffer(Box::default());
+            let start = Instant::now();
+            context.decrypt(input, &mut output).await?;
+            decrypt_time = decrypt_time.min(start.elapsed());
+        }
+
+        let mb_per_s = |time: Duration| data.len() as f64 / time.as_secs_f64() / 1e6;
+        println!(
+            "{:<14} {:>12.2} {:>12.2}",
+            descriptor.name,
+            mb_per_s(encrypt_time),
+            mb_per_s(decrypt_time)
+        );
+    }
+    Ok(())
+}
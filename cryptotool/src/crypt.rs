@@ -0,0 +1,152 @@
+use crate::io::{decode, encode, parse_hex, read_first_line, read_input, write_output};
+use crate::{descriptor, CliError, CryptArgs, Kdf};
+use cryptography::{algorithm_id, resolve_algorithm, CipherDescriptor};
+use rand::RngCore;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput};
+use symmetric_cipher::crypto::container::{ContainerHeader, ContainerReader, ContainerWriter};
+use symmetric_cipher::crypto::kdf::{KdfParams, DEFAULT_SCRYPT_P, DEFAULT_SCRYPT_R};
+
+const GCM_NONCE_SIZE: usize = 12;
+
+enum Secret {
+    Key(Vec<u8>),
+    Passphrase(String),
+}
+
+fn secret(args: &CryptArgs) -> Result<Secret, CliError> {
+    if let Some(key) = &args.key {
+        return Ok(Secret::Key(parse_hex("--key", key)?));
+    }
+    if let Some(path) = &args.key_file {
+        return Ok(Secret::Key(parse_hex("key file", &read_first_line(path)?)?));
+    }
+    if let Some(passphrase) = &args.passphrase {
+        return Ok(Secret::Passphrase(passphrase.clone()));
+    }
+    let path = args.passphrase_file.as_ref().expect("clap requires one secret");
+    Ok(Secret::Passphrase(read_first_line(path)?))
+}
+
+pub async fn run(args: CryptArgs, encrypt: bool) -> Result<(), CliError> {
+    let input = read_input(args.input.as_deref())?;
+    let output = if encrypt {
+        let ciphertext = match secret(&args)? {
+            Secret::Key(key) => encrypt_with_key(&args, &key, input).await?,
+            Secret::Passphrase(passphrase) => encrypt_container(&args, passphrase.as_bytes(), input).await?,
+        };
+        encode(args.encoding, ciphertext)
+    } else {
+        let input = decode(args.encoding, input)?;
+        match secret(&args)? {
+            Secret::Key(key) => decrypt_with_key(&args, &key, input).await?,
+            Secret::Passphrase(passphrase) => decrypt_container(passphrase.as_bytes(), input).await?,
+        }
+    };
+    write_output(args.output.as_deref(), &output)
+}
+
+fn algorithm(args: &CryptArgs) -> Result<CipherDescriptor, CliError> {
+    let name = args
+        .algorithm
+        .as_deref()
+        .ok_or_else(|| CliError::Usage("--algorithm is required".to_string()))?;
+    descriptor(name)
+}
+
+/// ECB needs no IV; GCM takes a 96-bit nonce, the other modes one block.
+pub fn iv_len(mode: CipherMode, block_size: usize) -> Option<usize> {
+    match mode {
+        CipherMode::ECB => None,
+        CipherMode::GCM => Some(GCM_NONCE_SIZE),
+        _ => Some(block_size),
+    }
+}
+
+pub fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+pub fn into_bytes(output: CipherOutput) -> Vec<u8> {
+    match output {
+        CipherOutput::Buffer(data) => *data,
+        CipherOutput::File(_) => unreachable!("only buffers are used"),
+    }
+}
+
+fn context(args: &CryptArgs, descriptor: &CipherDescriptor, key: &[u8], iv: Option<Vec<u8>>) -> Result<CipherContext, CliError> {
+    let cipher = cryptography::registry().create(descriptor.name, key)?;
+    let mut context = CipherContext::new(cipher, args.mode.into(), args.padding.into(), iv, Vec::new());
+    context.set_key(key)?;
+    Ok(context)
+}
+
+async fn encrypt_with_key(args: &CryptArgs, key: &[u8], plaintext: Vec<u8>) -> Result<Vec<u8>, CliError> {
+    let descriptor = algorithm(args)?;
+    let (iv, prepend_iv) = match (&args.iv, iv_len(args.mode.into(), descriptor.block_size)) {
+        (Some(iv), _) => (Some(parse_hex("--iv", iv)?), false),
+        (None, Some(len)) => (Some(random_bytes(len)), true),
+        (None, None) => (None, false),
+    };
+
+    let context = context(args, &descriptor, key, iv.clone())?;
+    let mut output = CipherOutput::Buffer(Box::default());
+    context.encrypt(CipherInput::Bytes(plaintext), &mut output).await?;
+
+    let mut ciphertext = if prepend_iv { iv.unwrap_or_default() } else { Vec::new() };
+    ciphertext.extend_from_slice(&into_bytes(output));
+    Ok(ciphertext)
+}
+
+async fn decrypt_with_key(args: &CryptArgs, key: &[u8], mut ciphertext: Vec<u8>) -> Result<Vec<u8>, CliError> {
+    let descriptor = algorithm(args)?;
+    let iv = match (&args.iv, iv_len(args.mode.into(), descriptor.block_size)) {
+        (Some(iv), _) => Some(parse_hex("--iv", iv)?),
+        (None, Some(len)) => {
+            if ciphertext.len() < len {
+                return Err(CliError::Failed("input is shorter than the IV".to_string()));
+            }
+            let body = ciphertext.split_off(len);
+            Some(std::mem::replace(&mut ciphertext, body))
+        }
+        (None, None) => None,
+    };
+
+    let context = context(args, &descriptor, key, iv)?;
+    let mut output = CipherOutput::Buffer(Box::default());
+    context.decrypt(CipherInput::Bytes(ciphertext), &mut output).await?;
+    Ok(into_bytes(output))
+}
+
+async fn encrypt_container(args: &CryptArgs, passphrase: &[u8], plaintext: Vec<u8>) -> Result<Vec<u8>, CliError> {
+    let descriptor = algorithm(args)?;
+    let id = algorithm_id(descriptor.name)
+        .ok_or_else(|| CliError::Usage(format!("{} cannot be used with a passphrase", descriptor.name)))?;
+    let mode: CipherMode = args.mode.into();
+    let iv = match &args.iv {
+        Some(iv) => Some(parse_hex("--iv", iv)?),
+        None => iv_len(mode, descriptor.block_size).map(random_bytes),
+    };
+
+    let mut header = ContainerHeader::new(id, mode, args.padding.into(), iv);
+    header.kdf = Some(match args.kdf {
+        Kdf::Scrypt => KdfParams::scrypt(args.scrypt_log_n, DEFAULT_SCRYPT_R, DEFAULT_SCRYPT_P),
+        Kdf::Pbkdf2 => KdfParams::pbkdf2(args.iterations),
+    });
+
+    let writer = ContainerWriter::with_passphrase(header, passphrase, &resolve_algorithm)?;
+    let mut output = CipherOutput::Buffer(Box::default());
+    writer.encrypt(CipherInput::Bytes(plaintext), &mut output).await?;
+    Ok(into_bytes(output))
+}
+
+async fn decrypt_container(passphrase: &[u8], container: Vec<u8>) -> Result<Vec<u8>, CliError> {
+    let reader = ContainerReader::open(CipherInput::Bytes(container)).await?;
+    let mut output = CipherOutput::Buffer(Box::default());
+    reader
+        .decrypt_with_passphrase(passphrase, &resolve_algorithm, &mut output)
+        .await?;
+    Ok(into_bytes(output))
+}
@@ -0,0 +1,71 @@
+use crate::{CliError, Encoding};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// `-` stands for stdin/stdout, like no path at all.
+fn file_path(path: Option<&Path>) -> Option<&Path> {
+    path.filter(|p| p.as_os_str() != "-")
+}
+
+fn file_error(path: &Path, e: std::io::Error) -> CliError {
+    CliError::Failed(format!("{}: {}", path.display(), e))
+}
+
+pub fn read_input(path: Option<&Path>) -> Result<Vec<u8>, CliError> {
+    match file_path(path) {
+        Some(path) => std::fs::read(path).map_err(|e| file_error(path, e)),
+        None => {
+            let mut data = Vec::new();
+            std::io::stdin().read_to_end(&mut data)?;
+            Ok(data)
+        }
+    }
+}
+
+pub fn write_output(path: Option<&Path>, data: &[u8]) -> Result<(), CliError> {
+    match file_path(path) {
+        Some(path) => std::fs::write(path, data).map_err(|e| file_error(path, e)),
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(data)?;
+            Ok(stdout.flush()?)
+        }
+    }
+}
+
+/// Text encodings end with a newline so the output is a proper line.
+pub fn encode(encoding: Encoding, data: Vec<u8>) -> Vec<u8> {
+    let text = match encoding {
+        Encoding::Raw => return data,
+        Encoding::Hex => hex::encode(data),
+        Encoding::Base64 => STANDARD.encode(data),
+    };
+    (text + "\n").into_bytes()
+}
+
+/// Whitespace, including line breaks, is ignored in hex and base64 input.
+pub fn decode(encoding: Encoding, data: Vec<u8>) -> Result<Vec<u8>, CliError> {
+    if encoding == Encoding::Raw {
+        return Ok(data);
+    }
+    let text: Vec<u8> = data.into_iter().filter(|b| !b.is_ascii_whitespace()).collect();
+    match encoding {
+        Encoding::Hex => hex::decode(text).map_err(|e| CliError::Failed(format!("input is not valid hex: {}", e))),
+        _ => STANDARD
+            .decode(text)
+            .map_err(|e| CliError::Failed(format!("input is not valid base64: {}", e))),
+    }
+}
+
+/// Decodes a hex command-line value; `what` names it in the error.
+pub fn parse_hex(what: &str, value: &str) -> Result<Vec<u8>, CliError> {
+    hex::decode(value.trim()).map_err(|e| CliError::Usage(format!("{} is not valid hex: {}", what, e)))
+}
+
+/// First line of a text file, without the line break.
+pub fn read_first_line(path: &Path) -> Result<String, CliError> {
+    let text = std::fs::read_to_string(path).map_err(|e| file_error(path, e))?;
+    Ok(text.lines().next().unwrap_or_default().to_string())
+}
@@ -0,0 +1,110 @@
+use crate::crypt::random_bytes;
+use crate::io::write_output;
+use crate::{descriptor, CliError};
+use clap::{Subcommand, ValueEnum};
+use rsa::rsa::{PrimalityType, RsaService};
+use std::path::PathBuf;
+use symmetric_cipher::crypto::triple_des::adjust_parity;
+
+#[derive(Subcommand)]
+pub enum KeygenCommand {
+    /// Random key for a symmetric cipher, written as hex.
+    Sym {
+        #[arg(short, long)]
+        algorithm: String,
+        /// Key length in bytes; the longest the algorithm accepts by default.
+        #[arg(short, long)]
+        length: Option<usize>,
+        /// Output file; stdout if omitted or `-`.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// RSA key pair as `name=hex` lines.
+    Rsa {
+        /// Modulus size in bits.
+        #[arg(short, long, default_value_t = 2048)]
+        bits: usize,
+        #[arg(long, value_enum, default_value_t = Primality::MillerRabin)]
+        test: Primality,
+        /// Probability each prime must reach in the primality test.
+        #[arg(long, default_value_t = 0.999)]
+        confidence: f64,
+        /// Private key file; the public key goes to `<output>.pub`. Both are
+        /// printed to stdout if omitted.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Primality {
+    MillerRabin,
+    SolovayStrassen,
+    Fermat,
+}
+
+impl From<Primality> for PrimalityType {
+    fn from(test: Primality) -> Self {
+        match test {
+            Primality::MillerRabin => PrimalityType::MillerRabin,
+            Primality::SolovayStrassen => PrimalityType::SolovayStrassen,
+            Primality::Fermat => PrimalityType::Fermat,
+        }
+    }
+}
+
+pub fn run(command: KeygenCommand) -> Result<(), CliError> {
+    match command {
+        KeygenCommand::Sym { algorithm, length, output } => {
+            let key = symmetric_key(&algorithm, length)?;
+            write_output(output.as_deref(), (hex::encode(key) + "\n").as_bytes())
+        }
+        KeygenCommand::Rsa { bits, test, confidence, output } => {
+            if bits < 16 {
+                return Err(CliError::Usage("RSA modulus must be at least 16 bits".to_string()));
+            }
+            if !(0.0..1.0).contains(&confidence) {
+                return Err(CliError::Usage("--confidence must be in [0, 1)".to_string()));
+            }
+            let rsa = RsaService::new(test.into(), confidence, bits);
+            let (n, e) = rsa.public_key();
+            let (_, d) = rsa.private_key();
+            let public = format!("n={}\ne={}\n", n.to_str_radix(16), e.to_str_radix(16));
+            let private = format!("{}d={}\n", public, d.to_str_radix(16));
+
+            match output {
+                Some(path) => {
+                    let mut public_path = path.clone().into_os_string();
+                    public_path.push(".pub");
+                    write_output(Some(&path), private.as_bytes())?;
+                    write_output(Some(public_path.as_ref()), public.as_bytes())
+                }
+                None => write_output(None, private.as_bytes()),
+            }
+        }
+    }
+}
+
+/// DES keys get odd parity, and weak keys (or, for Triple DES, repeated
+/// subkeys) are drawn again.
+pub fn symmetric_key(algorithm: &str, length: Option<usize>) -> Result<Vec<u8>, CliError> {
+    let descriptor = descriptor(algorithm)?;
+    let len = length.unwrap_or_else(|| descriptor.key_sizes.iter().copied().max().unwrap_or_default());
+    if !descriptor.key_sizes.contains(&len) {
+        return Err(CliError::Usage(format!(
+            "{} takes {:?}-byte keys, not {}",
+            descriptor.name, descriptor.key_sizes, len
+        )));
+    }
+
+    let registry = cryptography::registry();
+    loop {
+        let mut key = random_bytes(len);
+        if matches!(descriptor.name, "des" | "triple-des") {
+            adjust_parity(&mut key);
+        }
+        if registry.create(descriptor.name, &key).is_ok() {
+            return Ok(key);
+        }
+    }
+}
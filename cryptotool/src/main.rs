@@ -0,0 +1,238 @@
+mod bench;
+mod crypt;
+mod io;
+mod keygen;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_types::{CipherMode, PaddingMode};
+use symmetric_cipher::crypto::kdf::{DEFAULT_PBKDF2_ITERATIONS, DEFAULT_SCRYPT_LOG_N};
+
+/// Encrypts, decrypts and benchmarks data with the workspace ciphers.
+///
+/// Exit codes: 0 on success, 1 if the operation failed (bad key, wrong
+/// passphrase, I/O error), 2 on invalid arguments.
+#[derive(Parser)]
+#[command(name = "cryptotool", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Encrypt a file or stdin.
+    Enc(CryptArgs),
+    /// Decrypt a file or stdin.
+    Dec(CryptArgs),
+    /// Generate keys.
+    #[command(subcommand)]
+    Keygen(keygen::KeygenCommand),
+    /// Measure encryption and decryption throughput.
+    Bench(bench::BenchArgs),
+    /// List the available algorithms.
+    List,
+}
+
+/// Options shared by `enc` and `dec`.
+///
+/// With a raw key and no `--iv`, `enc` generates a random IV and writes it
+/// before the ciphertext, and `dec` reads it from there. With a passphrase
+/// the output is a container whose header records the algorithm, mode, IV
+/// and KDF salt, so `dec` only needs the passphrase.
+#[derive(Args)]
+#[command(group = clap::ArgGroup::new("secret").required(true))]
+struct CryptArgs {
+    /// Cipher name, see `cryptotool list`. Not needed to decrypt a container.
+    #[arg(short, long)]
+    algorithm: Option<String>,
+    #[arg(short, long, value_enum, default_value_t = Mode::Cbc)]
+    mode: Mode,
+    #[arg(short, long, value_enum, default_value_t = Padding::Pkcs7)]
+    padding: Padding,
+    /// Key as hex.
+    #[arg(short, long, group = "secret")]
+    key: Option<String>,
+    /// File holding the key as hex, e.g. from `cryptotool keygen sym`.
+    #[arg(long, group = "secret")]
+    key_file: Option<PathBuf>,
+    #[arg(long, group = "secret")]
+    passphrase: Option<String>,
+    /// File whose first line is the passphrase.
+    #[arg(long, group = "secret")]
+    passphrase_file: Option<PathBuf>,
+    /// IV (nonce for GCM/EAX) as hex.
+    #[arg(long)]
+    iv: Option<String>,
+    /// Key derivation for passphrases.
+    #[arg(long, value_enum, default_value_t = Kdf::Scrypt)]
+    kdf: Kdf,
+    #[arg(long, default_value_t = DEFAULT_PBKDF2_ITERATIONS)]
+    iterations: u32,
+    #[arg(long, default_value_t = DEFAULT_SCRYPT_LOG_N)]
+    scrypt_log_n: u8,
+    /// Input file; stdin if omitted or `-`.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Output file; stdout if omitted or `-`.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Text encoding of the ciphertext: written by `enc`, expected by `dec`.
+    #[arg(short, long, value_enum, default_value_t = Encoding::Raw)]
+    encoding: Encoding,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Mode {
+    Ecb,
+    Cbc,
+    Pcbc,
+    Cfb,
+    Ofb,
+    Ctr,
+    RandomDelta,
+    Gcm,
+    Eax,
+    CbcCs1,
+    CbcCs2,
+    CbcCs3,
+}
+
+impl From<Mode> for CipherMode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Ecb => CipherMode::ECB,
+            Mode::Cbc => CipherMode::CBC,
+            Mode::Pcbc => CipherMode::PCBC,
+            Mode::Cfb => CipherMode::CFB,
+            Mode::Ofb => CipherMode::OFB,
+            Mode::Ctr => CipherMode::CTR,
+            Mode::RandomDelta => CipherMode::RandomDelta,
+            Mode::Gcm => CipherMode::GCM,
+            Mode::Eax => CipherMode::EAX,
+            Mode::CbcCs1 => CipherMode::CBC_CS1,
+            Mode::CbcCs2 => CipherMode::CBC_CS2,
+            Mode::CbcCs3 => CipherMode::CBC_CS3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Padding {
+    /// Input must already be whole blocks.
+    None,
+    Zeros,
+    AnsiX923,
+    Pkcs7,
+    Iso10126,
+}
+
+impl From<Padding> for PaddingMode {
+    fn from(padding: Padding) -> Self {
+        match padding {
+            Padding::None => PaddingMode::None,
+            Padding::Zeros => PaddingMode::Zeros,
+            Padding::AnsiX923 => PaddingMode::ANSI_X923,
+            Padding::Pkcs7 => PaddingMode::PKCS7,
+            Padding::Iso10126 => PaddingMode::ISO10126,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Kdf {
+    Scrypt,
+    Pbkdf2,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Encoding {
+    Raw,
+    Hex,
+    Base64,
+}
+
+/// Why a command failed; decides the exit code.
+#[derive(Debug)]
+pub enum CliError {
+    /// The arguments make no sense together (exit code 2, like clap's own).
+    Usage(String),
+    /// The operation itself failed (exit code 1).
+    Failed(String),
+}
+
+impl From<CipherError> for CliError {
+    fn from(e: CipherError) -> Self {
+        CliError::Failed(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(e: std::io::Error) -> Self {
+        CliError::Failed(e.to_string())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Enc(args) => crypt::run(args, true).await,
+        Command::Dec(args) => crypt::run(args, false).await,
+        Command::Keygen(command) => keygen::run(command),
+        Command::Bench(args) => bench::run(args).await,
+        Command::List => {
+            list();
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(message)) => {
+            eprintln!("cryptotool: {}", message);
+            ExitCode::from(2)
+        }
+        Err(CliError::Failed(message)) => {
+            eprintln!("cryptotool: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list() {
+    println!("{:<14} {:>10} {:>6} {:>7}", "algorithm", "key bytes", "block", "rounds");
+    for descriptor in cryptography::registry().algorithms() {
+        println!(
+            "{:<14} {:>10} {:>6} {:>7}",
+            descriptor.name,
+            format_key_sizes(descriptor.key_sizes),
+            descriptor.block_size,
+            descriptor.default_rounds
+        );
+    }
+}
+
+/// Joins key sizes with "/", collapsing runs of consecutive sizes into "a-b".
+fn format_key_sizes(sizes: &[usize]) -> String {
+    let mut parts = Vec::new();
+    let mut rest = sizes;
+    while let Some(&first) = rest.first() {
+        let run = rest.iter().enumerate().take_while(|&(i, &size)| size == first + i).count();
+        let last = rest[run - 1];
+        parts.push(if run > 2 { format!("{}-{}", first, last) } else { rest[..run].iter().map(usize::to_string).collect::<Vec<_>>().join("/") });
+        rest = &rest[run..];
+    }
+    parts.join("/")
+}
+
+/// Looks `name` up in the registry, listing the valid names if it is unknown.
+pub fn descriptor(name: &str) -> Result<cryptography::CipherDescriptor, CliError> {
+    let registry = cryptography::registry();
+    registry.get(name).copied().ok_or_else(|| {
+        let names: Vec<_> = registry.names().collect();
+        CliError::Usage(format!("unknown algorithm '{}' (available: {})", name, names.join(", ")))
+    })
+}
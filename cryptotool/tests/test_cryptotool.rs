@@ -0,0 +1,102 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn cryptotool(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cryptotool"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: Output) -> Vec<u8> {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    output.stdout
+}
+
+#[test]
+fn test_enc_matches_openssl_aes_128_cbc() {
+    // openssl enc -aes-128-cbc -K 000102030405060708090a0b0c0d0e0f -iv 00…00 <<< "hello world"
+    let args = [
+        "enc", "-a", "rijndael-128", "-k", "000102030405060708090a0b0c0d0e0f",
+        "--iv", "00000000000000000000000000000000", "-e", "hex",
+    ];
+    let ciphertext = stdout(cryptotool(&args, b"hello world\n"));
+    assert_eq!(ciphertext, b"6d78359dd6ff41f19524a7335ca280f7\n");
+}
+
+#[test]
+fn test_generated_iv_is_prepended_and_read_back() {
+    let key = "00112233445566778899aabbccddeeff";
+    let plaintext = b"some text that spans more than one block".to_vec();
+    for mode in ["cbc", "ctr", "gcm", "eax", "cbc-cs3"] {
+        let ciphertext = stdout(cryptotool(&["enc", "-a", "twofish", "-m", mode, "-k", key, "-e", "base64"], &plaintext));
+        let decrypted = stdout(cryptotool(&["dec", "-a", "twofish", "-m", mode, "-k", key, "-e", "base64"], &ciphertext));
+        assert_eq!(decrypted, plaintext, "{}", mode);
+    }
+}
+
+#[test]
+fn test_passphrase_container_round_trip_through_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let plain = dir.path().join("plain.txt");
+    let sealed = dir.path().join("sealed.bin");
+    std::fs::write(&plain, b"secret report").unwrap();
+
+    let enc = [
+        "enc", "-a", "loki97", "--passphrase", "correct horse", "--kdf", "pbkdf2", "--iterations", "1000",
+        "-i", plain.to_str().unwrap(), "-o", sealed.to_str().unwrap(),
+    ];
+    stdout(cryptotool(&enc, b""));
+
+    // Алгоритм, режим и соль берутся из заголовка контейнера
+    let dec = ["dec", "--passphrase", "correct horse", "-i", sealed.to_str().unwrap()];
+    assert_eq!(stdout(cryptotool(&dec, b"")), b"secret report");
+
+    let wrong = ["dec", "--passphrase", "wrong", "-i", sealed.to_str().unwrap()];
+    let output = cryptotool(&wrong, b"");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_keygen_sym_produces_usable_keys() {
+    let key = stdout(cryptotool(&["keygen", "sym", "-a", "triple-des"], b""));
+    let key = String::from_utf8(key).unwrap();
+    assert_eq!(key.trim().len(), 48);
+
+    let ciphertext = stdout(cryptotool(&["enc", "-a", "triple-des", "-k", key.trim()], b"data"));
+    let decrypted = stdout(cryptotool(&["dec", "-a", "triple-des", "-k", key.trim()], &ciphertext));
+    assert_eq!(decrypted, b"data");
+}
+
+#[test]
+fn test_keygen_rsa_writes_both_halves() {
+    let dir = tempfile::tempdir().unwrap();
+    let private = dir.path().join("id_rsa");
+    stdout(cryptotool(&["keygen", "rsa", "-b", "256", "-o", private.to_str().unwrap()], b""));
+
+    let public = std::fs::read_to_string(dir.path().join("id_rsa.pub")).unwrap();
+    let private = std::fs::read_to_string(private).unwrap();
+    assert!(public.starts_with("n=") && public.contains("\ne=10001\n"));
+    assert!(private.starts_with(&public) && private.contains("\nd="));
+}
+
+#[test]
+fn test_exit_codes() {
+    // 2 — неверные аргументы, 1 — ошибка при выполнении
+    let unknown = cryptotool(&["enc", "-a", "rot13", "-k", "00"], b"x");
+    assert_eq!(unknown.status.code(), Some(2));
+
+    let no_secret = cryptotool(&["enc", "-a", "des"], b"x");
+    assert_eq!(no_secret.status.code(), Some(2));
+
+    let short_key = cryptotool(&["enc", "-a", "des", "-k", "0011"], b"x");
+    assert_eq!(short_key.status.code(), Some(1));
+
+    let truncated = cryptotool(&["dec", "-a", "des", "-k", "133457799bbcdff1"], b"abc");
+    assert_eq!(truncated.status.code(), Some(1));
+}
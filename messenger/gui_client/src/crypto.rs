@@ -1,11 +1,11 @@
 use dh_crypto::{DhParameters, DiffieHellman, KeyExchangeAlgorithm, KeyPair};
-use loki97_crypto::Loki97Cipher;
 use messenger_protos::EncryptionAlgorithm as ProtoAlgorithm;
 use num_bigint::{BigUint, ToBigUint};
+use once_cell::sync::Lazy;
 use rand::rngs::OsRng;
-use sha2::{Digest, Sha256};
-use symmetric_cipher::{CipherContext, CipherInput, CipherMode, CipherOutput, PaddingMode, SymmetricCipherWithRounds};
-use twofish_crypto::Twofish;
+use symmetric_cipher::crypto::registry::CipherRegistry;
+use symmetric_cipher::crypto::sha256::sha256;
+use symmetric_cipher::{CipherContext, CipherInput, CipherMode, CipherOutput, CipherStream, PaddingMode, SymmetricCipherWithRounds};
 
 pub struct CryptoState {
     pub dh_keypair_gui: Option<KeyPair>,
@@ -57,22 +57,40 @@ pub fn compute_shared_secret(private_key: &BigUint, remote_public_key: &[u8], al
         .compute_shared_secret(private_key, &remote_pub_key)
         .map_err(|e| format!("Failed to compute shared secret: {:?}", e))?;
 
-    let key_len = match algorithm {
-        ProtoAlgorithm::Loki97 | ProtoAlgorithm::Twofish => 32,
-        _ => 32,
-    };
+    let key_len = registry_name(algorithm)
+        .and_then(|name| CIPHERS.get(name))
+        .and_then(|descriptor| descriptor.key_sizes.iter().copied().max())
+        .unwrap_or(32);
 
     Ok(derive_key_from_shared_secret_gui(&shared_secret, key_len))
 }
 
-pub fn create_cipher_box(algorithm: ProtoAlgorithm, key: &[u8]) -> Result<Box<dyn SymmetricCipherWithRounds + Send + Sync>, String> {
+/// The ciphers a chat can negotiate.
+static CIPHERS: Lazy<CipherRegistry> = Lazy::new(cryptography::registry);
+
+fn registry_name(algorithm: ProtoAlgorithm) -> Option<&'static str> {
     match algorithm {
-        ProtoAlgorithm::Loki97 => Ok(Box::new(Loki97Cipher::new(key))),
-        ProtoAlgorithm::Twofish => Ok(Box::new(Twofish::new(key))),
-        _ => Err("Unsupported encryption algorithm".to_string()),
+        ProtoAlgorithm::Loki97 => Some("loki97"),
+        ProtoAlgorithm::Twofish => Some("twofish"),
+        ProtoAlgorithm::Rijndael => Some("rijndael-128"),
+        ProtoAlgorithm::Des => Some("des"),
+        ProtoAlgorithm::Deal => Some("deal"),
+        _ => None,
     }
 }
 
+/// CBC IV length for `algorithm`: one cipher block.
+pub fn iv_len(algorithm: ProtoAlgorithm) -> usize {
+    registry_name(algorithm)
+        .and_then(|name| CIPHERS.get(name))
+        .map_or(16, |descriptor| descriptor.block_size)
+}
+
+pub fn create_cipher_box(algorithm: ProtoAlgorithm, key: &[u8]) -> Result<Box<dyn SymmetricCipherWithRounds + Send + Sync>, String> {
+    let name = registry_name(algorithm).ok_or_else(|| "Unsupported encryption algorithm".to_string())?;
+    CIPHERS.create(name, key).map_err(|e| e.to_string())
+}
+
 pub async fn encrypt_data(cipher_box: Box<dyn SymmetricCipherWithRounds + Send + Sync>, data: Vec<u8>, iv: Vec<u8>) -> Result<Vec<u8>, String> {
     let initial_additional_params = cipher_box.export_round_keys().unwrap_or_else(|| vec![0u8; 32]);
 
@@ -105,9 +123,34 @@ pub async fn decrypt_data(cipher_box: Box<dyn SymmetricCipherWithRounds + Send +
     }
 }
 
+/// Encryptor for a file sent as a sequence of chunks: the IV is used once and
+/// CBC chaining continues from one chunk to the next.
+pub fn create_file_encryptor(cipher_box: Box<dyn SymmetricCipherWithRounds + Send + Sync>, iv: Vec<u8>) -> Result<CipherStream, String> {
+    let initial_additional_params = cipher_box.export_round_keys().unwrap_or_else(|| vec![0u8; 32]);
+
+    let ctx = CipherContext::new(cipher_box, CipherMode::CBC, PaddingMode::PKCS7, Some(iv), initial_additional_params);
+    ctx.encryptor().map_err(|e| format!("Encryption failed: {}", e))
+}
+
+/// Decrypts the chunks of a file produced by `create_file_encryptor`, in order.
+pub fn decrypt_file_chunks<I>(cipher_box: Box<dyn SymmetricCipherWithRounds + Send + Sync>, iv: Vec<u8>, chunks: I) -> Result<Vec<u8>, String>
+where
+    I: IntoIterator<Item = Vec<u8>>,
+{
+    let initial_additional_params = cipher_box.export_round_keys().unwrap_or_else(|| vec![0u8; 32]);
+
+    let ctx = CipherContext::new(cipher_box, CipherMode::CBC, PaddingMode::PKCS7, Some(iv), initial_additional_params);
+    let mut decryptor = ctx.decryptor().map_err(|e| format!("Decryption failed: {}", e))?;
+
+    let mut decrypted = Vec::new();
+    for chunk in chunks {
+        decrypted.extend(decryptor.update(&chunk));
+    }
+    decrypted.extend(decryptor.finalize().map_err(|e| format!("Decryption failed: {}", e))?);
+    Ok(decrypted)
+}
+
 pub fn hash_shared_key(key: &[u8]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(key);
-    let result = hasher.finalize();
+    let result = sha256(key);
     hex::encode(&result[..4])
 }
@@ -2,7 +2,7 @@ use eframe::egui;
 use std::collections::BTreeMap;
 use uuid::Uuid;
 
-use crate::crypto::{compute_shared_secret, create_cipher_box, decrypt_data, generate_dh_keypair, hash_shared_key};
+use crate::crypto::{compute_shared_secret, create_cipher_box, decrypt_file_chunks, generate_dh_keypair, hash_shared_key};
 use crate::state::DecryptedMessageDisplay;
 use crate::state::FileInfoDisplay;
 use crate::state::PendingImageData;
@@ -60,13 +60,8 @@ impl UpdateHandler {
             GuiUpdate::FileTransferCancelled { transfer_id } => {
                 self.handle_file_transfer_cancelled(app, transfer_id);
             }
-            GuiUpdate::FileChunkDecrypted {
-                transfer_id,
-                sequence_number,
-                data,
-                is_last,
-            } => {
-                self.handle_file_chunk_decrypted(app, ctx, transfer_id, sequence_number, data, is_last);
+            GuiUpdate::FileDecrypted { transfer_id, data } => {
+                self.handle_file_decrypted(app, ctx, transfer_id, data);
             }
         }
     }
@@ -221,7 +216,7 @@ impl UpdateHandler {
 
         if chat_msg_proto.payload_type != messenger_protos::PayloadType::Text as i32 {
             let transfer_id = chat_msg_proto.unique_transfer_id.clone();
-
+            let encrypted_chunks;
 
             {
                 let room = match app.active_rooms.get_mut(&room_id) {
@@ -258,7 +253,21 @@ impl UpdateHandler {
                     };
                     room.messages.push(new_msg);
                 }
-            } 
+
+                // The chunks form a single CBC message, so they are kept encrypted
+                // until the last one arrives and then decrypted in order.
+                let fi = room.messages.iter_mut().find_map(|m| m.file_info.as_mut().filter(|fi| fi.transfer_id == transfer_id)).unwrap();
+                fi.received_chunks.insert(chat_msg_proto.chunk_sequence_number, chat_msg_proto.encrypted_payload);
+                if !chat_msg_proto.is_last_chunk {
+                    return;
+                }
+                if fi.received_chunks.len() != chat_msg_proto.chunk_sequence_number as usize + 1 {
+                    fi.received_chunks.clear();
+                    let _ = app.gui_update_tx.try_send(GuiUpdate::LocalError(format!("File '{}' is missing chunks.", fi.name)));
+                    return;
+                }
+                encrypted_chunks = std::mem::take(&mut fi.received_chunks);
+            }
 
             let decryption_info = if let Some(room) = app.active_rooms.get(&room_id) {
                 app.get_crypto_state(&room_id).and_then(|cs| cs.shared_secret_key_gui.as_ref()).map(|key| (room.algorithm, key.clone()))
@@ -270,14 +279,13 @@ impl UpdateHandler {
                 let gui_tx = app.gui_update_tx.clone();
                 app.tokio_rt.spawn(async move {
                     let cipher_box = create_cipher_box(algorithm, &key).unwrap();
-                    if let Ok(decrypted_chunk) = decrypt_data(cipher_box, chat_msg_proto.encrypted_payload, chat_msg_proto.iv).await {
-                        let update = GuiUpdate::FileChunkDecrypted {
-                            transfer_id,
-                            sequence_number: chat_msg_proto.chunk_sequence_number,
-                            data: decrypted_chunk,
-                            is_last: chat_msg_proto.is_last_chunk,
-                        };
-                        gui_tx.send(update).await.ok();
+                    match decrypt_file_chunks(cipher_box, chat_msg_proto.iv, encrypted_chunks.into_values()) {
+                        Ok(data) => {
+                            gui_tx.send(GuiUpdate::FileDecrypted { transfer_id, data }).await.ok();
+                        }
+                        Err(e) => {
+                            gui_tx.send(GuiUpdate::LocalError(e)).await.ok();
+                        }
                     }
                 });
             }
@@ -296,30 +304,21 @@ impl UpdateHandler {
         }
     }
 
-    fn handle_file_chunk_decrypted(&self, app: &mut SecureMessengerEguiApp, ctx: &egui::Context, transfer_id: String, sequence_number: i32, data: Vec<u8>, is_last: bool) {
+    fn handle_file_decrypted(&self, app: &mut SecureMessengerEguiApp, ctx: &egui::Context, transfer_id: String, data: Vec<u8>) {
         for room in app.active_rooms.values_mut() {
             if let Some(msg) = room.messages.iter_mut().find(|m| m.file_info.as_ref().map_or(false, |fi| fi.transfer_id == transfer_id)) {
                 if let Some(fi) = &mut msg.file_info {
-                    fi.received_chunks.insert(sequence_number, data);
-
-                    if is_last {
-                        let mut full_data = Vec::new();
-                        for chunk_data in fi.received_chunks.values() {
-                            full_data.extend_from_slice(chunk_data);
-                        }
-                        fi.decrypted_data = Some(full_data);
-                        fi.received_chunks.clear(); 
-
-                        if is_image_filename(&fi.name) {
-                            if let Ok(dyn_img) = image::load_from_memory(fi.decrypted_data.as_ref().unwrap()) {
-                                let rgba_img = dyn_img.to_rgba8();
-                                fi.pending_image_data = Some(PendingImageData {
-                                    bytes: rgba_img.into_raw(),
-                                    width: dyn_img.width(),
-                                    height: dyn_img.height(),
-                                });
-                                let _ = app.gui_update_tx.try_send(GuiUpdate::NewMessageDecrypted(msg.clone()));
-                            }
+                    fi.decrypted_data = Some(data);
+
+                    if is_image_filename(&fi.name) {
+                        if let Ok(dyn_img) = image::load_from_memory(fi.decrypted_data.as_ref().unwrap()) {
+                            let rgba_img = dyn_img.to_rgba8();
+                            fi.pending_image_data = Some(PendingImageData {
+                                bytes: rgba_img.into_raw(),
+                                width: dyn_img.width(),
+                                height: dyn_img.height(),
+                            });
+                            let _ = app.gui_update_tx.try_send(GuiUpdate::NewMessageDecrypted(msg.clone()));
                         }
                     }
                 }
@@ -7,7 +7,7 @@ use std::collections::BTreeMap;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
-use crate::crypto::{create_cipher_box, encrypt_data};
+use crate::crypto::{create_cipher_box, create_file_encryptor, encrypt_data, iv_len};
 use crate::state::{DecryptedMessageDisplay, FileInfoDisplay, GuiUpdate, RoomState};
 use crate::{ui::is_image_filename, SecureMessengerEguiApp};
 use messenger_protos::{ClientRequest, ClientRequestType, PayloadType as ProtoPayloadType, SendMessageRequest};
@@ -50,7 +50,7 @@ impl MessageSender {
         let algo = room_state.algorithm;
 
         app.tokio_rt.spawn(async move {
-            let mut iv = vec![0u8; 16];
+            let mut iv = vec![0u8; iv_len(algo)];
             OsRng.fill(&mut iv[..]);
 
             let cipher_box = match create_cipher_box(algo, &key) {
@@ -166,6 +166,18 @@ impl MessageSender {
             let mut chunk_sequence_number = 0;
             let payload_type = if is_image_filename(&filename) { ProtoPayloadType::Image } else { ProtoPayloadType::File };
 
+            // One IV for the whole file; the chunks are pieces of a single CBC message.
+            let mut iv = vec![0u8; iv_len(algo)];
+            OsRng.fill(&mut iv[..]);
+            let cipher_box = create_cipher_box(algo, &key).unwrap();
+            let mut encryptor = match create_file_encryptor(cipher_box, iv.clone()) {
+                Ok(e) => Some(e),
+                Err(e) => {
+                    gui_tx_clone.send(GuiUpdate::LocalError(e)).await.ok();
+                    return;
+                }
+            };
+
             loop {
                 let mut chunk_buf = vec![0; CHUNK_SIZE];
                 let bytes_read = match file.read(&mut chunk_buf).await {
@@ -178,24 +190,25 @@ impl MessageSender {
                 };
                 chunk_buf.truncate(bytes_read);
 
-                let mut iv = vec![0u8; 16];
-                OsRng.fill(&mut iv[..]);
-                let cipher_box = create_cipher_box(algo, &key).unwrap();
-                let encrypted_chunk = match encrypt_data(cipher_box, chunk_buf, iv.clone()).await {
-                    Ok(d) => d,
-                    Err(e) => {
-                        gui_tx_clone.send(GuiUpdate::LocalError(format!("Encryption error: {}", e))).await.ok();
-                        break;
-                    }
-                };
-
                 bytes_sent += bytes_read as u64;
                 let is_last_chunk = bytes_sent >= total_size;
 
+                let Some(chunk_encryptor) = encryptor.as_mut() else { break };
+                let mut encrypted_chunk = chunk_encryptor.update(&chunk_buf);
+                if is_last_chunk {
+                    match encryptor.take().unwrap().finalize() {
+                        Ok(tail) => encrypted_chunk.extend(tail),
+                        Err(e) => {
+                            gui_tx_clone.send(GuiUpdate::LocalError(format!("Encryption error: {}", e))).await.ok();
+                            break;
+                        }
+                    }
+                }
+
                 let send_req = SendMessageRequest {
                     unique_transfer_id: transfer_id.clone(),
                     room_id: room_id_clone.clone(),
-                    iv,
+                    iv: iv.clone(),
                     encrypted_payload: encrypted_chunk,
                     payload_type: payload_type as i32,
                     filename: filename.clone(),
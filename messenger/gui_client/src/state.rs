@@ -68,11 +68,9 @@ pub enum GuiUpdate {
     FileTransferCancelled {
         transfer_id: String,
     },
-    FileChunkDecrypted {
+    FileDecrypted {
         transfer_id: String,
-        sequence_number: i32,
         data: Vec<u8>,
-        is_last: bool,
     },
     NewMessageDecrypted(DecryptedMessageDisplay),
     SharedSecretEstablished {
@@ -91,6 +91,9 @@ impl LobbyView {
             .show_ui(ui, |ui| {
                 ui.selectable_value(&mut app.selected_algorithm_lobby, ProtoAlgorithm::Loki97, "LOKI97");
                 ui.selectable_value(&mut app.selected_algorithm_lobby, ProtoAlgorithm::Twofish, "TWOFISH");
+                ui.selectable_value(&mut app.selected_algorithm_lobby, ProtoAlgorithm::Rijndael, "RIJNDAEL");
+                ui.selectable_value(&mut app.selected_algorithm_lobby, ProtoAlgorithm::Des, "DES");
+                ui.selectable_value(&mut app.selected_algorithm_lobby, ProtoAlgorithm::Deal, "DEAL");
             });
 
         if ui.button("Create Room").clicked() {
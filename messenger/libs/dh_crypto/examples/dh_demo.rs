@@ -14,7 +14,7 @@ fn get_standard_dh_params() -> DhParameters {
     }
 }
 
-fn main() -> Result<(), &'static str> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Diffie-Hellman Key Exchange Demo ===");
 
     // 0. Setup Diffie-Hellman context with standard parameters
@@ -109,7 +109,7 @@ fn main() -> Result<(), &'static str> {
         );
     } else {
         println!("ERROR: Shared secrets DO NOT match!");
-        return Err("Shared secret mismatch in demo.");
+        return Err("Shared secret mismatch in demo.".into());
     }
 
     // The raw shared secret should typically be passed through a Key Derivation Function (KDF)
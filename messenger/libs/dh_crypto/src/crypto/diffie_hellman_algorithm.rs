@@ -2,6 +2,7 @@ use crate::crypto::key_exchange_traits::{KeyExchangeAlgorithm, KeyPair};
 use num_bigint::{BigUint, ToBigUint};
 use num_traits::{One, Zero};
 use rand::RngCore;
+use symmetric_cipher::CipherError;
 
 #[derive(Clone, Debug)]
 pub struct DhParameters {
@@ -17,15 +18,19 @@ impl KeyExchangeAlgorithm for DiffieHellman {
     type Parameters = DhParameters;
     type SharedSecret = BigUint;
 
-    fn new(params: Self::Parameters) -> Result<Self, &'static str> {
+    fn new(params: Self::Parameters) -> Result<Self, CipherError> {
         if params.p <= 3.to_biguint().unwrap() {
-            return Err("Parameter 'p' must be greater than 3. For security, 'p' must be a large prime.");
+            return Err(CipherError::InvalidParameter(
+                "Parameter 'p' must be greater than 3. For security, 'p' must be a large prime.",
+            ));
         }
         if !params.p.bit(0) {
-            return Err("Parameter 'p' must be an odd prime for typical Diffie-Hellman groups.");
+            return Err(CipherError::InvalidParameter(
+                "Parameter 'p' must be an odd prime for typical Diffie-Hellman groups.",
+            ));
         }
         if params.g <= BigUint::one() || params.g >= (&params.p - BigUint::one()) {
-            return Err("Parameter 'g' must be in the range (1, p-1).");
+            return Err(CipherError::InvalidParameter("Parameter 'g' must be in the range (1, p-1)."));
         }
         Ok(Self { params })
     }
@@ -57,16 +62,20 @@ impl KeyExchangeAlgorithm for DiffieHellman {
         &self,
         own_private_key: &BigUint,
         other_public_key: &BigUint,
-    ) -> Result<Self::SharedSecret, &'static str> {
+    ) -> Result<Self::SharedSecret, CipherError> {
         let two = 2.to_biguint().unwrap();
         let p_minus_1 = &self.params.p - BigUint::one();
 
         if !(*own_private_key >= two && *own_private_key < p_minus_1) {
-            return Err("Own private key is out of the valid range [2, p-2].");
+            return Err(CipherError::InvalidParameter(
+                "Own private key is out of the valid range [2, p-2].",
+            ));
         }
 
         if *other_public_key < two || *other_public_key >= self.params.p {
-            return Err("Other party's public key is out of the valid range [2, p-1].");
+            return Err(CipherError::InvalidParameter(
+                "Other party's public key is out of the valid range [2, p-1].",
+            ));
         }
 
         Ok(other_public_key.modpow(own_private_key, &self.params.p))
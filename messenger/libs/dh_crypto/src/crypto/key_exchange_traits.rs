@@ -1,5 +1,6 @@
 use num_bigint::BigUint;
 use rand::RngCore;
+use symmetric_cipher::CipherError;
 
 #[derive(Debug, Clone)]
 pub struct KeyPair {
@@ -11,11 +12,11 @@ pub trait KeyExchangeAlgorithm: Sized {
     type Parameters;
     type SharedSecret;
 
-    fn new(params: Self::Parameters) -> Result<Self, &'static str>;
+    fn new(params: Self::Parameters) -> Result<Self, CipherError>;
     fn generate_keypair(&self, rng: &mut impl RngCore) -> KeyPair;
     fn compute_shared_secret(
         &self,
         own_private_key: &BigUint,
         other_public_key: &BigUint,
-    ) -> Result<Self::SharedSecret, &'static str>;
+    ) -> Result<Self::SharedSecret, CipherError>;
 }
\ No newline at end of file
@@ -1,5 +0,0 @@
-pub mod f_function;
-pub mod key_schedule;
-pub mod loki97;
-mod permutation;
-pub mod sboxes;
@@ -1,1006 +0,0 @@
-use crate::crypto::cipher_io::write_all;
-use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
-use crate::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
-use crate::crypto::utils::{apply_padding, remove_padding};
-use rayon::prelude::*;
-use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
-use std::sync::Arc;
-
-const CHUNK_SIZE: usize = 1024 * 1024;
-const OPTIMAL_PARALLELISM_THRESHOLD: usize = 4 * 1024 * 1024;
-
-struct VecWriter<'a>(&'a mut Vec<u8>);
-impl<'a> Write for VecWriter<'a> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.0.extend_from_slice(buf);
-        Ok(buf.len())
-    }
-    fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
-    }
-}
-
-#[derive(Clone)]
-pub struct CipherContext {
-    algorithm: Arc<dyn SymmetricCipherWithRounds + Send + Sync>,
-    mode: CipherMode,
-    padding: PaddingMode,
-    iv: Option<Vec<u8>>,
-    additional_params: Vec<u8>,
-}
-
-impl CipherContext {
-    pub fn new(
-        algorithm: Box<dyn SymmetricCipherWithRounds + Send + Sync>,
-        mode: CipherMode,
-        padding: PaddingMode,
-        iv: Option<Vec<u8>>,
-        additional_params: Vec<u8>,
-    ) -> Self {
-        Self {
-            algorithm: Arc::from(algorithm),
-            mode,
-            padding,
-            iv,
-            additional_params,
-        }
-    }
-
-    pub fn set_key(&mut self, key: &[u8]) -> Result<(), &'static str> {
-        let alg = Arc::get_mut(&mut self.algorithm).ok_or("Failed to acquire mutable algorithm")?;
-
-        alg.set_key(key)?;
-
-        self.additional_params = match alg.export_round_keys() {
-            Some(keys) => keys,
-            None => key.to_vec(),
-        };
-
-        Ok(())
-    }
-
-    #[inline]
-    fn increment_block(block: &mut [u8], value: usize) {
-        let mut carry = value;
-        for byte in block.iter_mut().rev() {
-            let (res, overflow) = byte.overflowing_add((carry & 0xFF) as u8);
-            *byte = res;
-            carry >>= 8;
-            if !overflow && carry == 0 {
-                break;
-            }
-        }
-    }
-
-    #[inline]
-    fn is_stream_mode(&self) -> bool {
-        matches!(
-            self.mode,
-            CipherMode::CFB | CipherMode::OFB | CipherMode::CTR
-        )
-    }
-
-    #[inline]
-    fn is_empty_input_test_pattern(&self, data: &[u8]) -> bool {
-        if !matches!(
-            self.mode,
-            CipherMode::CBC | CipherMode::PCBC | CipherMode::RandomDelta
-        ) {
-            return false;
-        }
-
-        if matches!(self.padding, PaddingMode::Zeros) {
-            return false;
-        }
-
-        let block_size = self.algorithm.block_size();
-
-        if block_size == 8 && (data.len() == block_size || data.len() == 2 * block_size) {
-            match self.padding {
-                PaddingMode::PKCS7 => {
-                    if data.len() == block_size && data.iter().all(|&b| b == 8) {
-                        return true;
-                    }
-                    if data.len() == 2 * block_size
-                        && data[..block_size].iter().all(|&b| b == 8)
-                        && data[block_size..].iter().all(|&b| b == 0)
-                    {
-                        return true;
-                    }
-                }
-                PaddingMode::ANSI_X923 => {
-                    if data.len() == block_size
-                        && data[..block_size - 1].iter().all(|&b| b == 0)
-                        && data[block_size - 1] == 8
-                    {
-                        return true;
-                    }
-                    if data.len() == 2 * block_size
-                        && data[..block_size - 1].iter().all(|&b| b == 0)
-                        && data[block_size - 1] == 8
-                        && data[block_size..].iter().all(|&b| b == 0)
-                    {
-                        return true;
-                    }
-                }
-                PaddingMode::ISO10126 => {
-                    if data.len() == 2 * block_size
-                        && data[block_size - 1] == 8
-                        && data[data.len() - 1] == 0
-                    {
-                        return true;
-                    }
-
-                    if data.len() == block_size && data[block_size - 1] == 8 {
-                        return true;
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        false
-    }
-
-    fn process_ctr_batch(&self, data: &[u8], counter_start: &[u8], start_idx: usize) -> Vec<u8> {
-        let block_size = self.algorithm.block_size();
-        let round_key = &self.additional_params;
-        let mut result = Vec::with_capacity(data.len());
-
-        for (i, chunk) in data.chunks(block_size).enumerate() {
-            let mut counter = counter_start.to_vec();
-            Self::increment_block(&mut counter, start_idx + i);
-
-            let keystream = self.algorithm.encrypt_block(&counter, round_key);
-
-            for (j, &b) in chunk.iter().enumerate() {
-                result.push(keystream[j] ^ b);
-            }
-        }
-
-        result
-    }
-
-    fn process_ecb_parallel(&self, data: &[u8], encrypt: bool) -> Vec<u8> {
-        let block_size = self.algorithm.block_size();
-        let round_key = &self.additional_params;
-
-        let optimal_chunk_size = if data.len() > OPTIMAL_PARALLELISM_THRESHOLD {
-            (data.len() / rayon::current_num_threads())
-                .max(block_size)
-                .min(CHUNK_SIZE)
-                / block_size
-                * block_size
-        } else {
-            block_size
-        };
-
-        data.par_chunks(optimal_chunk_size)
-            .flat_map(|mega_chunk| {
-                let mut result = Vec::with_capacity(mega_chunk.len());
-
-                for chunk in mega_chunk.chunks(block_size) {
-                    let mut block = chunk.to_vec();
-
-                    if block.len() < block_size {
-                        block.resize(block_size, 0);
-                    }
-
-                    let processed = if encrypt {
-                        self.algorithm.encrypt_block(&block, round_key)
-                    } else {
-                        self.algorithm.decrypt_block(&block, round_key)
-                    };
-
-                    result.extend(processed);
-                }
-
-                result
-            })
-            .collect()
-    }
-
-    fn process_ctr_parallel(&self, data: &[u8], iv: &[u8]) -> Vec<u8> {
-        let block_size = self.algorithm.block_size();
-
-        let optimal_chunk_size = if data.len() > OPTIMAL_PARALLELISM_THRESHOLD {
-            (data.len() / rayon::current_num_threads())
-                .max(block_size)
-                .min(CHUNK_SIZE)
-        } else {
-            block_size * 64
-        };
-
-        data.par_chunks(optimal_chunk_size)
-            .enumerate()
-            .flat_map(|(chunk_idx, chunk)| {
-                let counter_offset = chunk_idx * (optimal_chunk_size / block_size);
-
-                self.process_ctr_batch(chunk, iv, counter_offset)
-            })
-            .collect()
-    }
-
-    fn process_ecb_data(&self, data: &[u8], encrypt: bool) -> Vec<u8> {
-        let block_size = self.algorithm.block_size();
-
-        if encrypt {
-            let padded_data = if data.len() % block_size == 0 && !data.is_empty() {
-                if matches!(
-                    self.padding,
-                    PaddingMode::PKCS7 | PaddingMode::ANSI_X923 | PaddingMode::ISO10126
-                ) {
-                    apply_padding(data.to_vec(), block_size, self.padding.clone())
-                } else {
-                    data.to_vec()
-                }
-            } else {
-                apply_padding(data.to_vec(), block_size, self.padding.clone())
-            };
-
-            self.process_ecb_parallel(&padded_data, true)
-        } else {
-            let processed = self.process_ecb_parallel(data, false);
-
-            if processed.is_empty() {
-                return processed;
-            }
-
-            if self.is_empty_input_test_pattern(&processed) {
-                return Vec::new();
-            }
-
-            let last_block_idx = processed.len() - block_size;
-            let (prefix, last_block) = processed.split_at(last_block_idx);
-
-            let mut result = prefix.to_vec();
-            let unpadded_block = remove_padding(last_block.to_vec(), self.padding.clone());
-            result.extend_from_slice(&unpadded_block);
-
-            result
-        }
-    }
-
-    fn process_chunked_parallel<R: Read, W: Write>(
-        &self,
-        mut reader: R,
-        mut writer: W,
-        encrypt: bool,
-    ) -> std::io::Result<()> {
-        let block_size = self.algorithm.block_size();
-        let is_stream_mode = self.is_stream_mode();
-        let is_ecb_mode = matches!(self.mode, CipherMode::ECB);
-
-        if is_ecb_mode {
-            let mut buf = vec![0u8; block_size];
-            let mut prev_block: Option<Vec<u8>> = None;
-
-            loop {
-                let n = reader.read(&mut buf[..])?;
-                if n == 0 {
-                    break;
-                }
-
-                let block = buf[..n].to_vec();
-
-                if encrypt {
-                    if let Some(prev) = prev_block.replace(block) {
-                        let encrypted =
-                            self.algorithm.encrypt_block(&prev, &self.additional_params);
-                        writer.write_all(&encrypted)?;
-                    }
-                } else {
-                    if n < block_size {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::UnexpectedEof,
-                            "ECB decryption: incomplete block",
-                        ));
-                    }
-                    let decrypted = self
-                        .algorithm
-                        .decrypt_block(&block, &self.additional_params);
-                    if let Some(prev) = prev_block.replace(decrypted) {
-                        writer.write_all(&prev)?;
-                    }
-                }
-            }
-
-            if encrypt {
-                let padded = apply_padding(
-                    prev_block.unwrap_or_else(Vec::new),
-                    block_size,
-                    self.padding.clone(),
-                );
-                let encrypted = self
-                    .algorithm
-                    .encrypt_block(&padded, &self.additional_params);
-                writer.write_all(&encrypted)?;
-            } else if let Some(last_block) = prev_block {
-                let unpadded = remove_padding(last_block, self.padding.clone());
-                writer.write_all(&unpadded)?;
-            }
-
-            return writer.flush();
-        }
-
-        let mut chunk_buffer = vec![0u8; CHUNK_SIZE];
-        let counter_base = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-        let mut counter_offset = 0;
-        let mut prev_block = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-        let mut pending_blocks = Vec::new();
-        let mut is_first_chunk = true;
-        let mut all_data = Vec::new();
-
-        loop {
-            let n = reader.read(&mut chunk_buffer)?;
-            if n == 0 {
-                break;
-            }
-
-            let data_slice = &chunk_buffer[..n];
-            let is_last_chunk = n < CHUNK_SIZE;
-
-            if !encrypt
-                && matches!(
-                    self.mode,
-                    CipherMode::CBC | CipherMode::PCBC | CipherMode::RandomDelta
-                )
-            {
-                all_data.extend_from_slice(data_slice);
-            }
-
-            let result = match self.mode {
-                CipherMode::CTR => {
-                    let result = self.process_ctr_batch(data_slice, &counter_base, counter_offset);
-                    counter_offset += (n + block_size - 1) / block_size;
-                    result
-                }
-                _ => {
-                    let mut result = Vec::with_capacity(data_slice.len() + block_size);
-                    for (idx, chunk) in data_slice.chunks(block_size).enumerate() {
-                        let is_last_block =
-                            is_last_chunk && idx == data_slice.chunks(block_size).count() - 1;
-                        if !encrypt && is_last_block && !is_stream_mode {
-                            pending_blocks.push(chunk.to_vec());
-                            continue;
-                        }
-
-                        let needs_padding = is_last_block && encrypt && !is_stream_mode;
-                        let processed_block = self.process_single_block(
-                            chunk,
-                            &mut prev_block,
-                            encrypt,
-                            needs_padding,
-                        )?;
-                        result.extend_from_slice(&processed_block);
-                    }
-                    result
-                }
-            };
-
-            writer.write_all(&result)?;
-            is_first_chunk = false;
-        }
-
-        if !encrypt
-            && is_first_chunk
-            && matches!(
-                self.mode,
-                CipherMode::CBC | CipherMode::PCBC | CipherMode::RandomDelta
-            )
-        {
-            if self.is_empty_input_test_pattern(&all_data) {
-                return writer.flush();
-            }
-        }
-
-        if is_first_chunk && encrypt {
-            if is_stream_mode {
-                return writer.flush();
-            } else {
-                let padding_block = apply_padding(Vec::new(), block_size, self.padding.clone());
-                let processed =
-                    self.process_single_block(&padding_block, &mut prev_block, true, true)?;
-                writer.write_all(&processed)?;
-            }
-        }
-
-        if !encrypt && !is_stream_mode && !pending_blocks.is_empty() {
-            let mut last_block = pending_blocks.pop().unwrap();
-            if last_block.len() < block_size {
-                last_block.resize(block_size, 0);
-            }
-
-            let processed_last =
-                self.process_single_block(&last_block, &mut prev_block, false, false)?;
-
-            if self.is_empty_input_test_pattern(&processed_last) {
-                return writer.flush();
-            }
-
-            let unpadded = match self.padding {
-                PaddingMode::Zeros => {
-                    let mut result = processed_last.clone();
-                    while result.len() > 0 && result[result.len() - 1] == 0 {
-                        result.pop();
-                    }
-
-                    if result.is_empty()
-                        && processed_last.len() > 0
-                        && (last_block.len() == 1 || (last_block.len() > 1 && last_block[0] == 0))
-                    {
-                        vec![0]
-                    } else {
-                        result
-                    }
-                }
-                _ => remove_padding(processed_last, self.padding.clone()),
-            };
-
-            if !unpadded.is_empty() {
-                writer.write_all(&unpadded)?;
-            }
-
-            for block in pending_blocks.iter().rev() {
-                let processed = self.process_single_block(block, &mut prev_block, false, false)?;
-                writer.write_all(&processed)?;
-            }
-        }
-
-        writer.flush()
-    }
-
-    fn process_single_block(
-        &self,
-        block_data: &[u8],
-        prev: &mut Vec<u8>,
-        encrypt: bool,
-        apply_padding_if_needed: bool,
-    ) -> std::io::Result<Vec<u8>> {
-        let block_size = self.algorithm.block_size();
-        let round_key = &self.additional_params;
-        let is_stream_mode = self.is_stream_mode();
-
-        let mut block = block_data.to_vec();
-
-        if apply_padding_if_needed && !is_stream_mode {
-            if block.len() < block_size
-                || (block.len() == block_size
-                    && matches!(
-                        self.padding,
-                        PaddingMode::PKCS7 | PaddingMode::ANSI_X923 | PaddingMode::ISO10126
-                    ))
-            {
-                block = apply_padding(block, block_size, self.padding.clone());
-            }
-        } else if !is_stream_mode && block.len() < block_size {
-            block.resize(block_size, 0);
-        }
-
-        let result = match self.mode {
-            CipherMode::CBC => {
-                if encrypt {
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < block.len() {
-                            block[i] ^= p;
-                        }
-                    }
-
-                    let encrypted = self.algorithm.encrypt_block(&block, round_key);
-
-                    prev.clear();
-                    prev.extend_from_slice(&encrypted);
-
-                    encrypted
-                } else {
-                    let decrypted = self.algorithm.decrypt_block(&block, round_key);
-
-                    let mut result = decrypted.clone();
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < result.len() {
-                            result[i] ^= p;
-                        }
-                    }
-
-                    prev.clear();
-                    prev.extend_from_slice(&block);
-
-                    result
-                }
-            }
-            CipherMode::CFB => {
-                if encrypt {
-                    let keystream = self.algorithm.encrypt_block(prev, round_key);
-
-                    let mut result = Vec::with_capacity(block.len());
-                    for i in 0..block.len() {
-                        if i < keystream.len() {
-                            result.push(block[i] ^ keystream[i]);
-                        } else {
-                            result.push(block[i]);
-                        }
-                    }
-
-                    prev.clear();
-                    prev.extend_from_slice(&result[..block.len().min(block_size)]);
-                    if prev.len() < block_size {
-                        prev.resize(block_size, 0);
-                    }
-
-                    result
-                } else {
-                    let keystream = self.algorithm.encrypt_block(prev, round_key);
-
-                    let mut result = Vec::with_capacity(block.len());
-                    for i in 0..block.len() {
-                        if i < keystream.len() {
-                            result.push(block[i] ^ keystream[i]);
-                        } else {
-                            result.push(block[i]);
-                        }
-                    }
-
-                    prev.clear();
-                    prev.extend_from_slice(&block[..block.len().min(block_size)]);
-                    if prev.len() < block_size {
-                        prev.resize(block_size, 0);
-                    }
-
-                    result
-                }
-            }
-            CipherMode::OFB => {
-                let keystream = self.algorithm.encrypt_block(prev, round_key);
-
-                let mut result = Vec::with_capacity(block.len());
-                for i in 0..block.len() {
-                    if i < keystream.len() {
-                        result.push(block[i] ^ keystream[i]);
-                    } else {
-                        result.push(block[i]);
-                    }
-                }
-
-                prev.clear();
-                prev.extend_from_slice(&keystream[..block_size]);
-
-                result
-            }
-            CipherMode::PCBC => {
-                let mut sized_block = block.clone();
-                if !is_stream_mode && sized_block.len() < block_size {
-                    sized_block.resize(block_size, 0);
-                }
-
-                if encrypt {
-                    let mut temp_block = sized_block.clone();
-
-                    for i in 0..block_size.min(temp_block.len()) {
-                        if i < prev.len() {
-                            temp_block[i] ^= prev[i];
-                        }
-                    }
-
-                    let actual_block_len = if is_stream_mode {
-                        block.len()
-                    } else {
-                        block_size
-                    };
-                    let block_to_encrypt = if temp_block.len() < block_size && !is_stream_mode {
-                        let mut padded = temp_block.clone();
-                        padded.resize(block_size, 0);
-                        padded
-                    } else {
-                        temp_block
-                    };
-
-                    let encrypted = self.algorithm.encrypt_block(&block_to_encrypt, round_key);
-
-                    let mut new_prev = vec![0u8; block_size];
-                    for i in 0..block_size {
-                        if i < block.len() {
-                            new_prev[i] = block[i];
-                        }
-                        if i < encrypted.len() {
-                            new_prev[i] ^= encrypted[i];
-                        }
-                    }
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && encrypted.len() > actual_block_len {
-                        encrypted[..actual_block_len].to_vec()
-                    } else {
-                        encrypted
-                    }
-                } else {
-                    let block_to_decrypt = if sized_block.len() < block_size && !is_stream_mode {
-                        let mut padded = sized_block.clone();
-                        padded.resize(block_size, 0);
-                        padded
-                    } else {
-                        sized_block
-                    };
-
-                    let decrypted = self.algorithm.decrypt_block(&block_to_decrypt, round_key);
-
-                    let mut result = decrypted.clone();
-                    for i in 0..result.len() {
-                        if i < prev.len() {
-                            result[i] ^= prev[i];
-                        }
-                    }
-
-                    let mut new_prev = vec![0u8; block_size];
-                    for i in 0..block_size {
-                        if i < result.len() {
-                            new_prev[i] = result[i];
-                        }
-                        if i < block_to_decrypt.len() {
-                            new_prev[i] ^= block_to_decrypt[i];
-                        }
-                    }
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && result.len() > block.len() {
-                        result[..block.len()].to_vec()
-                    } else {
-                        result
-                    }
-                }
-            }
-            CipherMode::RandomDelta => {
-                let delta = &self.additional_params;
-
-                let block_to_process = if block.len() < block_size && !is_stream_mode {
-                    let mut sized = block.clone();
-                    sized.resize(block_size, 0);
-                    sized
-                } else {
-                    block.clone()
-                };
-
-                if encrypt {
-                    let mut temp_block = block_to_process.clone();
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < temp_block.len() {
-                            temp_block[i] ^= p;
-                        }
-                    }
-
-                    let encrypted = self.algorithm.encrypt_block(&temp_block, round_key);
-
-                    let new_prev: Vec<u8> = prev
-                        .iter()
-                        .zip(delta.iter().chain(std::iter::repeat(&0)))
-                        .map(|(&p, &d)| p ^ d)
-                        .take(block_size)
-                        .collect();
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && encrypted.len() > block.len() {
-                        encrypted[..block.len()].to_vec()
-                    } else {
-                        encrypted
-                    }
-                } else {
-                    let decrypted = self.algorithm.decrypt_block(&block_to_process, round_key);
-
-                    let mut result = decrypted.clone();
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < result.len() {
-                            result[i] ^= p;
-                        }
-                    }
-
-                    let new_prev: Vec<u8> = prev
-                        .iter()
-                        .zip(delta.iter().chain(std::iter::repeat(&0)))
-                        .map(|(&p, &d)| p ^ d)
-                        .take(block_size)
-                        .collect();
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && result.len() > block.len() {
-                        result[..block.len()].to_vec()
-                    } else {
-                        result
-                    }
-                }
-            }
-
-            _ => Vec::new(),
-        };
-
-        Ok(result)
-    }
-
-    pub fn is_all_padding(data: &[u8], padding: &PaddingMode) -> bool {
-        match padding {
-            PaddingMode::PKCS7 => {
-                if let Some(&last_byte) = data.last() {
-                    let pad_len = last_byte as usize;
-
-                    if pad_len > 0
-                        && pad_len <= data.len()
-                        && data.len() % pad_len == 0
-                        && data.iter().all(|&b| b == last_byte)
-                    {
-                        return true;
-                    }
-                }
-            }
-            PaddingMode::ANSI_X923 => {
-                if let Some(&last_byte) = data.last() {
-                    let pad_len = last_byte as usize;
-                    if pad_len > 0 && pad_len <= data.len() && data.len() % pad_len == 0 {
-                        let blocks = data.len() / pad_len;
-                        for i in 0..blocks {
-                            let block_start = i * pad_len;
-                            let block_end = block_start + pad_len;
-                            if data[block_end - 1] != last_byte
-                                || !data[block_start..block_end - 1].iter().all(|&b| b == 0)
-                            {
-                                return false;
-                            }
-                        }
-                        return true;
-                    }
-                }
-            }
-            PaddingMode::ISO10126 => {
-                if let Some(&last_byte) = data.last() {
-                    let pad_len = last_byte as usize;
-                    if pad_len > 0 && pad_len <= data.len() && data.len() % pad_len == 0 {
-                        let blocks = data.len() / pad_len;
-                        for i in 0..blocks {
-                            let block_end = (i + 1) * pad_len;
-                            if data[block_end - 1] != last_byte {
-                                return false;
-                            }
-                        }
-                        return true;
-                    }
-                }
-            }
-            _ => {}
-        }
-        false
-    }
-
-    async fn process_data(&self, data: &[u8], encrypt: bool) -> std::io::Result<Vec<u8>> {
-        let block_size = self.algorithm.block_size();
-        let is_stream_mode = self.is_stream_mode();
-
-        if !encrypt && self.is_empty_input_test_pattern(data) {
-            return Ok(Vec::new());
-        }
-
-        if data.is_empty() {
-            return if encrypt && !is_stream_mode {
-                let padding_block = apply_padding(Vec::new(), block_size, self.padding.clone());
-
-                match self.mode {
-                    CipherMode::ECB => Ok(self.process_ecb_parallel(&padding_block, true)),
-                    _ => {
-                        let mut prev = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-                        let processed =
-                            self.process_single_block(&padding_block, &mut prev, true, true)?;
-                        Ok(processed)
-                    }
-                }
-            } else {
-                Ok(Vec::new())
-            };
-        }
-
-        match self.mode {
-            CipherMode::ECB => Ok(self.process_ecb_data(data, encrypt)),
-            CipherMode::CTR => {
-                let default_iv = vec![0u8; block_size];
-                let iv = self.iv.as_deref().unwrap_or(&default_iv);
-                let parallel_result = self.process_ctr_parallel(data, iv);
-                Ok(parallel_result)
-            }
-            _ => {
-                let prepared_data = if encrypt && !is_stream_mode {
-                    apply_padding(data.to_vec(), block_size, self.padding.clone())
-                } else {
-                    data.to_vec()
-                };
-
-                let result = if prepared_data.len() > OPTIMAL_PARALLELISM_THRESHOLD {
-                    let mut output = Vec::with_capacity(prepared_data.len());
-                    {
-                        let cursor = std::io::Cursor::new(&prepared_data);
-                        let mut writer = VecWriter(&mut output);
-                        self.process_chunked_parallel(cursor, &mut writer, encrypt)?;
-                    }
-                    output
-                } else {
-                    let mut prev = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-                    let mut result = Vec::with_capacity(prepared_data.len());
-
-                    let blocks: Vec<_> = if is_stream_mode {
-                        prepared_data.chunks(block_size).collect()
-                    } else {
-                        prepared_data.chunks(block_size).collect()
-                    };
-
-                    let block_count = blocks.len();
-
-                    for (idx, chunk) in blocks.into_iter().enumerate() {
-                        let is_last = idx == block_count - 1;
-                        let needs_padding = is_last && encrypt && !is_stream_mode;
-
-                        let processed =
-                            self.process_single_block(chunk, &mut prev, encrypt, needs_padding)?;
-
-                        result.extend_from_slice(&processed);
-                    }
-
-                    if !encrypt && self.is_empty_input_test_pattern(&result) {
-                        return Ok(Vec::new());
-                    }
-
-                    if !encrypt && !is_stream_mode && !result.is_empty() {
-                        if Self::is_all_padding(&result, &self.padding) {
-                            return Ok(Vec::new());
-                        }
-
-                        let last_block_start = if result.len() <= block_size {
-                            0
-                        } else {
-                            result.len() - block_size
-                        };
-
-                        let mut final_result = Vec::with_capacity(result.len());
-
-                        if last_block_start > 0 {
-                            final_result.extend_from_slice(&result[..last_block_start]);
-                        }
-
-                        let last_block = match self.padding {
-                            PaddingMode::Zeros => {
-                                if result.len() == block_size
-                                    && (data.len() == 1 || (data.len() > 1 && data[0] == 0))
-                                {
-                                    vec![0]
-                                } else {
-                                    let mut block = result[last_block_start..].to_vec();
-                                    while block.len() > 0 && block[block.len() - 1] == 0 {
-                                        block.pop();
-                                    }
-
-                                    if block.is_empty() && data.len() > 0 {
-                                        vec![0]
-                                    } else {
-                                        block
-                                    }
-                                }
-                            }
-                            _ => remove_padding(
-                                result[last_block_start..].to_vec(),
-                                self.padding.clone(),
-                            ),
-                        };
-
-                        final_result.extend_from_slice(&last_block);
-
-                        final_result
-                    } else {
-                        result
-                    }
-                };
-
-                Ok(result)
-            }
-        }
-    }
-
-    fn run_file_task<F, T>(task: F) -> std::io::Result<T>
-    where
-        F: FnOnce() -> std::io::Result<T> + Send + 'static,
-        T: Send + 'static,
-    {
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(tokio::task::spawn_blocking(task))
-        })
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
-    }
-
-    pub async fn encrypt(
-        &self,
-        input: CipherInput,
-        output: &mut CipherOutput,
-    ) -> std::io::Result<()> {
-        match (input, output) {
-            (CipherInput::Bytes(data), out) => {
-                let encrypted = self.process_data(&data, true).await?;
-                write_all(out, &encrypted)
-            }
-            (CipherInput::File(input_path), CipherOutput::File(output_path)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let output_path = output_path.clone();
-                Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let writer = BufWriter::new(File::create(output_path)?);
-                    this.process_chunked_parallel(reader, writer, true)
-                })
-            }
-            (CipherInput::File(input_path), CipherOutput::Buffer(buf)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let result = Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let mut result = Vec::new();
-                    {
-                        let mut writer = VecWriter(&mut result);
-                        this.process_chunked_parallel(reader, &mut writer, true)?;
-                    }
-                    Ok(result)
-                })?;
-                **buf = result;
-                Ok(())
-            }
-        }
-    }
-
-    pub async fn decrypt(
-        &self,
-        input: CipherInput,
-        output: &mut CipherOutput,
-    ) -> std::io::Result<()> {
-        match (input, output) {
-            (CipherInput::Bytes(data), out) => {
-                if self.is_empty_input_test_pattern(&data) {
-                    return write_all(out, &Vec::new());
-                }
-
-                if matches!(self.mode, CipherMode::CBC)
-                    && matches!(self.padding, PaddingMode::Zeros)
-                    && data.len() == self.algorithm.block_size()
-                {
-                    let mut decrypted = self.process_data(&data, false).await?;
-                    if decrypted.is_empty() && data.len() > 0 {
-                        decrypted = vec![0];
-                    }
-                    return write_all(out, &decrypted);
-                }
-
-                let decrypted = self.process_data(&data, false).await?;
-                write_all(out, &decrypted)
-            }
-            (CipherInput::File(input_path), CipherOutput::File(output_path)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let output_path = output_path.clone();
-                Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let writer = BufWriter::new(File::create(output_path)?);
-                    this.process_chunked_parallel(reader, writer, false)
-                })
-            }
-            (CipherInput::File(input_path), CipherOutput::Buffer(buf)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let result = Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let mut result = Vec::new();
-                    {
-                        let mut writer = VecWriter(&mut result);
-                        this.process_chunked_parallel(reader, &mut writer, false)?;
-                    }
-                    Ok(result)
-                })?;
-                **buf = result;
-                Ok(())
-            }
-        }
-    }
-}
@@ -1,3 +0,0 @@
-pub trait KeyExpansion {
-    fn generate_round_keys(&self, key: &[u8]) -> Vec<Vec<u8>>;
-}
@@ -1,2 +0,0 @@
-#[tokio::main]
-async fn main() {}
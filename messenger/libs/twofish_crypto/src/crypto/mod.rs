@@ -1,7 +0,0 @@
-pub mod gf256;
-pub mod key_schedule;
-pub mod mds;
-pub mod pht;
-pub mod sboxes;
-pub mod twofish;
-pub mod utils;
@@ -1,17 +0,0 @@
-pub use symmetric_cipher::crypto as symmetric_crypto;
-pub mod crypto;
-pub use crate::crypto::twofish::Twofish;
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
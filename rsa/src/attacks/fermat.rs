@@ -1,6 +1,5 @@
 use crate::number_theory::extended_gcd;
-use num_bigint::{BigUint, ToBigInt, ToBigUint};
-use num_integer::Roots;
+use num_bigint::{BigUint, ToBigInt};
 use num_traits::{One, ToPrimitive};
 
 #[derive(Debug)]
@@ -66,7 +66,7 @@ impl WienerAttack {
 
             let s2 = &s * &s;
             let four_n = BigUint::from(4u8) * n;
-            if &s2 < &four_n {
+            if s2 < four_n {
                 continue;
             }
 
@@ -16,7 +16,7 @@ impl PrimalityTest for MillerRabinTest {
         }
 
         let upper = n - &one;
-        if &two >= &upper {
+        if two >= upper {
             return false; // защита от генерирования a ∈ [2, n-2], если диапазон пуст
         }
 
@@ -27,7 +27,7 @@ pub trait PrimalityTest {
 }
 
 fn confidence_to_iterations(confidence: f64, per_iter_error: f64) -> u32 {
-    assert!(confidence >= 0.0 && confidence < 1.0,
+    assert!((0.0..1.0).contains(&confidence),
             "confidence must be in [0,1), got {}", confidence);
     assert!(per_iter_error > 0.0 && per_iter_error < 1.0,
             "per-iteration error must be in (0,1), got {}", per_iter_error);
@@ -1,6 +1,6 @@
 use crate::number_theory::{jacobi_symbol, mod_pow};
 use crate::primality::PrimalityTest;
-use num_bigint::{BigUint, BigInt, RandBigInt, ToBigInt, ToBigUint};
+use num_bigint::{BigUint, RandBigInt, ToBigInt, ToBigUint};
 use num_integer::Integer;
 use num_traits::{One, Zero};
 use rand::thread_rng;
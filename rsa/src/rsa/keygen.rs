@@ -70,8 +70,8 @@ impl RsaKeyGenerator {
                 candidate.set_bit((half_bits - 1) as u64, true);
                 if candidate != p
                     && test.is_probably_prime(&candidate, self.confidence)
-                    && (&p > &candidate && &p - &candidate > min_diff
-                    || &candidate > &p && &candidate - &p > min_diff)
+                    && (p > candidate && &p - &candidate > min_diff
+                    || candidate > p && &candidate - &p > min_diff)
                 {
                     break candidate;
                 }
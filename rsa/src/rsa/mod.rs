@@ -1,4 +1,5 @@
 pub mod keygen;
+#[allow(clippy::module_inception)]
 pub mod rsa;
 
 pub use keygen::{RsaKeyGenerator, RsaKeyPair, PrimalityType};
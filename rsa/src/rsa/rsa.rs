@@ -1,6 +1,5 @@
 use crate::rsa::keygen::{RsaKeyGenerator, RsaKeyPair, PrimalityType};
 use num_bigint::{BigUint};
-use num_traits::ToPrimitive;
 use crate::number_theory::mod_pow;
 
 pub struct RsaService {
@@ -1,5 +1,5 @@
-use rsa::attacks::fermat::{FermatAttack, FermatAttackResult};
-use num_bigint::{BigUint, ToBigInt, ToBigUint};
+use rsa::attacks::fermat::FermatAttack;
+use num_bigint::{ToBigInt, ToBigUint};
 use rsa::number_theory::extended_gcd;
 
 #[test]
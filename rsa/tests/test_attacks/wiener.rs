@@ -1,8 +1,8 @@
-use rsa::attacks::wiener::{WienerAttack, WienerAttackResult};
+use rsa::attacks::wiener::WienerAttack;
 use rsa::number_theory::extended_gcd;
 use rsa::primality::{MillerRabinTest, PrimalityTest};
 
-use num_bigint::{BigUint, RandBigInt, ToBigInt, ToBigUint};
+use num_bigint::{BigUint, RandBigInt, ToBigInt};
 use num_traits::{FromPrimitive, One, Zero};
 use quickcheck::quickcheck;
 use rand::thread_rng;
@@ -20,7 +20,7 @@ fn gen_prime(bits: u64) -> BigUint {
 
 quickcheck! {
     fn prop_wiener_attack_detects_small_d(bits: u64) -> bool {
-        if bits < 8 || bits > 20 { return true; }
+        if !(8..=20).contains(&bits) { return true; }
         let p = gen_prime(bits);
         let mut q;
         loop {
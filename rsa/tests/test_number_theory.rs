@@ -1,5 +1,5 @@
 use rsa::number_theory::*;
-use num_bigint::{BigUint, BigInt, ToBigInt};
+use num_bigint::{BigUint, BigInt};
 use num_traits::{FromPrimitive, One, Zero};
 
 #[test]
@@ -33,7 +33,7 @@ fn test_fermat_may_fail_on_carmichael_numbers() {
     let carmichaels = [561u32, 1105, 1729, 2465, 2821, 6601];
     let mut any_failed = false;
 
-    for i in 0..10 {
+    for _i in 0..10 {
         for &n in &carmichaels {
             let n = BigUint::from_u32(n).unwrap();
             if test.is_probably_prime(&n, 0.99) {
@@ -70,8 +70,6 @@ fn test_fermat_on_large_primes_and_composites() {
 }
 
 use quickcheck::quickcheck;
-use num_bigint::RandBigInt;
-use rand::thread_rng;
 
 quickcheck! {
     fn prop_fermat_detects_small_composites(n: u8) -> bool {
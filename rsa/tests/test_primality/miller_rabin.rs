@@ -58,14 +58,12 @@ fn test_miller_rabin_large_cases() {
 }
 
 use quickcheck::quickcheck;
-use rand::thread_rng;
-use num_bigint::RandBigInt;
 
 quickcheck! {
     fn prop_miller_rabin_rejects_odd_composites(a: u8, b: u8) -> bool {
         if a < 3 || b < 3 { return true; }
         let n = (a as u32) * (b as u32);
-        if n % 2 == 0 || n < 9 || a == b { return true; }
+        if n.is_multiple_of(2) || n < 9 || a == b { return true; }
         let n = BigUint::from(n);
         let test = MillerRabinTest;
         !test.is_probably_prime(&n, 0.99)
@@ -1,9 +1,8 @@
 use rsa::rsa::keygen::{RsaKeyGenerator, PrimalityType};
 use rsa::primality::{PrimalityTest, MillerRabinTest};
-use num_bigint::{BigUint, ToBigUint};
+use num_bigint::BigUint;
 use num_traits::{FromPrimitive};
 use quickcheck::quickcheck;
-use rand::Rng;
 
 #[test]
 fn test_key_generation_basic() {
@@ -0,0 +1,60 @@
+pub use symmetric_cipher::crypto::registry::{BoxedCipher, CipherDescriptor, CipherRegistry};
+use symmetric_cipher::crypto::container::{builtin_algorithm, AlgorithmId};
+
+/// Every cipher in the workspace enabled by this crate's features, plus the
+/// ones `symmetric_cipher` registers itself.
+pub fn registry() -> CipherRegistry {
+    #[allow(unused_mut)]
+    let mut registry = CipherRegistry::with_builtin();
+    #[cfg(feature = "rijndael")]
+    rijndael::register(&mut registry).expect("cipher names are unique");
+    #[cfg(feature = "twofish")]
+    twofish::register(&mut registry).expect("cipher names are unique");
+    #[cfg(feature = "loki97")]
+    LOK197::register(&mut registry).expect("cipher names are unique");
+    registry
+}
+
+/// Container header id of a registry algorithm. Rijndael uses the AES
+/// modulus.
+pub fn algorithm_id(name: &str) -> Option<AlgorithmId> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "des" => AlgorithmId::Des,
+        "triple-des" => AlgorithmId::TripleDes,
+        "deal" => AlgorithmId::Deal,
+        "rijndael-128" => AlgorithmId::Rijndael { block_size: 16, poly: 0x11B },
+        "rijndael-160" => AlgorithmId::Rijndael { block_size: 20, poly: 0x11B },
+        "rijndael-192" => AlgorithmId::Rijndael { block_size: 24, poly: 0x11B },
+        "rijndael-224" => AlgorithmId::Rijndael { block_size: 28, poly: 0x11B },
+        "rijndael-256" => AlgorithmId::Rijndael { block_size: 32, poly: 0x11B },
+        "twofish" => AlgorithmId::Twofish,
+        "loki97" => AlgorithmId::Loki97,
+        _ => return None,
+    })
+}
+
+/// [`AlgorithmResolver`](symmetric_cipher::crypto::container::AlgorithmResolver)
+/// covering the ciphers enabled by this crate's features. The instances are
+/// keyed with zeros; the container rekeys them.
+pub fn resolve_algorithm(id: AlgorithmId) -> Option<BoxedCipher> {
+    match id {
+        #[cfg(feature = "rijndael")]
+        AlgorithmId::Rijndael { block_size, poly } if matches!(block_size, 16 | 20 | 24 | 28 | 32) => {
+            // The modulus comes from the header: anything but an irreducible
+            // polynomial of degree 8 leaves S-box bytes without an inverse
+            if poly >> 8 != 1 {
+                return None;
+            }
+            let poly: rijndael::gf::arithmetic::Poly = (0..9).map(|bit| poly >> bit & 1 == 1).collect();
+            if !rijndael::gf::irreducible::is_irreducible(&poly) {
+                return None;
+            }
+            Some(Box::new(rijndael::rijndael::cipher::Rijndael::new(poly, block_size as usize / 4)))
+        }
+        #[cfg(feature = "twofish")]
+        AlgorithmId::Twofish => Some(Box::new(twofish::Twofish::new(&[0; 16]).ok()?)),
+        #[cfg(feature = "loki97")]
+        AlgorithmId::Loki97 => Some(Box::new(LOK197::Loki97Cipher::new(&[0; 16]).ok()?)),
+        id => builtin_algorithm(id),
+    }
+}
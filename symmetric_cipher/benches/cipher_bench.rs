@@ -6,13 +6,14 @@ use rand::RngCore;
 use std::io::Write;
 use tempfile::NamedTempFile;
 use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
 use symmetric_cipher::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
 use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
 
 struct IdentityCipher;
 
 impl SymmetricCipher for IdentityCipher {
-    fn set_key(&mut self, _: &[u8]) -> Result<(), &'static str> { Ok(()) }
+    fn set_key(&mut self, _: &[u8]) -> Result<(), CipherError> { Ok(()) }
 }
 impl CipherAlgorithm for IdentityCipher {
     fn encrypt(&self, data: &[u8]) -> Vec<u8> { data.to_vec() }
@@ -25,7 +26,7 @@ impl SymmetricCipherWithRounds for IdentityCipher {
     fn block_size(&self) -> usize { 8 }
 
     fn export_round_keys(&self) -> Option<Vec<u8>> {
-        todo!()
+        None
     }
 }
 
@@ -0,0 +1,83 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use symmetric_cipher::crypto::cipher_builder::{
+    BitPermutation, CipherBuilder, CustomCipher, FnKeyExpansion, FnTransformation, SBox,
+};
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use std::sync::Arc;
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    use rand::RngCore;
+    let mut buf = vec![0u8; len];
+    rand::rng().fill_bytes(&mut buf);
+    buf
+}
+
+const ROUNDS: usize = 16;
+
+// PRESENT's S-box and pLayer-style bit permutation.
+const SBOX: [u8; 16] = [0xC, 0x5, 0x6, 0xB, 0x9, 0x0, 0xA, 0xD, 0x3, 0xE, 0xF, 0x8, 0x4, 0x7, 0x1, 0x2];
+
+fn permutation() -> Vec<usize> {
+    (0..64).map(|i| if i == 63 { 64 } else { (i * 16) % 63 + 1 }).collect()
+}
+
+fn ciphers() -> Vec<(&'static str, CustomCipher)> {
+    let schedule = || {
+        Arc::new(FnKeyExpansion(|key: &[u8]| {
+            (0..=ROUNDS)
+                .map(|i| key.iter().map(|k| k.rotate_left(i as u32) ^ i as u8).take(8).collect())
+                .collect()
+        }))
+    };
+    let round_function = |out_len: usize| {
+        Arc::new(FnTransformation(move |input: &[u8], key: &[u8]| {
+            (0..out_len)
+                .map(|i| (input[i % input.len()] ^ key[i % key.len()]).rotate_left(3).wrapping_mul(0x1D))
+                .collect()
+        }))
+    };
+
+    let builder = || CipherBuilder::new(8).rounds(ROUNDS).key_sizes(&[16]).key_expansion(schedule());
+    vec![
+        ("feistel", builder().feistel(round_function(4)).build().unwrap()),
+        ("unbalanced feistel", builder().unbalanced_feistel(2, round_function(6)).build().unwrap()),
+        ("lai-massey", builder().lai_massey(round_function(4)).build().unwrap()),
+        (
+            "spn",
+            builder()
+                .spn(SBox::new(&SBOX).unwrap(), Some(BitPermutation::new(&permutation()).unwrap()))
+                .build()
+                .unwrap(),
+        ),
+    ]
+}
+
+fn bench_round_structures(c: &mut Criterion) {
+    let data = random_bytes(64 * 1024);
+
+    let mut group = c.benchmark_group("Custom ciphers 64KB CBC");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    for (name, cipher) in ciphers() {
+        let mut ctx = CipherContext::new(Box::new(cipher), CipherMode::CBC, PaddingMode::PKCS7, Some(vec![0u8; 8]), vec![]);
+        ctx.set_key(&[0x11; 16]).unwrap();
+
+        group.bench_function(BenchmarkId::new("encrypt", name), |b| {
+            b.iter_batched(
+                || data.clone(),
+                |input| {
+                    let mut out = CipherOutput::Buffer(Box::default());
+                    futures::executor::block_on(ctx.encrypt(CipherInput::Bytes(input), &mut out)).unwrap();
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_round_structures);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherOutput, CipherMode, PaddingMode};
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+use std::sync::Arc;
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    use rand::RngCore;
+    let mut buf = vec![0u8; len];
+    rand::rng().fill_bytes(&mut buf);
+    buf
+}
+
+// CBC/CFB decryption runs in parallel, their encryption and PCBC/OFB do not:
+// the encrypt/decrypt gap per mode is the gain.
+fn bench_chained_modes_encrypt_vs_decrypt(c: &mut Criterion) {
+    let modes = [CipherMode::CBC, CipherMode::CFB, CipherMode::PCBC, CipherMode::OFB];
+    let data = random_bytes(64 * 1024);
+
+    let mut group = c.benchmark_group("DES 64KB chained modes");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    for &mode in &modes {
+        let des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+        let mut ctx = CipherContext::new(Box::new(des), mode, PaddingMode::PKCS7, Some(vec![0u8; 8]), vec![]);
+        ctx.set_key(&[0x11; 8]).unwrap();
+
+        let mut ciphertext = CipherOutput::Buffer(Box::default());
+        futures::executor::block_on(ctx.encrypt(CipherInput::Bytes(data.clone()), &mut ciphertext)).unwrap();
+        let ciphertext = ciphertext.as_buffer().clone();
+
+        let mode_name = format!("{:?}", mode);
+
+        group.bench_function(BenchmarkId::new("encrypt", &mode_name), |b| {
+            b.iter_batched(
+                || data.clone(),
+                |input| {
+                    let mut out = CipherOutput::Buffer(Box::default());
+                    futures::executor::block_on(ctx.encrypt(CipherInput::Bytes(input), &mut out)).unwrap();
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_function(BenchmarkId::new("decrypt", &mode_name), |b| {
+            b.iter_batched(
+                || ciphertext.clone(),
+                |input| {
+                    let mut out = CipherOutput::Buffer(Box::default());
+                    futures::executor::block_on(ctx.decrypt(CipherInput::Bytes(input), &mut out)).unwrap();
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_chained_modes_encrypt_vs_decrypt);
+criterion_main!(benches);
@@ -57,7 +57,7 @@ fn bench_all_modes_and_paddings(c: &mut Criterion) {
                 b.iter_batched(
                     || data.clone(),
                     |input| {
-                        let mut out = CipherOutput::Buffer(Box::new(Vec::new()));
+                        let mut out = CipherOutput::Buffer(Box::default());
                         futures::executor::block_on(
                             ctx_des.encrypt(CipherInput::Bytes(input), &mut out)
                         ).unwrap();
@@ -68,17 +68,17 @@ fn bench_all_modes_and_paddings(c: &mut Criterion) {
 
             // === DEAL ===
             let deal_key = vec![0x22; 24];
-            let des_base = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
-            let mut deal = DEAL::new(des_base);
+            let mut deal = DEAL::new();
             deal.set_key(&deal_key).unwrap();
-            let mut ctx_deal = CipherContext::new(Box::new(deal), mode, pad, iv.clone(), vec![]);
+            let deal_iv = iv.as_ref().map(|_| vec![0u8; 16]);
+            let mut ctx_deal = CipherContext::new(Box::new(deal), mode, pad, deal_iv, vec![]);
             ctx_deal.set_key(&deal_key).unwrap();
 
             c.bench_function(&bench_name_deal, |b| {
                 b.iter_batched(
                     || data.clone(),
                     |input| {
-                        let mut out = CipherOutput::Buffer(Box::new(Vec::new()));
+                        let mut out = CipherOutput::Buffer(Box::default());
                         futures::executor::block_on(
                             ctx_deal.encrypt(CipherInput::Bytes(input), &mut out)
                         ).unwrap();
@@ -1,4 +1,3 @@
-use tokio;
 use std::fs;
 use std::path::Path;
 
@@ -118,18 +117,15 @@ async fn main() -> std::io::Result<()> {
                 // ==== DEAL ====
                 //
                 let deal_key = random_key(24);
-                let des_for_deal = DES::new(
-                    Arc::new(DesKeyExpansion) as Arc<dyn KeyExpansion + Send + Sync>,
-                    Arc::new(DesTransformation) as Arc<dyn EncryptionTransformation + Send + Sync>,
-                );
-                let mut deal = DEAL::new(des_for_deal);
+                let mut deal = DEAL::new();
                 deal.set_key(&deal_key).expect("Failed to set DEAL key");
 
+                let deal_iv = iv.as_ref().map(|_| vec![0u8; 16]);
                 let mut ctx_deal = CipherContext::new(
                     Box::new(deal),
                     mode,
                     pad,
-                    iv.clone(),
+                    deal_iv,
                     vec![],
                 );
                 ctx_deal.set_key(&deal_key).expect("Failed to set DEAL key in context");
@@ -0,0 +1,46 @@
+use std::env;
+
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::container::{
+    builtin_algorithm, AlgorithmId, ContainerHeader, ContainerReader, ContainerWriter,
+};
+use symmetric_cipher::crypto::kdf::KdfParams;
+
+/// Encrypts a file with a passphrase instead of a raw key:
+///
+/// cargo run --example encrypt_passphrase -- <input> <output> <passphrase>
+///
+/// The output is a container whose header carries the scrypt salt and cost,
+/// so the passphrase alone is enough to decrypt it.
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+    let (input, output, passphrase) = match args.as_slice() {
+        [_, input, output, passphrase] => (input, output, passphrase),
+        _ => {
+            eprintln!("usage: encrypt_passphrase <input> <output> <passphrase>");
+            std::process::exit(2);
+        }
+    };
+
+    let mut iv = vec![0u8; 16];
+    rand::RngCore::fill_bytes(&mut rand::rng(), &mut iv);
+    let mut header = ContainerHeader::new(AlgorithmId::Deal, CipherMode::CBC, PaddingMode::PKCS7, Some(iv));
+    header.kdf = Some(KdfParams::default());
+
+    let writer = ContainerWriter::with_passphrase(header, passphrase.as_bytes(), &builtin_algorithm)?;
+    writer
+        .encrypt(CipherInput::File(input.clone()), &mut CipherOutput::File(output.clone()))
+        .await?;
+    println!("{} -> {}", input, output);
+
+    let reader = ContainerReader::open(CipherInput::File(output.clone())).await?;
+    let mut check = CipherOutput::Buffer(Box::default());
+    reader
+        .decrypt_with_passphrase(passphrase.as_bytes(), &builtin_algorithm, &mut check)
+        .await?;
+    assert_eq!(check.as_buffer(), &std::fs::read(input)?);
+    println!("round trip OK");
+
+    Ok(())
+}
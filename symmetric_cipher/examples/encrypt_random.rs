@@ -1,11 +1,8 @@
-use tokio;
 use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherOutput, CipherMode, PaddingMode};
 use symmetric_cipher::crypto::cipher_context::CipherContext;
 use symmetric_cipher::crypto::des::DES;
 use symmetric_cipher::crypto::deal::DEAL;
 use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
-use symmetric_cipher::crypto::key_expansion::KeyExpansion;
-use symmetric_cipher::crypto::encryption_transformation::EncryptionTransformation;
 use symmetric_cipher::crypto::cipher_traits::SymmetricCipher;
 use symmetric_cipher::crypto::des_transformation::DesTransformation;
 
@@ -60,14 +57,14 @@ async fn main() -> std::io::Result<()> {
             let mut ctx_des = CipherContext::new(Box::new(des), mode, pad, iv.clone(), vec![]);
             ctx_des.set_key(&des_key).unwrap();
 
-            let mut out = CipherOutput::Buffer(Box::new(Vec::new()));
+            let mut out = CipherOutput::Buffer(Box::default());
             ctx_des.encrypt(CipherInput::Bytes(data.clone()), &mut out).await?;
             let ciphertext = match out {
                 CipherOutput::Buffer(b) => *b,
                 _ => unreachable!(),
             };
 
-            let mut decrypted = CipherOutput::Buffer(Box::new(Vec::new()));
+            let mut decrypted = CipherOutput::Buffer(Box::default());
             ctx_des.decrypt(CipherInput::Bytes(ciphertext.clone()), &mut decrypted).await?;
             let plain = match decrypted {
                 CipherOutput::Buffer(b) => *b,
@@ -81,24 +78,21 @@ async fn main() -> std::io::Result<()> {
             // === DEAL ===
             //
             let deal_key = random_bytes(24);
-            let base_des = DES::new(
-                Arc::new(DesKeyExpansion),
-                Arc::new(DesTransformation),
-            );
-            let mut deal = DEAL::new(base_des);
+            let mut deal = DEAL::new();
             deal.set_key(&deal_key).unwrap();
 
-            let mut ctx_deal = CipherContext::new(Box::new(deal), mode, pad, iv.clone(), vec![]);
+            let deal_iv = iv.as_ref().map(|_| vec![0u8; 16]);
+            let mut ctx_deal = CipherContext::new(Box::new(deal), mode, pad, deal_iv, vec![]);
             ctx_deal.set_key(&deal_key).unwrap();
 
-            let mut out = CipherOutput::Buffer(Box::new(Vec::new()));
+            let mut out = CipherOutput::Buffer(Box::default());
             ctx_deal.encrypt(CipherInput::Bytes(data.clone()), &mut out).await?;
             let ciphertext = match out {
                 CipherOutput::Buffer(b) => *b,
                 _ => unreachable!(),
             };
 
-            let mut decrypted = CipherOutput::Buffer(Box::new(Vec::new()));
+            let mut decrypted = CipherOutput::Buffer(Box::default());
             ctx_deal.decrypt(CipherInput::Bytes(ciphertext.clone()), &mut decrypted).await?;
             let plain = match decrypted {
                 CipherOutput::Buffer(b) => *b,
@@ -0,0 +1,99 @@
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
+use crate::crypto::mac::Cmac;
+
+/// Multiplication in GF(2^128) with the GCM bit order (NIST SP 800-38D, Algorithm 1).
+/// Branch-free so that the timing does not depend on H.
+pub fn gf128_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xE1 << 120;
+
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        let bit = (x >> (127 - i)) & 1;
+        z ^= v & 0u128.wrapping_sub(bit);
+        let lsb = v & 1;
+        v = (v >> 1) ^ (R & 0u128.wrapping_sub(lsb));
+    }
+    z
+}
+
+fn ghash_update(h: u128, mut acc: u128, data: &[u8]) -> u128 {
+    for chunk in data.chunks(16) {
+        let mut block = [0u8; 16];
+        block[..chunk.len()].copy_from_slice(chunk);
+        acc = gf128_mul(acc ^ u128::from_be_bytes(block), h);
+    }
+    acc
+}
+
+/// GHASH_H(A || 0^v || C || 0^u || [len(A)]_64 || [len(C)]_64)
+pub fn ghash(h: u128, associated_data: &[u8], ciphertext: &[u8]) -> u128 {
+    let mut acc = ghash_update(h, 0, associated_data);
+    acc = ghash_update(h, acc, ciphertext);
+
+    let lengths =
+        ((associated_data.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+    gf128_mul(acc ^ lengths, h)
+}
+
+/// Largest block size, in bytes, that [`dbl`] and so CMAC and EAX support.
+pub const MAX_DOUBLING_BLOCK_SIZE: usize = 32;
+
+/// Reduction constants for doubling in GF(2^n), n = 8 · block size, indexed by
+/// block size - 1: the low terms of the minimal-weight irreducible polynomial
+/// of each degree (Seroussi's table, the one CMAC/OMAC use).
+const DOUBLING_CONSTANTS: [u16; MAX_DOUBLING_BLOCK_SIZE] = [
+    0x001B, 0x002B, 0x001B, 0x008D, 0x0039, 0x002D, 0x0095, 0x001B,
+    0x0609, 0x0215, 0x00C5, 0x0641, 0x001B, 0x0039, 0x001B, 0x0087,
+    0x002D, 0x0095, 0x004D, 0x002D, 0x800D, 0x080D, 0x0381, 0x0087,
+    0x002D, 0x020B, 0x008B, 0x0309, 0x0215, 0x0129, 0xC401, 0x0425,
+];
+
+/// Fails for block sizes [`dbl`] has no reduction constant for.
+pub fn check_doubling_block_size(block_size: usize) -> Result<(), CipherError> {
+    if block_size == 0 || block_size > MAX_DOUBLING_BLOCK_SIZE {
+        return Err(CipherError::InvalidParameter("CMAC/EAX support block sizes of 1 to 32 bytes"));
+    }
+    Ok(())
+}
+
+/// Multiplication by x in GF(2^n), big-endian. Panics for blocks outside
+/// 1..=[`MAX_DOUBLING_BLOCK_SIZE`] bytes; see [`check_doubling_block_size`].
+pub fn dbl(block: &[u8]) -> Vec<u8> {
+    check_doubling_block_size(block.len()).expect("dbl block size");
+    let msb_set = block[0] & 0x80 != 0;
+
+    let mut out = vec![0u8; block.len()];
+    for (i, o) in out.iter_mut().enumerate() {
+        let next = block.get(i + 1).map_or(0, |&b| b >> 7);
+        *o = (block[i] << 1) | next;
+    }
+
+    if msb_set {
+        let rb = DOUBLING_CONSTANTS[block.len() - 1].to_be_bytes();
+        for (o, r) in out.iter_mut().rev().zip(rb.iter().rev()) {
+            *o ^= r;
+        }
+    }
+    out
+}
+
+/// OMAC1 (CMAC) over `[tweak]_n || data`, as used by EAX.
+pub fn omac(cipher: &dyn SymmetricCipherWithRounds, tweak: u8, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let mut mac = Cmac::new(cipher)?;
+    let mut tweak_block = vec![0u8; cipher.block_size()];
+    *tweak_block.last_mut().unwrap() = tweak;
+
+    mac.update(&tweak_block);
+    mac.update(data);
+    Ok(mac.finalize())
+}
+
+/// Compares two tags without an early exit on the first differing byte.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
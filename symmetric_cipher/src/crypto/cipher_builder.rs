@@ -0,0 +1,435 @@
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
+use crate::crypto::encryption_transformation::EncryptionTransformation;
+use crate::crypto::key_expansion::KeyExpansion;
+use crate::crypto::utils::permute_bits;
+use std::sync::Arc;
+
+pub type SharedKeyExpansion = Arc<dyn KeyExpansion + Send + Sync>;
+pub type SharedTransformation = Arc<dyn EncryptionTransformation + Send + Sync>;
+
+/// Uses a closure `|input, round_key| -> output` as a round function.
+pub struct FnTransformation<F>(pub F);
+
+impl<F: Fn(&[u8], &[u8]) -> Vec<u8>> EncryptionTransformation for FnTransformation<F> {
+    fn transform(&self, input_block: &[u8], round_key: &[u8]) -> Vec<u8> {
+        (self.0)(input_block, round_key)
+    }
+}
+
+/// Uses a closure `|key| -> round_keys` as a key schedule.
+pub struct FnKeyExpansion<F>(pub F);
+
+impl<F: Fn(&[u8]) -> Vec<Vec<u8>>> KeyExpansion for FnKeyExpansion<F> {
+    fn generate_round_keys(&self, key: &[u8]) -> Vec<Vec<u8>> {
+        (self.0)(key)
+    }
+}
+
+/// Byte substitution together with its inverse.
+#[derive(Clone)]
+pub struct SBox {
+    forward: [u8; 256],
+    inverse: [u8; 256],
+}
+
+impl SBox {
+    /// Takes a 256-entry table, or a 16-entry 4-bit table that is applied to
+    /// both nibbles of every byte. The table must be a bijection.
+    pub fn new(table: &[u8]) -> Result<Self, CipherError> {
+        let mut forward = [0u8; 256];
+        match table.len() {
+            256 => forward.copy_from_slice(table),
+            16 if table.iter().all(|&v| v < 16) => {
+                for (b, out) in forward.iter_mut().enumerate() {
+                    *out = (table[b >> 4] << 4) | table[b & 0xF];
+                }
+            }
+            _ => return Err(CipherError::InvalidParameter("S-box must have 16 4-bit or 256 8-bit entries")),
+        }
+
+        let mut inverse = [0u8; 256];
+        let mut seen = [false; 256];
+        for (input, &output) in forward.iter().enumerate() {
+            if seen[output as usize] {
+                return Err(CipherError::InvalidParameter("S-box is not a bijection"));
+            }
+            seen[output as usize] = true;
+            inverse[output as usize] = input as u8;
+        }
+        Ok(SBox { forward, inverse })
+    }
+
+    pub fn apply(&self, byte: u8) -> u8 {
+        self.forward[byte as usize]
+    }
+
+    pub fn invert(&self, byte: u8) -> u8 {
+        self.inverse[byte as usize]
+    }
+}
+
+/// Bit permutation numbered like the DES tables: output bit `i` is input bit
+/// `table[i]`, counting from 1 at the most significant bit of the first byte.
+#[derive(Clone)]
+pub struct BitPermutation {
+    forward: Vec<usize>,
+    inverse: Vec<usize>,
+}
+
+impl BitPermutation {
+    pub fn new(table: &[usize]) -> Result<Self, CipherError> {
+        let mut inverse = vec![0usize; table.len()];
+        for (i, &pos) in table.iter().enumerate() {
+            if pos == 0 || pos > table.len() || inverse[pos - 1] != 0 {
+                return Err(CipherError::InvalidParameter("bit permutation must use every position 1..=n once"));
+            }
+            inverse[pos - 1] = i + 1;
+        }
+        Ok(BitPermutation { forward: table.to_vec(), inverse })
+    }
+
+    pub fn bits(&self) -> usize {
+        self.forward.len()
+    }
+
+    pub fn apply(&self, block: &mut [u8]) {
+        let out = permute_bits(block, &self.forward);
+        block.copy_from_slice(&out);
+    }
+
+    pub fn invert(&self, block: &mut [u8]) {
+        let out = permute_bits(block, &self.inverse);
+        block.copy_from_slice(&out);
+    }
+}
+
+#[derive(Clone)]
+enum Structure {
+    /// Block = target || source. A round XORs F(source) into the target and
+    /// rotates the source to the front: (y, x) -> (x, y ^ F(x)).
+    Feistel { source_len: usize, round_function: SharedTransformation },
+    /// Halves (L, R), t = F(L ^ R): (L, R) -> (sigma(L ^ t), R ^ t), with
+    /// sigma(a, b) = (b, a ^ b) on the quarters of L. The last round skips sigma.
+    LaiMassey { round_function: SharedTransformation },
+    /// Key addition, S-box layer and bit permutation per round; the last
+    /// round skips the permutation and is followed by a whitening key.
+    Spn { sbox: Box<SBox>, permutation: Option<BitPermutation> },
+}
+
+/// Assembles a block cipher from a key schedule, a round count and one of
+/// the round structures above.
+///
+/// ```ignore
+/// let cipher = CipherBuilder::new(8)
+///     .rounds(12)
+///     .key_sizes(&[16])
+///     .key_expansion(Arc::new(FnKeyExpansion(my_schedule)))
+///     .unbalanced_feistel(2, Arc::new(FnTransformation(my_round_function)))
+///     .build()?;
+/// ```
+pub struct CipherBuilder {
+    block_size: usize,
+    rounds: usize,
+    key_sizes: &'static [usize],
+    key_expansion: Option<SharedKeyExpansion>,
+    structure: Option<Structure>,
+}
+
+impl CipherBuilder {
+    pub fn new(block_size: usize) -> Self {
+        CipherBuilder {
+            block_size,
+            rounds: 0,
+            key_sizes: &[],
+            key_expansion: None,
+            structure: None,
+        }
+    }
+
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Key lengths `set_key` accepts; any length if left empty.
+    pub fn key_sizes(mut self, key_sizes: &'static [usize]) -> Self {
+        self.key_sizes = key_sizes;
+        self
+    }
+
+    pub fn key_expansion(mut self, key_expansion: SharedKeyExpansion) -> Self {
+        self.key_expansion = Some(key_expansion);
+        self
+    }
+
+    /// Balanced Feistel network, same layout as `FeistelNetwork`.
+    pub fn feistel(self, round_function: SharedTransformation) -> Self {
+        let source_len = self.block_size / 2;
+        self.unbalanced_feistel(source_len, round_function)
+    }
+
+    /// Feistel network whose round function reads the last `source_len`
+    /// bytes and must return at least `block_size - source_len` bytes.
+    pub fn unbalanced_feistel(mut self, source_len: usize, round_function: SharedTransformation) -> Self {
+        self.structure = Some(Structure::Feistel { source_len, round_function });
+        self
+    }
+
+    /// The round function gets and must return half a block.
+    pub fn lai_massey(mut self, round_function: SharedTransformation) -> Self {
+        self.structure = Some(Structure::LaiMassey { round_function });
+        self
+    }
+
+    /// Round keys must be one block long; the key schedule has to produce
+    /// `rounds + 1` of them.
+    pub fn spn(mut self, sbox: SBox, permutation: Option<BitPermutation>) -> Self {
+        self.structure = Some(Structure::Spn { sbox: Box::new(sbox), permutation });
+        self
+    }
+
+    pub fn build(self) -> Result<CustomCipher, CipherError> {
+        if self.block_size == 0 {
+            return Err(CipherError::InvalidParameter("block size must be positive"));
+        }
+        if self.rounds == 0 {
+            return Err(CipherError::InvalidParameter("round count must be positive"));
+        }
+        let key_expansion = self
+            .key_expansion
+            .ok_or(CipherError::InvalidParameter("key schedule is not set"))?;
+        let structure = self
+            .structure
+            .ok_or(CipherError::InvalidParameter("round structure is not set"))?;
+
+        match &structure {
+            Structure::Feistel { source_len, .. } if *source_len == 0 || *source_len >= self.block_size => {
+                return Err(CipherError::InvalidParameter("Feistel source must be a proper part of the block"));
+            }
+            Structure::LaiMassey { .. } if !self.block_size.is_multiple_of(4) => {
+                return Err(CipherError::InvalidParameter("Lai-Massey needs a block size divisible by 4"));
+            }
+            Structure::Spn { permutation: Some(p), .. } if p.bits() != self.block_size * 8 => {
+                return Err(CipherError::InvalidParameter("bit permutation must cover the whole block"));
+            }
+            _ => {}
+        }
+
+        Ok(CustomCipher {
+            block_size: self.block_size,
+            rounds: self.rounds,
+            key_sizes: self.key_sizes,
+            key_expansion,
+            structure,
+            round_keys: Vec::new(),
+        })
+    }
+}
+
+/// A cipher produced by [`CipherBuilder`].
+#[derive(Clone)]
+pub struct CustomCipher {
+    block_size: usize,
+    rounds: usize,
+    key_sizes: &'static [usize],
+    key_expansion: SharedKeyExpansion,
+    structure: Structure,
+    round_keys: Vec<Vec<u8>>,
+}
+
+impl CustomCipher {
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Round keys for one block operation. The in-place block methods cannot
+    /// return an error, so they panic on an unkeyed cipher; `CipherContext`
+    /// reports [`CipherError::InvalidParameter`] instead.
+    fn active_round_keys(&self) -> &[Vec<u8>] {
+        assert!(!self.round_keys.is_empty(), "CustomCipher used before set_key");
+        &self.round_keys[..self.required_round_keys()]
+    }
+
+    fn required_round_keys(&self) -> usize {
+        match self.structure {
+            Structure::Spn { .. } => self.rounds + 1,
+            _ => self.rounds,
+        }
+    }
+}
+
+fn xor_in_place(dst: &mut [u8], src: &[u8]) {
+    assert!(src.len() >= dst.len(), "round function output is shorter than its target");
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+/// sigma(a, b) = (b, a ^ b), an orthomorphism for XOR.
+fn sigma(half: &mut [u8]) {
+    let (a, b) = half.split_at_mut(half.len() / 2);
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let old = *x;
+        *x = *y;
+        *y ^= old;
+    }
+}
+
+fn sigma_inverse(half: &mut [u8]) {
+    let (a, b) = half.split_at_mut(half.len() / 2);
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let old = *x;
+        *x ^= *y;
+        *y = old;
+    }
+}
+
+fn lai_massey_mix(block: &mut [u8], round_function: &SharedTransformation, round_key: &[u8]) {
+    let (left, right) = block.split_at_mut(block.len() / 2);
+    let sum: Vec<u8> = left.iter().zip(right.iter()).map(|(l, r)| l ^ r).collect();
+    let t = round_function.transform(&sum, round_key);
+    xor_in_place(left, &t);
+    xor_in_place(right, &t);
+}
+
+impl CipherAlgorithm for CustomCipher {
+    fn encrypt(&self, block: &[u8]) -> Vec<u8> {
+        let mut out = block.to_vec();
+        self.encrypt_block_inplace(&mut out);
+        out
+    }
+
+    fn decrypt(&self, block: &[u8]) -> Vec<u8> {
+        let mut out = block.to_vec();
+        self.decrypt_block_inplace(&mut out);
+        out
+    }
+}
+
+impl SymmetricCipher for CustomCipher {
+    fn set_key(&mut self, key: &[u8]) -> Result<(), CipherError> {
+        if !self.key_sizes.is_empty() && !self.key_sizes.contains(&key.len()) {
+            return Err(CipherError::InvalidKeyLength {
+                expected: self.key_sizes,
+                actual: key.len(),
+            });
+        }
+        let round_keys = self.key_expansion.generate_round_keys(key);
+        if round_keys.len() < self.required_round_keys() {
+            return Err(CipherError::InvalidParameter("key schedule produced too few round keys"));
+        }
+        if matches!(self.structure, Structure::Spn { .. })
+            && round_keys.iter().any(|k| k.len() != self.block_size)
+        {
+            return Err(CipherError::InvalidParameter("SPN round keys must be one block long"));
+        }
+        self.round_keys = round_keys;
+        Ok(())
+    }
+
+    fn key_sizes(&self) -> &'static [usize] {
+        self.key_sizes
+    }
+
+    fn check_key(&self) -> Result<(), CipherError> {
+        if self.round_keys.is_empty() {
+            return Err(CipherError::InvalidParameter("key is not set"));
+        }
+        Ok(())
+    }
+}
+
+impl SymmetricCipherWithRounds for CustomCipher {
+    fn set_key_with_rounds(&mut self, key: &[u8]) {
+        self.set_key(key).unwrap();
+    }
+
+    fn encrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
+        CipherAlgorithm::encrypt(self, data)
+    }
+
+    fn decrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
+        CipherAlgorithm::decrypt(self, data)
+    }
+
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        let round_keys = self.active_round_keys();
+        match &self.structure {
+            Structure::Feistel { source_len, round_function } => {
+                let target_len = block.len() - source_len;
+                for round_key in round_keys {
+                    let f = round_function.transform(&block[target_len..], round_key);
+                    xor_in_place(&mut block[..target_len], &f);
+                    block.rotate_right(*source_len);
+                }
+            }
+            Structure::LaiMassey { round_function } => {
+                let half = block.len() / 2;
+                for (i, round_key) in round_keys.iter().enumerate() {
+                    lai_massey_mix(block, round_function, round_key);
+                    if i + 1 < self.rounds {
+                        sigma(&mut block[..half]);
+                    }
+                }
+            }
+            Structure::Spn { sbox, permutation } => {
+                let (whitening, round_keys) = round_keys.split_last().unwrap();
+                for (i, round_key) in round_keys.iter().enumerate() {
+                    xor_in_place(block, round_key);
+                    for b in block.iter_mut() {
+                        *b = sbox.apply(*b);
+                    }
+                    if let Some(p) = permutation.as_ref().filter(|_| i + 1 < self.rounds) {
+                        p.apply(block);
+                    }
+                }
+                xor_in_place(block, whitening);
+            }
+        }
+    }
+
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        let round_keys = self.active_round_keys();
+        match &self.structure {
+            Structure::Feistel { source_len, round_function } => {
+                for round_key in round_keys.iter().rev() {
+                    let f = round_function.transform(&block[..*source_len], round_key);
+                    xor_in_place(&mut block[*source_len..], &f);
+                    block.rotate_left(*source_len);
+                }
+            }
+            Structure::LaiMassey { round_function } => {
+                let half = block.len() / 2;
+                for (i, round_key) in round_keys.iter().enumerate().rev() {
+                    if i + 1 < self.rounds {
+                        sigma_inverse(&mut block[..half]);
+                    }
+                    lai_massey_mix(block, round_function, round_key);
+                }
+            }
+            Structure::Spn { sbox, permutation } => {
+                let (whitening, round_keys) = round_keys.split_last().unwrap();
+                xor_in_place(block, whitening);
+                for (i, round_key) in round_keys.iter().enumerate().rev() {
+                    if let Some(p) = permutation.as_ref().filter(|_| i + 1 < self.rounds) {
+                        p.invert(block);
+                    }
+                    for b in block.iter_mut() {
+                        *b = sbox.invert(*b);
+                    }
+                    xor_in_place(block, round_key);
+                }
+            }
+        }
+    }
+
+    fn export_round_keys(&self) -> Option<Vec<u8>> {
+        self.check_key().ok()?;
+        Some(self.round_keys.concat())
+    }
+}
@@ -1,23 +1,35 @@
+use crate::crypto::aead::{check_doubling_block_size, constant_time_eq, ghash, omac};
+use crate::crypto::cipher_error::CipherError;
 use crate::crypto::cipher_io::write_all;
+use crate::crypto::cipher_stream::CipherStream;
 use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
 use crate::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use crate::crypto::kdf::{KdfParams, key_len_for};
 use crate::crypto::utils::{apply_padding, remove_padding};
+use crate::crypto::xts::{process_sector, process_sectors, XTS_BLOCK_SIZE};
 use rayon::prelude::*;
-use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
 use std::sync::Arc;
 
-const CHUNK_SIZE: usize = 1024 * 1024;
+pub(crate) const CHUNK_SIZE: usize = 1024 * 1024;
 const OPTIMAL_PARALLELISM_THRESHOLD: usize = 4 * 1024 * 1024;
+const GCM_NONCE_SIZE: usize = 12;
+const GCM_COUNTER_SIZE: usize = 4;
+const UNPADDED_PARTIAL_BLOCK: CipherError =
+    CipherError::InvalidParameter("without padding the input must be whole blocks");
+
+/// Chaining state of a message processed piecewise: the feedback register
+/// (IV / CTR counter), and input that cannot be released yet.
+pub(crate) struct StreamState {
+    prev: Vec<u8>,
+    counter_offset: usize,
+    pending: Vec<u8>,
+    seen_input: bool,
+}
 
-struct VecWriter<'a>(&'a mut Vec<u8>);
-impl<'a> Write for VecWriter<'a> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.0.extend_from_slice(buf);
-        Ok(buf.len())
-    }
-    fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
+impl StreamState {
+    /// Input received but not yet turned into output.
+    pub(crate) fn buffered(&self) -> usize {
+        self.pending.len()
     }
 }
 
@@ -28,6 +40,10 @@ pub struct CipherContext {
     padding: PaddingMode,
     iv: Option<Vec<u8>>,
     additional_params: Vec<u8>,
+    associated_data: Vec<u8>,
+    /// Second, independently keyed cipher that encrypts the XTS tweak.
+    tweak_algorithm: Option<Arc<dyn SymmetricCipherWithRounds + Send + Sync>>,
+    sector_size: usize,
 }
 
 impl CipherContext {
@@ -44,11 +60,61 @@ impl CipherContext {
             padding,
             iv,
             additional_params,
+            associated_data: Vec::new(),
+            tweak_algorithm: None,
+            sector_size: 0,
         }
     }
 
-    pub fn set_key(&mut self, key: &[u8]) -> Result<(), &'static str> {
-        let alg = Arc::get_mut(&mut self.algorithm).ok_or("Failed to acquire mutable algorithm")?;
+    /// XTS context over two instances of a 128-bit block cipher: `data_cipher`
+    /// encrypts the data, `tweak_cipher` the sector number. `encrypt`/`decrypt`
+    /// treat the message as consecutive `sector_size`-byte sectors, starting
+    /// from the sector number given as IV (16 bytes, little-endian; 0 if none).
+    /// Output length always equals input length; padding is not used.
+    pub fn new_xts(
+        data_cipher: Box<dyn SymmetricCipherWithRounds + Send + Sync>,
+        tweak_cipher: Box<dyn SymmetricCipherWithRounds + Send + Sync>,
+        sector_size: usize,
+    ) -> Self {
+        Self {
+            tweak_algorithm: Some(Arc::from(tweak_cipher)),
+            sector_size,
+            ..Self::new(data_cipher, CipherMode::XTS, PaddingMode::Zeros, None, Vec::new())
+        }
+    }
+
+    /// For contexts built by `new_xts`, which starts without an IV.
+    pub(crate) fn set_iv(&mut self, iv: Option<Vec<u8>>) {
+        self.iv = iv;
+    }
+
+    /// Associated data authenticated (but not encrypted) by GCM/EAX when going
+    /// through `encrypt`/`decrypt`.
+    pub fn set_associated_data(&mut self, associated_data: &[u8]) {
+        self.associated_data = associated_data.to_vec();
+    }
+
+    /// In XTS mode `key` is the two cipher keys concatenated: the first half
+    /// keys the data cipher, the second half the tweak cipher.
+    pub fn set_key(&mut self, key: &[u8]) -> Result<(), CipherError> {
+        const SHARED: CipherError = CipherError::InvalidParameter(
+            "cannot rekey a CipherContext whose algorithm is shared with a clone",
+        );
+
+        let key = if let Some(tweak_algorithm) = self.tweak_algorithm.as_mut() {
+            if !key.len().is_multiple_of(2) {
+                return Err(CipherError::InvalidParameter(
+                    "XTS key must be two keys of equal length",
+                ));
+            }
+            let (data_key, tweak_key) = key.split_at(key.len() / 2);
+            Arc::get_mut(tweak_algorithm).ok_or(SHARED)?.set_key(tweak_key)?;
+            data_key
+        } else {
+            key
+        };
+
+        let alg = Arc::get_mut(&mut self.algorithm).ok_or(SHARED)?;
 
         alg.set_key(key)?;
 
@@ -60,947 +126,858 @@ impl CipherContext {
         Ok(())
     }
 
+    /// `new` followed by [`CipherContext::set_passphrase`].
+    pub fn with_passphrase(
+        algorithm: Box<dyn SymmetricCipherWithRounds + Send + Sync>,
+        mode: CipherMode,
+        padding: PaddingMode,
+        iv: Option<Vec<u8>>,
+        passphrase: &[u8],
+        kdf: &KdfParams,
+    ) -> Result<Self, CipherError> {
+        let mut context = Self::new(algorithm, mode, padding, iv, Vec::new());
+        context.set_passphrase(passphrase, kdf)?;
+        Ok(context)
+    }
+
+    /// Derives the key from `passphrase` with `kdf` and sets it. The key is as
+    /// long as the longest one the algorithm accepts (twice that in XTS).
+    /// Keep `kdf` (with its salt) alongside the ciphertext to decrypt later.
+    pub fn set_passphrase(&mut self, passphrase: &[u8], kdf: &KdfParams) -> Result<(), CipherError> {
+        let mut key_len = key_len_for(&*self.algorithm)?;
+        if self.tweak_algorithm.is_some() {
+            key_len *= 2;
+        }
+        let mut key = kdf.derive_key(passphrase, key_len)?;
+        self.algorithm.prepare_derived_key(&mut key);
+        self.set_key(&key)
+    }
+
     #[inline]
     fn increment_block(block: &mut [u8], value: usize) {
         let mut carry = value;
         for byte in block.iter_mut().rev() {
-            let (res, overflow) = byte.overflowing_add((carry & 0xFF) as u8);
-            *byte = res;
-            carry >>= 8;
-            if !overflow && carry == 0 {
+            let sum = *byte as usize + (carry & 0xFF);
+            *byte = sum as u8;
+            carry = (carry >> 8) + (sum >> 8);
+            if carry == 0 {
                 break;
             }
         }
     }
 
     #[inline]
-    fn is_stream_mode(&self) -> bool {
+    pub(crate) fn is_authenticated_mode(&self) -> bool {
+        matches!(self.mode, CipherMode::GCM | CipherMode::EAX)
+    }
+
+    #[inline]
+    fn is_ciphertext_stealing(&self) -> bool {
         matches!(
             self.mode,
-            CipherMode::CFB | CipherMode::OFB | CipherMode::CTR
+            CipherMode::CBC_CS1 | CipherMode::CBC_CS2 | CipherMode::CBC_CS3
         )
     }
 
     #[inline]
-    fn is_empty_input_test_pattern(&self, data: &[u8]) -> bool {
-        if !matches!(
+    fn is_cbc(&self) -> bool {
+        matches!(self.mode, CipherMode::CBC) || self.is_ciphertext_stealing()
+    }
+
+    #[inline]
+    fn is_stream_mode(&self) -> bool {
+        matches!(
             self.mode,
-            CipherMode::CBC | CipherMode::PCBC | CipherMode::RandomDelta
-        ) {
-            return false;
-        }
+            CipherMode::CFB | CipherMode::OFB | CipherMode::CTR
+        )
+    }
 
-        if matches!(self.padding, PaddingMode::Zeros) {
-            return false;
+    /// ECB ignores the IV; GCM/EAX need a non-empty nonce (and EAX a block of
+    /// at most 32 bytes); the other modes
+    /// fall back to an all-zero IV when none is given.
+    /// Checks that the ciphers are keyed and the IV suits the mode.
+    pub(crate) fn check_ready(&self) -> Result<(), CipherError> {
+        self.algorithm.check_key()?;
+        if let Some(tweak_algorithm) = &self.tweak_algorithm {
+            tweak_algorithm.check_key()?;
         }
+        self.check_iv()
+    }
 
+    fn check_iv(&self) -> Result<(), CipherError> {
         let block_size = self.algorithm.block_size();
 
-        if block_size == 8 && (data.len() == block_size || data.len() == 2 * block_size) {
-            match self.padding {
-                PaddingMode::PKCS7 => {
-                    if data.len() == block_size && data.iter().all(|&b| b == 8) {
-                        return true;
-                    }
-                    if data.len() == 2 * block_size
-                        && data[..block_size].iter().all(|&b| b == 8)
-                        && data[block_size..].iter().all(|&b| b == 0)
-                    {
-                        return true;
-                    }
+        match self.mode {
+            CipherMode::ECB => Ok(()),
+            CipherMode::XTS => {
+                let tweak_block_size = self.tweak_algorithm.as_ref().map(|t| t.block_size());
+                if block_size != XTS_BLOCK_SIZE || tweak_block_size != Some(XTS_BLOCK_SIZE) {
+                    return Err(CipherError::InvalidParameter(
+                        "XTS requires two ciphers with a 128-bit block (use CipherContext::new_xts)",
+                    ));
                 }
-                PaddingMode::ANSI_X923 => {
-                    if data.len() == block_size
-                        && data[..block_size - 1].iter().all(|&b| b == 0)
-                        && data[block_size - 1] == 8
-                    {
-                        return true;
-                    }
-                    if data.len() == 2 * block_size
-                        && data[..block_size - 1].iter().all(|&b| b == 0)
-                        && data[block_size - 1] == 8
-                        && data[block_size..].iter().all(|&b| b == 0)
-                    {
-                        return true;
-                    }
+                if self.sector_size < XTS_BLOCK_SIZE {
+                    return Err(CipherError::InvalidParameter(
+                        "XTS sector size must be at least one 16-byte block",
+                    ));
                 }
-                PaddingMode::ISO10126 => {
-                    if data.len() == 2 * block_size
-                        && data[block_size - 1] == 8
-                        && data[data.len() - 1] == 0
-                    {
-                        return true;
-                    }
-
-                    if data.len() == block_size && data[block_size - 1] == 8 {
-                        return true;
-                    }
+                match self.iv.as_deref() {
+                    Some(iv) if iv.len() != XTS_BLOCK_SIZE => Err(CipherError::InvalidIvLength {
+                        expected: XTS_BLOCK_SIZE,
+                        actual: iv.len(),
+                    }),
+                    _ => Ok(()),
+                }
+            }
+            CipherMode::GCM | CipherMode::EAX => {
+                if self.mode == CipherMode::EAX {
+                    check_doubling_block_size(block_size)?;
+                }
+                match self.iv.as_deref() {
+                    Some(nonce) if !nonce.is_empty() => Ok(()),
+                    _ => Err(CipherError::InvalidIvLength {
+                        expected: if matches!(self.mode, CipherMode::GCM) {
+                            GCM_NONCE_SIZE
+                        } else {
+                            block_size
+                        },
+                        actual: 0,
+                    }),
                 }
-                _ => {}
             }
+            _ => match self.iv.as_deref() {
+                Some(iv) if iv.len() != block_size => Err(CipherError::InvalidIvLength {
+                    expected: block_size,
+                    actual: iv.len(),
+                }),
+                _ => Ok(()),
+            },
         }
-
-        false
     }
 
-    fn process_ctr_batch(&self, data: &[u8], counter_start: &[u8], start_idx: usize) -> Vec<u8> {
+    /// XORs `data` in place with the CTR keystream for blocks `start_idx..`,
+    /// encrypting the whole batch of counters in one `encrypt_blocks` call.
+    fn process_ctr_batch(
+        &self,
+        data: &mut [u8],
+        counter_start: &[u8],
+        start_idx: usize,
+        counter_size: usize,
+    ) {
         let block_size = self.algorithm.block_size();
-        let round_key = &self.additional_params;
-        let mut result = Vec::with_capacity(data.len());
-
-        for (i, chunk) in data.chunks(block_size).enumerate() {
-            let mut counter = counter_start.to_vec();
-            Self::increment_block(&mut counter, start_idx + i);
-
-            let keystream = self.algorithm.encrypt_block(&counter, round_key);
-
-            for (j, &b) in chunk.iter().enumerate() {
-                result.push(keystream[j] ^ b);
-            }
+        let blocks = data.len().div_ceil(block_size);
+
+        let mut keystream = Vec::with_capacity(blocks * block_size);
+        for i in 0..blocks {
+            let offset = keystream.len();
+            keystream.extend_from_slice(counter_start);
+            Self::increment_block(
+                &mut keystream[offset + block_size - counter_size..],
+                start_idx + i,
+            );
         }
+        self.algorithm.encrypt_blocks(&mut keystream);
 
-        result
+        xor_in_place(data, &keystream);
     }
 
-    fn process_ecb_parallel(&self, data: &[u8], encrypt: bool) -> Vec<u8> {
+    /// Size (whole blocks) of the pieces `data_len` bytes are split into for
+    /// rayon: one piece per thread for large inputs, `small_blocks` blocks otherwise.
+    fn parallel_chunk_size(&self, data_len: usize, small_blocks: usize) -> usize {
         let block_size = self.algorithm.block_size();
-        let round_key = &self.additional_params;
 
-        let optimal_chunk_size = if data.len() > OPTIMAL_PARALLELISM_THRESHOLD {
-            (data.len() / rayon::current_num_threads())
+        if data_len > OPTIMAL_PARALLELISM_THRESHOLD {
+            (data_len / rayon::current_num_threads())
                 .max(block_size)
                 .min(CHUNK_SIZE)
                 / block_size
                 * block_size
         } else {
-            block_size
-        };
-
-        data.par_chunks(optimal_chunk_size)
-            .flat_map(|mega_chunk| {
-                let mut result = Vec::with_capacity(mega_chunk.len());
-
-                for chunk in mega_chunk.chunks(block_size) {
-                    let mut block = chunk.to_vec();
-
-                    if block.len() < block_size {
-                        block.resize(block_size, 0);
-                    }
-
-                    let processed = if encrypt {
-                        self.algorithm.encrypt_block(&block, round_key)
-                    } else {
-                        self.algorithm.decrypt_block(&block, round_key)
-                    };
+            block_size * small_blocks
+        }
+    }
 
-                    result.extend(processed);
-                }
+    /// `data` must be whole blocks.
+    fn process_ecb_parallel(&self, data: &mut [u8], encrypt: bool) {
+        let optimal_chunk_size = self.parallel_chunk_size(data.len(), 64);
 
-                result
-            })
-            .collect()
+        data.par_chunks_mut(optimal_chunk_size).for_each(|chunk| {
+            if encrypt {
+                self.algorithm.encrypt_blocks(chunk);
+            } else {
+                self.algorithm.decrypt_blocks(chunk);
+            }
+        });
     }
 
-    fn process_ctr_parallel(&self, data: &[u8], iv: &[u8]) -> Vec<u8> {
+    /// `counter_size` is the number of trailing bytes of the counter block that
+    /// get incremented: the whole block for CTR/EAX, 4 bytes (inc32) for GCM.
+    fn process_ctr_parallel(&self, data: &[u8], iv: &[u8], counter_size: usize) -> Vec<u8> {
         let block_size = self.algorithm.block_size();
+        let optimal_chunk_size = self.parallel_chunk_size(data.len(), 64);
 
-        let optimal_chunk_size = if data.len() > OPTIMAL_PARALLELISM_THRESHOLD {
-            (data.len() / rayon::current_num_threads())
-                .max(block_size)
-                .min(CHUNK_SIZE)
-        } else {
-            block_size * 64
-        };
-
-        data.par_chunks(optimal_chunk_size)
+        let mut output = data.to_vec();
+        output
+            .par_chunks_mut(optimal_chunk_size)
             .enumerate()
-            .flat_map(|(chunk_idx, chunk)| {
+            .for_each(|(chunk_idx, chunk)| {
                 let counter_offset = chunk_idx * (optimal_chunk_size / block_size);
-
-                self.process_ctr_batch(chunk, iv, counter_offset)
-            })
-            .collect()
+                self.process_ctr_batch(chunk, iv, counter_offset, counter_size);
+            });
+        output
     }
 
-    fn process_ecb_data(&self, data: &[u8], encrypt: bool) -> Vec<u8> {
+    /// CBC and CFB decryption: the feedback for every block is the previous
+    /// ciphertext block, which is already known, so chunks are independent.
+    /// `prev` is left at the last ciphertext block, as the sequential path does.
+    fn process_feedback_decrypt_parallel(&self, data: &[u8], prev: &mut [u8]) -> Vec<u8> {
         let block_size = self.algorithm.block_size();
+        if data.is_empty() {
+            return Vec::new();
+        }
 
-        if encrypt {
-            let padded_data = if data.len() % block_size == 0 && !data.is_empty() {
-                if matches!(
-                    self.padding,
-                    PaddingMode::PKCS7 | PaddingMode::ANSI_X923 | PaddingMode::ISO10126
-                ) {
-                    apply_padding(data.to_vec(), block_size, self.padding.clone())
+        let optimal_chunk_size = self.parallel_chunk_size(data.len(), 64);
+        let iv: &[u8] = prev;
+        let mut output = data.to_vec();
+
+        output
+            .par_chunks_mut(optimal_chunk_size)
+            .enumerate()
+            .for_each(|(chunk_idx, chunk)| {
+                let start = chunk_idx * optimal_chunk_size;
+                let feedback = if start == 0 {
+                    iv
                 } else {
-                    data.to_vec()
+                    &data[start - block_size..start]
+                };
+                // Ciphertext blocks shifted by one: C[i-1] for every block of the chunk.
+                let mut shifted = Vec::with_capacity(chunk.len().next_multiple_of(block_size));
+                shifted.extend_from_slice(feedback);
+                shifted.extend_from_slice(&data[start..start + chunk.len().next_multiple_of(block_size) - block_size]);
+
+                if self.is_cbc() {
+                    self.algorithm.decrypt_blocks(chunk);
+                } else {
+                    self.algorithm.encrypt_blocks(&mut shifted);
                 }
-            } else {
-                apply_padding(data.to_vec(), block_size, self.padding.clone())
-            };
+                xor_in_place(chunk, &shifted);
+            });
 
-            self.process_ecb_parallel(&padded_data, true)
-        } else {
-            let processed = self.process_ecb_parallel(data, false);
+        let last_start = (data.len() - 1) / block_size * block_size;
+        let tail = &data[last_start..];
+        prev[..tail.len()].copy_from_slice(tail);
+        prev[tail.len()..].fill(0);
 
-            if processed.is_empty() {
-                return processed;
-            }
+        output
+    }
 
-            if self.is_empty_input_test_pattern(&processed) {
-                return Vec::new();
+    fn process_ecb_data(&self, data: &[u8], encrypt: bool) -> Result<Vec<u8>, CipherError> {
+        let block_size = self.algorithm.block_size();
+
+        let unpadded = self.padding == PaddingMode::None;
+        if encrypt {
+            if unpadded && !data.len().is_multiple_of(block_size) {
+                return Err(UNPADDED_PARTIAL_BLOCK);
             }
+            let mut padded_data = apply_padding(data.to_vec(), block_size, self.padding);
+            self.process_ecb_parallel(&mut padded_data, true);
+            return Ok(padded_data);
+        }
 
-            let last_block_idx = processed.len() - block_size;
-            let (prefix, last_block) = processed.split_at(last_block_idx);
+        if unpadded && data.is_empty() {
+            return Ok(Vec::new());
+        }
+        if data.is_empty() || !data.len().is_multiple_of(block_size) {
+            return Err(CipherError::TruncatedCiphertext);
+        }
 
-            let mut result = prefix.to_vec();
-            let unpadded_block = remove_padding(last_block.to_vec(), self.padding.clone());
-            result.extend_from_slice(&unpadded_block);
+        let mut processed = data.to_vec();
+        self.process_ecb_parallel(&mut processed, false);
+        let last_block = processed.split_off(processed.len() - block_size);
+        processed.extend(remove_padding(last_block, self.padding)?);
 
-            result
-        }
+        Ok(processed)
     }
 
-    fn process_chunked_parallel<R: Read, W: Write>(
+    /// Runs `data` through the current mode. `data` is whole blocks, except
+    /// that stream modes may end with a partial one. For CTR, `prev` is the
+    /// initial counter and `counter_offset` counts blocks consumed so far.
+    fn process_blocks(
         &self,
-        mut reader: R,
-        mut writer: W,
+        data: &[u8],
+        prev: &mut [u8],
+        counter_offset: &mut usize,
         encrypt: bool,
-    ) -> std::io::Result<()> {
+    ) -> Vec<u8> {
         let block_size = self.algorithm.block_size();
-        let is_stream_mode = self.is_stream_mode();
-        let is_ecb_mode = matches!(self.mode, CipherMode::ECB);
 
-        if is_ecb_mode {
-            let mut buf = vec![0u8; block_size];
-            let mut prev_block: Option<Vec<u8>> = None;
+        match self.mode {
+            _ if !encrypt && (self.is_cbc() || matches!(self.mode, CipherMode::CFB)) => {
+                return self.process_feedback_decrypt_parallel(data, prev);
+            }
+            CipherMode::CTR => {
+                let mut output = data.to_vec();
+                self.process_ctr_batch(&mut output, prev, *counter_offset, block_size);
+                *counter_offset += data.len().div_ceil(block_size);
+                return output;
+            }
+            _ => {}
+        }
 
-            loop {
-                let n = reader.read(&mut buf[..])?;
-                if n == 0 {
-                    break;
-                }
+        let mut output = data.to_vec();
+        if matches!(self.mode, CipherMode::ECB) {
+            self.process_ecb_parallel(&mut output, encrypt);
+            return output;
+        }
 
-                let block = buf[..n].to_vec();
+        let mut scratch = vec![0u8; block_size];
+        for block in output.chunks_mut(block_size) {
+            self.process_block_inplace(block, prev, &mut scratch, encrypt);
+        }
+        output
+    }
 
-                if encrypt {
-                    if let Some(prev) = prev_block.replace(block) {
-                        let encrypted =
-                            self.algorithm.encrypt_block(&prev, &self.additional_params);
-                        writer.write_all(&encrypted)?;
-                    }
-                } else {
-                    if n < block_size {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::UnexpectedEof,
-                            "ECB decryption: incomplete block",
-                        ));
-                    }
-                    let decrypted = self
-                        .algorithm
-                        .decrypt_block(&block, &self.additional_params);
-                    if let Some(prev) = prev_block.replace(decrypted) {
-                        writer.write_all(&prev)?;
-                    }
-                }
-            }
+    pub(crate) fn start_stream(&self) -> StreamState {
+        let block_size = self.algorithm.block_size();
 
-            if encrypt {
-                let padded = apply_padding(
-                    prev_block.unwrap_or_else(Vec::new),
-                    block_size,
-                    self.padding.clone(),
-                );
-                let encrypted = self
-                    .algorithm
-                    .encrypt_block(&padded, &self.additional_params);
-                writer.write_all(&encrypted)?;
-            } else if let Some(last_block) = prev_block {
-                let unpadded = remove_padding(last_block, self.padding.clone());
-                writer.write_all(&unpadded)?;
-            }
+        StreamState {
+            prev: self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]),
+            counter_offset: 0,
+            pending: Vec::new(),
+            seen_input: false,
+        }
+    }
 
-            return writer.flush();
+    /// Feeds the next piece of the message and returns whatever output is
+    /// ready. Only whole blocks are released; on decryption of padded modes
+    /// the last block is held back because it may carry the padding.
+    pub(crate) fn stream_update(&self, state: &mut StreamState, data: &[u8], encrypt: bool) -> Vec<u8> {
+        if data.is_empty() {
+            return Vec::new();
         }
+        state.seen_input = true;
+        state.pending.extend_from_slice(data);
 
-        let mut chunk_buffer = vec![0u8; CHUNK_SIZE];
-        let counter_base = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-        let mut counter_offset = 0;
-        let mut prev_block = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-        let mut pending_blocks = Vec::new();
-        let mut is_first_chunk = true;
-        let mut all_data = Vec::new();
+        if self.is_authenticated_mode() {
+            // The tag covers the whole message, so nothing can be released before it is checked.
+            return Vec::new();
+        }
 
-        loop {
-            let n = reader.read(&mut chunk_buffer)?;
-            if n == 0 {
-                break;
-            }
+        if matches!(self.mode, CipherMode::XTS) {
+            // The last sector may be short and needs ciphertext stealing, so it
+            // waits for `stream_finish`.
+            let ready = state.pending.len().saturating_sub(1) / self.sector_size * self.sector_size;
+            let output = self
+                .process_xts(&state.pending[..ready], state.counter_offset, encrypt)
+                .expect("whole XTS sectors always hold at least one block");
+            state.counter_offset += ready / self.sector_size;
+            state.pending.drain(..ready);
+            return output;
+        }
 
-            let data_slice = &chunk_buffer[..n];
-            let is_last_chunk = n < CHUNK_SIZE;
+        let block_size = self.algorithm.block_size();
+        let ready = if self.is_ciphertext_stealing() {
+            // The last two blocks are rearranged at the end of the message.
+            state.pending.len().saturating_sub(2 * block_size) / block_size * block_size
+        } else if !encrypt && !self.is_stream_mode() {
+            state.pending.len().saturating_sub(1) / block_size * block_size
+        } else {
+            state.pending.len() / block_size * block_size
+        };
 
-            if !encrypt
-                && matches!(
-                    self.mode,
-                    CipherMode::CBC | CipherMode::PCBC | CipherMode::RandomDelta
-                )
-            {
-                all_data.extend_from_slice(data_slice);
-            }
+        let output = self.process_blocks(
+            &state.pending[..ready],
+            &mut state.prev,
+            &mut state.counter_offset,
+            encrypt,
+        );
+        state.pending.drain(..ready);
+        output
+    }
 
-            let result = match self.mode {
-                CipherMode::CTR => {
-                    let result = self.process_ctr_batch(data_slice, &counter_base, counter_offset);
-                    counter_offset += (n + block_size - 1) / block_size;
-                    result
-                }
-                _ => {
-                    let mut result = Vec::with_capacity(data_slice.len() + block_size);
-                    for (idx, chunk) in data_slice.chunks(block_size).enumerate() {
-                        let is_last_block =
-                            is_last_chunk && idx == data_slice.chunks(block_size).count() - 1;
-                        if !encrypt && is_last_block && !is_stream_mode {
-                            pending_blocks.push(chunk.to_vec());
-                            continue;
-                        }
-
-                        let needs_padding = is_last_block && encrypt && !is_stream_mode;
-                        let processed_block = self.process_single_block(
-                            chunk,
-                            &mut prev_block,
-                            encrypt,
-                            needs_padding,
-                        )?;
-                        result.extend_from_slice(&processed_block);
-                    }
-                    result
-                }
-            };
+    /// Ends the message: applies or strips the padding (or the GCM/EAX tag).
+    pub(crate) fn stream_finish(&self, state: StreamState, encrypt: bool) -> Result<Vec<u8>, CipherError> {
+        let block_size = self.algorithm.block_size();
+        let StreamState {
+            mut prev,
+            mut counter_offset,
+            pending,
+            seen_input,
+        } = state;
+
+        if self.is_authenticated_mode() {
+            return self.process_authenticated(&pending, encrypt);
+        }
 
-            writer.write_all(&result)?;
-            is_first_chunk = false;
+        if matches!(self.mode, CipherMode::XTS) {
+            return self.process_xts(&pending, counter_offset, encrypt);
         }
 
-        if !encrypt
-            && is_first_chunk
-            && matches!(
-                self.mode,
-                CipherMode::CBC | CipherMode::PCBC | CipherMode::RandomDelta
-            )
-        {
-            if self.is_empty_input_test_pattern(&all_data) {
-                return writer.flush();
-            }
+        if self.is_stream_mode() {
+            return Ok(self.process_blocks(&pending, &mut prev, &mut counter_offset, encrypt));
         }
 
-        if is_first_chunk && encrypt {
-            if is_stream_mode {
-                return writer.flush();
-            } else {
-                let padding_block = apply_padding(Vec::new(), block_size, self.padding.clone());
-                let processed =
-                    self.process_single_block(&padding_block, &mut prev_block, true, true)?;
-                writer.write_all(&processed)?;
+        if self.is_ciphertext_stealing() {
+            if !seen_input {
+                return Ok(Vec::new());
             }
+            if pending.len() < block_size {
+                return Err(if encrypt {
+                    CipherError::InvalidParameter("ciphertext stealing needs at least one full block")
+                } else {
+                    CipherError::TruncatedCiphertext
+                });
+            }
+            return Ok(if encrypt {
+                self.steal_encrypt_tail(&pending, &mut prev)
+            } else {
+                self.steal_decrypt_tail(&pending, &mut prev)
+            });
         }
 
-        if !encrypt && !is_stream_mode && !pending_blocks.is_empty() {
-            let mut last_block = pending_blocks.pop().unwrap();
-            if last_block.len() < block_size {
-                last_block.resize(block_size, 0);
+        if self.padding == PaddingMode::None {
+            // Encryption has already released every whole block.
+            if pending.is_empty() {
+                return Ok(Vec::new());
             }
-
-            let processed_last =
-                self.process_single_block(&last_block, &mut prev_block, false, false)?;
-
-            if self.is_empty_input_test_pattern(&processed_last) {
-                return writer.flush();
+            if encrypt {
+                return Err(UNPADDED_PARTIAL_BLOCK);
             }
-
-            let unpadded = match self.padding {
-                PaddingMode::Zeros => {
-                    let mut result = processed_last.clone();
-                    while result.len() > 0 && result[result.len() - 1] == 0 {
-                        result.pop();
-                    }
-
-                    if result.is_empty()
-                        && processed_last.len() > 0
-                        && (last_block.len() == 1 || (last_block.len() > 1 && last_block[0] == 0))
-                    {
-                        vec![0]
-                    } else {
-                        result
-                    }
-                }
-                _ => remove_padding(processed_last, self.padding.clone()),
-            };
-
-            if !unpadded.is_empty() {
-                writer.write_all(&unpadded)?;
+            if pending.len() != block_size {
+                return Err(CipherError::TruncatedCiphertext);
             }
+            return Ok(self.process_blocks(&pending, &mut prev, &mut counter_offset, false));
+        }
 
-            for block in pending_blocks.iter().rev() {
-                let processed = self.process_single_block(block, &mut prev_block, false, false)?;
-                writer.write_all(&processed)?;
+        if encrypt {
+            if seen_input && pending.is_empty() && matches!(self.padding, PaddingMode::Zeros) {
+                return Ok(Vec::new());
             }
+            let padded = apply_padding(pending, block_size, self.padding);
+            return Ok(self.process_blocks(&padded, &mut prev, &mut counter_offset, true));
         }
 
-        writer.flush()
+        if pending.len() != block_size {
+            return Err(CipherError::TruncatedCiphertext);
+        }
+        let last_block = self.process_blocks(&pending, &mut prev, &mut counter_offset, false);
+        remove_padding(last_block, self.padding)
     }
 
-    fn process_single_block(
-        &self,
-        block_data: &[u8],
-        prev: &mut Vec<u8>,
-        encrypt: bool,
-        apply_padding_if_needed: bool,
-    ) -> std::io::Result<Vec<u8>> {
+    /// Whether the final (possibly partial) block goes before the one preceding it.
+    fn steal_swaps(&self, partial_len: usize) -> bool {
+        match self.mode {
+            CipherMode::CBC_CS2 => partial_len < self.algorithm.block_size(),
+            CipherMode::CBC_CS3 => true,
+            _ => false,
+        }
+    }
+
+    /// CBC-encrypts the end of the message (at least one block) with the last
+    /// block zero-filled, then drops the surplus bytes of the next-to-last
+    /// ciphertext block and orders the two blocks as the CS variant says.
+    fn steal_encrypt_tail(&self, tail: &[u8], prev: &mut [u8]) -> Vec<u8> {
         let block_size = self.algorithm.block_size();
-        let round_key = &self.additional_params;
-        let is_stream_mode = self.is_stream_mode();
-
-        let mut block = block_data.to_vec();
-
-        if apply_padding_if_needed && !is_stream_mode {
-            if block.len() < block_size
-                || (block.len() == block_size
-                    && matches!(
-                        self.padding,
-                        PaddingMode::PKCS7 | PaddingMode::ANSI_X923 | PaddingMode::ISO10126
-                    ))
-            {
-                block = apply_padding(block, block_size, self.padding.clone());
-            }
-        } else if !is_stream_mode && block.len() < block_size {
-            block.resize(block_size, 0);
+        let partial_len = tail.len() - (tail.len() - 1) / block_size * block_size;
+
+        let mut padded = tail.to_vec();
+        padded.resize(tail.len().next_multiple_of(block_size), 0);
+        let mut output = self.process_blocks(&padded, prev, &mut 0, true);
+        if output.len() == block_size {
+            return output;
         }
 
-        let result = match self.mode {
-            CipherMode::CBC => {
-                if encrypt {
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < block.len() {
-                            block[i] ^= p;
-                        }
-                    }
+        let last = output.split_off(output.len() - block_size);
+        output.truncate(output.len() - block_size + partial_len);
+        if self.steal_swaps(partial_len) {
+            let stolen = output.split_off(output.len() - partial_len);
+            output.extend_from_slice(&last);
+            output.extend_from_slice(&stolen);
+        } else {
+            output.extend_from_slice(&last);
+        }
+        output
+    }
 
-                    let encrypted = self.algorithm.encrypt_block(&block, round_key);
+    /// Inverse of `steal_encrypt_tail`: the bytes cut from the next-to-last
+    /// ciphertext block are recovered from the decryption of the last one.
+    fn steal_decrypt_tail(&self, tail: &[u8], prev: &mut [u8]) -> Vec<u8> {
+        let block_size = self.algorithm.block_size();
+        if tail.len() == block_size {
+            return self.process_blocks(tail, prev, &mut 0, false);
+        }
 
-                    prev.clear();
-                    prev.extend_from_slice(&encrypted);
+        let partial_len = tail.len() - (tail.len() - 1) / block_size * block_size;
+        let split = tail.len() - block_size - partial_len;
+        let (head, last_two) = tail.split_at(split);
+        let (stolen, last) = if self.steal_swaps(partial_len) {
+            let (last, stolen) = last_two.split_at(block_size);
+            (stolen, last)
+        } else {
+            last_two.split_at(partial_len)
+        };
 
-                    encrypted
-                } else {
-                    let decrypted = self.algorithm.decrypt_block(&block, round_key);
+        let mut decrypted_last = last.to_vec();
+        self.algorithm.decrypt_block_inplace(&mut decrypted_last);
 
-                    let mut result = decrypted.clone();
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < result.len() {
-                            result[i] ^= p;
-                        }
-                    }
+        let mut ciphertext = Vec::with_capacity(tail.len() + block_size - partial_len);
+        ciphertext.extend_from_slice(head);
+        ciphertext.extend_from_slice(stolen);
+        ciphertext.extend_from_slice(&decrypted_last[partial_len..]);
+        ciphertext.extend_from_slice(last);
 
-                    prev.clear();
-                    prev.extend_from_slice(&block);
+        let mut output = self.process_blocks(&ciphertext, prev, &mut 0, false);
+        output.truncate(tail.len());
+        output
+    }
+
+    /// One step of a sequential chained mode. `block` is a whole block except
+    /// for the final piece of CFB/OFB; `scratch` is a block-sized work buffer.
+    fn process_block_inplace(
+        &self,
+        block: &mut [u8],
+        prev: &mut [u8],
+        scratch: &mut [u8],
+        encrypt: bool,
+    ) {
+        let alg = &self.algorithm;
 
-                    result
+        match self.mode {
+            _ if self.is_cbc() => {
+                if encrypt {
+                    xor_in_place(block, prev);
+                    alg.encrypt_block_inplace(block);
+                    prev.copy_from_slice(block);
+                } else {
+                    scratch.copy_from_slice(block);
+                    alg.decrypt_block_inplace(block);
+                    xor_in_place(block, prev);
+                    prev.copy_from_slice(scratch);
                 }
             }
             CipherMode::CFB => {
+                scratch.copy_from_slice(prev);
+                alg.encrypt_block_inplace(scratch);
+                if !encrypt {
+                    prev[..block.len()].copy_from_slice(block);
+                }
+                xor_in_place(block, scratch);
                 if encrypt {
-                    let keystream = self.algorithm.encrypt_block(prev, round_key);
-
-                    let mut result = Vec::with_capacity(block.len());
-                    for i in 0..block.len() {
-                        if i < keystream.len() {
-                            result.push(block[i] ^ keystream[i]);
-                        } else {
-                            result.push(block[i]);
-                        }
-                    }
-
-                    prev.clear();
-                    prev.extend_from_slice(&result[..block.len().min(block_size)]);
-                    if prev.len() < block_size {
-                        prev.resize(block_size, 0);
-                    }
-
-                    result
-                } else {
-                    let keystream = self.algorithm.encrypt_block(prev, round_key);
-
-                    let mut result = Vec::with_capacity(block.len());
-                    for i in 0..block.len() {
-                        if i < keystream.len() {
-                            result.push(block[i] ^ keystream[i]);
-                        } else {
-                            result.push(block[i]);
-                        }
-                    }
-
-                    prev.clear();
-                    prev.extend_from_slice(&block[..block.len().min(block_size)]);
-                    if prev.len() < block_size {
-                        prev.resize(block_size, 0);
-                    }
-
-                    result
+                    prev[..block.len()].copy_from_slice(block);
                 }
+                prev[block.len()..].fill(0);
             }
             CipherMode::OFB => {
-                let keystream = self.algorithm.encrypt_block(prev, round_key);
-
-                let mut result = Vec::with_capacity(block.len());
-                for i in 0..block.len() {
-                    if i < keystream.len() {
-                        result.push(block[i] ^ keystream[i]);
-                    } else {
-                        result.push(block[i]);
-                    }
-                }
-
-                prev.clear();
-                prev.extend_from_slice(&keystream[..block_size]);
-
-                result
+                alg.encrypt_block_inplace(prev);
+                xor_in_place(block, prev);
             }
             CipherMode::PCBC => {
-                let mut sized_block = block.clone();
-                if !is_stream_mode && sized_block.len() < block_size {
-                    sized_block.resize(block_size, 0);
-                }
-
+                // The next feedback is P xor C whichever way we go.
+                scratch.copy_from_slice(block);
                 if encrypt {
-                    let mut temp_block = sized_block.clone();
-
-                    for i in 0..block_size.min(temp_block.len()) {
-                        if i < prev.len() {
-                            temp_block[i] ^= prev[i];
-                        }
-                    }
-
-                    let actual_block_len = if is_stream_mode {
-                        block.len()
-                    } else {
-                        block_size
-                    };
-                    let block_to_encrypt = if temp_block.len() < block_size && !is_stream_mode {
-                        let mut padded = temp_block.clone();
-                        padded.resize(block_size, 0);
-                        padded
-                    } else {
-                        temp_block
-                    };
-
-                    let encrypted = self.algorithm.encrypt_block(&block_to_encrypt, round_key);
-
-                    let mut new_prev = vec![0u8; block_size];
-                    for i in 0..block_size {
-                        if i < block.len() {
-                            new_prev[i] = block[i];
-                        }
-                        if i < encrypted.len() {
-                            new_prev[i] ^= encrypted[i];
-                        }
-                    }
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && encrypted.len() > actual_block_len {
-                        encrypted[..actual_block_len].to_vec()
-                    } else {
-                        encrypted
-                    }
+                    xor_in_place(block, prev);
+                    alg.encrypt_block_inplace(block);
                 } else {
-                    let block_to_decrypt = if sized_block.len() < block_size && !is_stream_mode {
-                        let mut padded = sized_block.clone();
-                        padded.resize(block_size, 0);
-                        padded
-                    } else {
-                        sized_block
-                    };
-
-                    let decrypted = self.algorithm.decrypt_block(&block_to_decrypt, round_key);
-
-                    let mut result = decrypted.clone();
-                    for i in 0..result.len() {
-                        if i < prev.len() {
-                            result[i] ^= prev[i];
-                        }
-                    }
-
-                    let mut new_prev = vec![0u8; block_size];
-                    for i in 0..block_size {
-                        if i < result.len() {
-                            new_prev[i] = result[i];
-                        }
-                        if i < block_to_decrypt.len() {
-                            new_prev[i] ^= block_to_decrypt[i];
-                        }
-                    }
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && result.len() > block.len() {
-                        result[..block.len()].to_vec()
-                    } else {
-                        result
-                    }
+                    alg.decrypt_block_inplace(block);
+                    xor_in_place(block, prev);
                 }
+                prev.copy_from_slice(block);
+                xor_in_place(prev, scratch);
             }
             CipherMode::RandomDelta => {
-                let delta = &self.additional_params;
-
-                let block_to_process = if block.len() < block_size && !is_stream_mode {
-                    let mut sized = block.clone();
-                    sized.resize(block_size, 0);
-                    sized
-                } else {
-                    block.clone()
-                };
-
                 if encrypt {
-                    let mut temp_block = block_to_process.clone();
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < temp_block.len() {
-                            temp_block[i] ^= p;
-                        }
-                    }
-
-                    let encrypted = self.algorithm.encrypt_block(&temp_block, round_key);
-
-                    let new_prev: Vec<u8> = prev
-                        .iter()
-                        .zip(delta.iter().chain(std::iter::repeat(&0)))
-                        .map(|(&p, &d)| p ^ d)
-                        .take(block_size)
-                        .collect();
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && encrypted.len() > block.len() {
-                        encrypted[..block.len()].to_vec()
-                    } else {
-                        encrypted
-                    }
+                    xor_in_place(block, prev);
+                    alg.encrypt_block_inplace(block);
                 } else {
-                    let decrypted = self.algorithm.decrypt_block(&block_to_process, round_key);
-
-                    let mut result = decrypted.clone();
-                    for (i, &p) in prev.iter().enumerate() {
-                        if i < result.len() {
-                            result[i] ^= p;
-                        }
-                    }
-
-                    let new_prev: Vec<u8> = prev
-                        .iter()
-                        .zip(delta.iter().chain(std::iter::repeat(&0)))
-                        .map(|(&p, &d)| p ^ d)
-                        .take(block_size)
-                        .collect();
-
-                    *prev = new_prev;
-
-                    if is_stream_mode && result.len() > block.len() {
-                        result[..block.len()].to_vec()
-                    } else {
-                        result
-                    }
-                }
-            }
-
-            _ => Vec::new(),
-        };
-
-        Ok(result)
-    }
-
-    pub fn is_all_padding(data: &[u8], padding: &PaddingMode) -> bool {
-        match padding {
-            PaddingMode::PKCS7 => {
-                if let Some(&last_byte) = data.last() {
-                    let pad_len = last_byte as usize;
-
-                    if pad_len > 0
-                        && pad_len <= data.len()
-                        && data.len() % pad_len == 0
-                        && data.iter().all(|&b| b == last_byte)
-                    {
-                        return true;
-                    }
-                }
-            }
-            PaddingMode::ANSI_X923 => {
-                if let Some(&last_byte) = data.last() {
-                    let pad_len = last_byte as usize;
-                    if pad_len > 0 && pad_len <= data.len() && data.len() % pad_len == 0 {
-                        let blocks = data.len() / pad_len;
-                        for i in 0..blocks {
-                            let block_start = i * pad_len;
-                            let block_end = block_start + pad_len;
-                            if data[block_end - 1] != last_byte
-                                || !data[block_start..block_end - 1].iter().all(|&b| b == 0)
-                            {
-                                return false;
-                            }
-                        }
-                        return true;
-                    }
-                }
-            }
-            PaddingMode::ISO10126 => {
-                if let Some(&last_byte) = data.last() {
-                    let pad_len = last_byte as usize;
-                    if pad_len > 0 && pad_len <= data.len() && data.len() % pad_len == 0 {
-                        let blocks = data.len() / pad_len;
-                        for i in 0..blocks {
-                            let block_end = (i + 1) * pad_len;
-                            if data[block_end - 1] != last_byte {
-                                return false;
-                            }
-                        }
-                        return true;
-                    }
+                    alg.decrypt_block_inplace(block);
+                    xor_in_place(block, prev);
                 }
+                xor_in_place(prev, &self.additional_params);
             }
             _ => {}
         }
-        false
     }
 
-    async fn process_data(&self, data: &[u8], encrypt: bool) -> std::io::Result<Vec<u8>> {
+    async fn process_data(&self, data: &[u8], encrypt: bool) -> Result<Vec<u8>, CipherError> {
         let block_size = self.algorithm.block_size();
-        let is_stream_mode = self.is_stream_mode();
 
-        if !encrypt && self.is_empty_input_test_pattern(data) {
-            return Ok(Vec::new());
-        }
-
-        if data.is_empty() {
-            return if encrypt && !is_stream_mode {
-                let padding_block = apply_padding(Vec::new(), block_size, self.padding.clone());
-
-                match self.mode {
-                    CipherMode::ECB => Ok(self.process_ecb_parallel(&padding_block, true)),
-                    _ => {
-                        let mut prev = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-                        let processed =
-                            self.process_single_block(&padding_block, &mut prev, true, true)?;
-                        Ok(processed)
-                    }
-                }
-            } else {
-                Ok(Vec::new())
-            };
+        if self.is_authenticated_mode() {
+            return self.process_authenticated(data, encrypt);
         }
 
         match self.mode {
-            CipherMode::ECB => Ok(self.process_ecb_data(data, encrypt)),
+            CipherMode::ECB => self.process_ecb_data(data, encrypt),
             CipherMode::CTR => {
                 let default_iv = vec![0u8; block_size];
                 let iv = self.iv.as_deref().unwrap_or(&default_iv);
-                let parallel_result = self.process_ctr_parallel(data, iv);
-                Ok(parallel_result)
+                Ok(self.process_ctr_parallel(data, iv, block_size))
             }
             _ => {
-                let prepared_data = if encrypt && !is_stream_mode {
-                    apply_padding(data.to_vec(), block_size, self.padding.clone())
-                } else {
-                    data.to_vec()
-                };
+                let mut state = self.start_stream();
+                let mut output = self.stream_update(&mut state, data, encrypt);
+                output.extend(self.stream_finish(state, encrypt)?);
+                Ok(output)
+            }
+        }
+    }
 
-                let result = if prepared_data.len() > OPTIMAL_PARALLELISM_THRESHOLD {
-                    let mut output = Vec::with_capacity(prepared_data.len());
-                    {
-                        let cursor = std::io::Cursor::new(&prepared_data);
-                        let mut writer = VecWriter(&mut output);
-                        self.process_chunked_parallel(cursor, &mut writer, encrypt)?;
-                    }
-                    output
-                } else {
-                    let mut prev = self.iv.clone().unwrap_or_else(|| vec![0u8; block_size]);
-                    let mut result = Vec::with_capacity(prepared_data.len());
+    fn nonce(&self) -> Result<&[u8], CipherError> {
+        self.check_ready()?;
+        Ok(self.iv.as_deref().unwrap_or_default())
+    }
+
+    fn encrypt_single(&self, block: &[u8]) -> Vec<u8> {
+        let mut out = block.to_vec();
+        self.algorithm.encrypt_block_inplace(&mut out);
+        out
+    }
+
+    /// Returns the hash subkey H and the pre-counter block J0.
+    fn gcm_setup(&self) -> Result<(u128, Vec<u8>), CipherError> {
+        if self.algorithm.block_size() != 16 {
+            return Err(CipherError::InvalidParameter(
+                "GCM requires a cipher with a 128-bit block",
+            ));
+        }
+        let nonce = self.nonce()?;
 
-                    let blocks: Vec<_> = if is_stream_mode {
-                        prepared_data.chunks(block_size).collect()
-                    } else {
-                        prepared_data.chunks(block_size).collect()
-                    };
+        let h_block = self.encrypt_single(&[0u8; 16]);
+        let h = u128::from_be_bytes(h_block[..16].try_into().unwrap());
 
-                    let block_count = blocks.len();
+        let j0 = if nonce.len() == GCM_NONCE_SIZE {
+            let mut j0 = nonce.to_vec();
+            j0.extend_from_slice(&[0, 0, 0, 1]);
+            j0
+        } else {
+            ghash(h, &[], nonce).to_be_bytes().to_vec()
+        };
 
-                    for (idx, chunk) in blocks.into_iter().enumerate() {
-                        let is_last = idx == block_count - 1;
-                        let needs_padding = is_last && encrypt && !is_stream_mode;
+        Ok((h, j0))
+    }
 
-                        let processed =
-                            self.process_single_block(chunk, &mut prev, encrypt, needs_padding)?;
+    fn gcm_tag(&self, h: u128, j0: &[u8], associated_data: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let s = ghash(h, associated_data, ciphertext).to_be_bytes();
+        self.encrypt_single(j0)
+            .iter()
+            .zip(s.iter())
+            .map(|(a, b)| a ^ b)
+            .collect()
+    }
 
-                        result.extend_from_slice(&processed);
-                    }
+    fn gcm_keystream(&self, j0: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut counter = j0.to_vec();
+        Self::increment_block(&mut counter[16 - GCM_COUNTER_SIZE..], 1);
+        self.process_ctr_parallel(data, &counter, GCM_COUNTER_SIZE)
+    }
 
-                    if !encrypt && self.is_empty_input_test_pattern(&result) {
-                        return Ok(Vec::new());
-                    }
+    fn eax_omac(&self, tweak: u8, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        omac(&*self.algorithm, tweak, data)
+    }
 
-                    if !encrypt && !is_stream_mode && !result.is_empty() {
-                        if Self::is_all_padding(&result, &self.padding) {
-                            return Ok(Vec::new());
-                        }
+    fn eax_tag(&self, nonce_mac: &[u8], associated_data: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let header_mac = self.eax_omac(1, associated_data)?;
+        let ciphertext_mac = self.eax_omac(2, ciphertext)?;
+        Ok(nonce_mac
+            .iter()
+            .zip(header_mac.iter())
+            .zip(ciphertext_mac.iter())
+            .map(|((n, h), c)| n ^ h ^ c)
+            .collect())
+    }
 
-                        let last_block_start = if result.len() <= block_size {
-                            0
-                        } else {
-                            result.len() - block_size
-                        };
-
-                        let mut final_result = Vec::with_capacity(result.len());
-
-                        if last_block_start > 0 {
-                            final_result.extend_from_slice(&result[..last_block_start]);
-                        }
-
-                        let last_block = match self.padding {
-                            PaddingMode::Zeros => {
-                                if result.len() == block_size
-                                    && (data.len() == 1 || (data.len() > 1 && data[0] == 0))
-                                {
-                                    vec![0]
-                                } else {
-                                    let mut block = result[last_block_start..].to_vec();
-                                    while block.len() > 0 && block[block.len() - 1] == 0 {
-                                        block.pop();
-                                    }
-
-                                    if block.is_empty() && data.len() > 0 {
-                                        vec![0]
-                                    } else {
-                                        block
-                                    }
-                                }
-                            }
-                            _ => remove_padding(
-                                result[last_block_start..].to_vec(),
-                                self.padding.clone(),
-                            ),
-                        };
-
-                        final_result.extend_from_slice(&last_block);
-
-                        final_result
-                    } else {
-                        result
-                    }
-                };
+    /// Sector number given as IV, 0 if there is none.
+    fn first_sector(&self) -> Result<u128, CipherError> {
+        match self.iv.as_deref() {
+            None => Ok(0),
+            Some(iv) => iv.try_into().map(u128::from_le_bytes).map_err(|_| CipherError::InvalidIvLength {
+                expected: XTS_BLOCK_SIZE,
+                actual: iv.len(),
+            }),
+        }
+    }
+
+    /// The data and tweak ciphers, once the context is known to be a valid XTS one.
+    fn xts_ciphers(
+        &self,
+    ) -> Result<(&(dyn SymmetricCipherWithRounds + Sync), &(dyn SymmetricCipherWithRounds + Sync)), CipherError> {
+        if !matches!(self.mode, CipherMode::XTS) {
+            return Err(CipherError::InvalidParameter("sector API requires XTS mode"));
+        }
+        self.check_ready()?;
+        Ok((&*self.algorithm, self.tweak_algorithm.as_deref().unwrap()))
+    }
+
+    /// Sectors of the message starting `sector_offset` sectors after the IV's.
+    fn process_xts(&self, data: &[u8], sector_offset: usize, encrypt: bool) -> Result<Vec<u8>, CipherError> {
+        let (data_cipher, tweak_cipher) = self.xts_ciphers()?;
+        let first_sector = self.first_sector()? + sector_offset as u128;
+        process_sectors(data_cipher, tweak_cipher, first_sector, self.sector_size, data, encrypt)
+    }
 
-                Ok(result)
+    /// Encrypts one sector independently of the others (random access). `data`
+    /// is usually `sector_size` bytes, but any length from one block up works.
+    pub fn encrypt_sector(&self, sector_index: u128, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let (data_cipher, tweak_cipher) = self.xts_ciphers()?;
+        process_sector(data_cipher, tweak_cipher, sector_index, data, true)
+    }
+
+    pub fn decrypt_sector(&self, sector_index: u128, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let (data_cipher, tweak_cipher) = self.xts_ciphers()?;
+        process_sector(data_cipher, tweak_cipher, sector_index, data, false)
+    }
+
+    /// Encrypts consecutive sectors `first_sector..` in parallel; only the
+    /// last one may be shorter than `sector_size`.
+    pub fn encrypt_sectors(&self, first_sector: u128, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let (data_cipher, tweak_cipher) = self.xts_ciphers()?;
+        process_sectors(data_cipher, tweak_cipher, first_sector, self.sector_size, data, true)
+    }
+
+    pub fn decrypt_sectors(&self, first_sector: u128, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let (data_cipher, tweak_cipher) = self.xts_ciphers()?;
+        process_sectors(data_cipher, tweak_cipher, first_sector, self.sector_size, data, false)
+    }
+
+    /// Encrypts `data` in GCM or EAX mode and returns `(ciphertext, tag)`.
+    /// The tag is one cipher block long.
+    pub fn encrypt_authenticated(
+        &self,
+        data: &[u8],
+        associated_data: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), CipherError> {
+        match self.mode {
+            CipherMode::GCM => {
+                let (h, j0) = self.gcm_setup()?;
+                let ciphertext = self.gcm_keystream(&j0, data);
+                let tag = self.gcm_tag(h, &j0, associated_data, &ciphertext);
+                Ok((ciphertext, tag))
+            }
+            CipherMode::EAX => {
+                let nonce_mac = self.eax_omac(0, self.nonce()?)?;
+                let block_size = self.algorithm.block_size();
+                let ciphertext = self.process_ctr_parallel(data, &nonce_mac, block_size);
+                let tag = self.eax_tag(&nonce_mac, associated_data, &ciphertext)?;
+                Ok((ciphertext, tag))
+            }
+            _ => Err(CipherError::InvalidParameter(
+                "authenticated encryption requires GCM or EAX mode",
+            )),
+        }
+    }
+
+    /// Verifies `tag` over `associated_data` and `data`, then decrypts.
+    /// A mismatch yields [`CipherError::AuthenticationFailed`] and no plaintext.
+    pub fn decrypt_authenticated(
+        &self,
+        data: &[u8],
+        associated_data: &[u8],
+        tag: &[u8],
+    ) -> Result<Vec<u8>, CipherError> {
+        match self.mode {
+            CipherMode::GCM => {
+                let (h, j0) = self.gcm_setup()?;
+                let expected = self.gcm_tag(h, &j0, associated_data, data);
+                if !constant_time_eq(&expected, tag) {
+                    return Err(CipherError::AuthenticationFailed);
+                }
+                Ok(self.gcm_keystream(&j0, data))
             }
+            CipherMode::EAX => {
+                let nonce_mac = self.eax_omac(0, self.nonce()?)?;
+                let expected = self.eax_tag(&nonce_mac, associated_data, data)?;
+                if !constant_time_eq(&expected, tag) {
+                    return Err(CipherError::AuthenticationFailed);
+                }
+                let block_size = self.algorithm.block_size();
+                Ok(self.process_ctr_parallel(data, &nonce_mac, block_size))
+            }
+            _ => Err(CipherError::InvalidParameter(
+                "authenticated decryption requires GCM or EAX mode",
+            )),
+        }
+    }
+
+    /// `encrypt`/`decrypt` framing for GCM/EAX: the tag is appended to the ciphertext.
+    fn process_authenticated(&self, data: &[u8], encrypt: bool) -> Result<Vec<u8>, CipherError> {
+        if encrypt {
+            let (mut ciphertext, tag) = self.encrypt_authenticated(data, &self.associated_data)?;
+            ciphertext.extend_from_slice(&tag);
+            return Ok(ciphertext);
         }
+
+        let tag_size = self.algorithm.block_size();
+        if data.len() < tag_size {
+            return Err(CipherError::TruncatedCiphertext);
+        }
+        let (ciphertext, tag) = data.split_at(data.len() - tag_size);
+        self.decrypt_authenticated(ciphertext, &self.associated_data, tag)
+    }
+
+    /// Starts an incremental encryption of one message, for data that arrives
+    /// in pieces (file chunks, network frames). The IV is used once, at the
+    /// start of the message; chaining continues across `update` calls.
+    pub fn encryptor(&self) -> Result<CipherStream, CipherError> {
+        CipherStream::new(self, true)
     }
 
-    fn run_file_task<F, T>(task: F) -> std::io::Result<T>
-    where
-        F: FnOnce() -> std::io::Result<T> + Send + 'static,
-        T: Send + 'static,
-    {
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(tokio::task::spawn_blocking(task))
-        })
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+    /// Decrypting counterpart of [`CipherContext::encryptor`].
+    pub fn decryptor(&self) -> Result<CipherStream, CipherError> {
+        CipherStream::new(self, false)
+    }
+
+    fn stream(&self, encrypt: bool) -> Result<CipherStream, CipherError> {
+        CipherStream::new(self, encrypt)
     }
 
     pub async fn encrypt(
         &self,
         input: CipherInput,
         output: &mut CipherOutput,
-    ) -> std::io::Result<()> {
-        match (input, output) {
-            (CipherInput::Bytes(data), out) => {
-                let encrypted = self.process_data(&data, true).await?;
-                write_all(out, &encrypted)
-            }
-            (CipherInput::File(input_path), CipherOutput::File(output_path)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let output_path = output_path.clone();
-                Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let writer = BufWriter::new(File::create(output_path)?);
-                    this.process_chunked_parallel(reader, writer, true)
-                })
-            }
-            (CipherInput::File(input_path), CipherOutput::Buffer(buf)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let result = Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let mut result = Vec::new();
-                    {
-                        let mut writer = VecWriter(&mut result);
-                        this.process_chunked_parallel(reader, &mut writer, true)?;
-                    }
-                    Ok(result)
-                })?;
-                **buf = result;
-                Ok(())
-            }
-        }
+    ) -> Result<(), CipherError> {
+        self.check_ready()?;
+        self.run(input, output, true).await
     }
 
     pub async fn decrypt(
         &self,
         input: CipherInput,
         output: &mut CipherOutput,
-    ) -> std::io::Result<()> {
+    ) -> Result<(), CipherError> {
+        self.check_ready()?;
+        self.run(input, output, false).await
+    }
+
+    async fn run(
+        &self,
+        input: CipherInput,
+        output: &mut CipherOutput,
+        encrypt: bool,
+    ) -> Result<(), CipherError> {
         match (input, output) {
             (CipherInput::Bytes(data), out) => {
-                if self.is_empty_input_test_pattern(&data) {
-                    return write_all(out, &Vec::new());
-                }
-
-                if matches!(self.mode, CipherMode::CBC)
-                    && matches!(self.padding, PaddingMode::Zeros)
-                    && data.len() == self.algorithm.block_size()
-                {
-                    let mut decrypted = self.process_data(&data, false).await?;
-                    if decrypted.is_empty() && data.len() > 0 {
-                        decrypted = vec![0];
-                    }
-                    return write_all(out, &decrypted);
-                }
-
-                let decrypted = self.process_data(&data, false).await?;
-                write_all(out, &decrypted)
+                let processed = self.process_data(&data, encrypt).await?;
+                Ok(write_all(out, &processed)?)
             }
             (CipherInput::File(input_path), CipherOutput::File(output_path)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let output_path = output_path.clone();
-                Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let writer = BufWriter::new(File::create(output_path)?);
-                    this.process_chunked_parallel(reader, writer, false)
-                })
+                let reader = tokio::fs::File::open(input_path).await?;
+                let writer = tokio::fs::File::create(output_path.as_str()).await?;
+                self.stream(encrypt)?.copy_async(reader, writer).await
             }
             (CipherInput::File(input_path), CipherOutput::Buffer(buf)) => {
-                let this = self.clone();
-                let input_path = input_path.clone();
-                let result = Self::run_file_task(move || {
-                    let reader = BufReader::new(File::open(input_path)?);
-                    let mut result = Vec::new();
-                    {
-                        let mut writer = VecWriter(&mut result);
-                        this.process_chunked_parallel(reader, &mut writer, false)?;
-                    }
-                    Ok(result)
-                })?;
+                let reader = tokio::fs::File::open(input_path).await?;
+                let mut result = Vec::new();
+                self.stream(encrypt)?.copy_async(reader, &mut result).await?;
                 **buf = result;
                 Ok(())
             }
         }
     }
 }
+
+/// XORs `src` into the front of `dst`; extra bytes of either side are ignored.
+#[inline]
+fn xor_in_place(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
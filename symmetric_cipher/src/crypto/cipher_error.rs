@@ -0,0 +1,79 @@
+use std::fmt;
+use std::io;
+
+/// Error shared by the block ciphers and `CipherContext`.
+#[derive(Debug)]
+pub enum CipherError {
+    /// `expected` lists the accepted key lengths in bytes.
+    InvalidKeyLength {
+        expected: &'static [usize],
+        actual: usize,
+    },
+    InvalidIvLength {
+        expected: usize,
+        actual: usize,
+    },
+    /// The last decrypted block does not end in well-formed padding.
+    InvalidPadding,
+    /// Ciphertext is not a whole number of blocks, or is shorter than the tag.
+    TruncatedCiphertext,
+    /// GCM/EAX tag mismatch; no plaintext is released.
+    AuthenticationFailed,
+    /// Anything else the caller got wrong: unsupported mode/cipher combination,
+    /// out-of-range key exchange parameters, etc.
+    InvalidParameter(&'static str),
+    /// The input is not a well-formed container: bad magic, unsupported
+    /// version, unknown identifiers or a header checksum mismatch.
+    InvalidContainer(&'static str),
+    Io(io::Error),
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherError::InvalidKeyLength { expected, actual } => {
+                write!(f, "invalid key length: {} bytes (expected {:?})", actual, expected)
+            }
+            CipherError::InvalidIvLength { expected, actual } => {
+                write!(f, "invalid IV length: {} bytes (expected {})", actual, expected)
+            }
+            CipherError::InvalidPadding => write!(f, "invalid padding"),
+            CipherError::TruncatedCiphertext => write!(f, "truncated ciphertext"),
+            CipherError::AuthenticationFailed => write!(f, "authentication tag mismatch"),
+            CipherError::InvalidParameter(msg) => write!(f, "invalid parameter: {}", msg),
+            CipherError::InvalidContainer(msg) => write!(f, "invalid container: {}", msg),
+            CipherError::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CipherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CipherError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CipherError {
+    fn from(err: io::Error) -> Self {
+        CipherError::Io(err)
+    }
+}
+
+/// Lets `?` work in code that still returns `io::Result`.
+impl From<CipherError> for io::Error {
+    fn from(err: CipherError) -> Self {
+        match err {
+            CipherError::Io(err) => err,
+            CipherError::TruncatedCiphertext => io::Error::new(io::ErrorKind::UnexpectedEof, err),
+            CipherError::InvalidPadding
+            | CipherError::AuthenticationFailed
+            | CipherError::InvalidContainer(_) => {
+                io::Error::new(io::ErrorKind::InvalidData, err)
+            }
+            _ => io::Error::new(io::ErrorKind::InvalidInput, err),
+        }
+    }
+}
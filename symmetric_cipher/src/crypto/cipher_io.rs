@@ -1,18 +1,6 @@
-use crate::crypto::cipher_types::{CipherInput, CipherOutput};
+use crate::crypto::cipher_types::CipherOutput;
 use std::fs::File;
-use std::io::{self, BufReader, BufWriter, Read, Write};
-
-pub fn read_all(input: &CipherInput) -> io::Result<Vec<u8>> {
-    match input {
-        CipherInput::Bytes(data) => Ok(data.clone()),
-        CipherInput::File(path) => {
-            let mut reader = BufReader::new(File::open(path)?);
-            let mut buffer = Vec::new();
-            reader.read_to_end(&mut buffer)?;
-            Ok(buffer)
-        }
-    }
-}
+use std::io::{self, BufWriter, Write};
 
 pub fn write_all(output: &mut CipherOutput, data: &[u8]) -> io::Result<()> {
     match output {
@@ -28,41 +16,3 @@ pub fn write_all(output: &mut CipherOutput, data: &[u8]) -> io::Result<()> {
         }
     }
 }
-
-/// Поблочное чтение из файла с обработкой блока
-pub fn read_blockwise_with_end<F>(path: &str, block_size: usize, mut handle_block: F) -> io::Result<()>
-where
-    F: FnMut(&[u8], bool) -> io::Result<()>,
-{
-    let mut reader = BufReader::new(File::open(path)?);
-    let mut prev_block = Vec::new();
-    let mut buffer = vec![0u8; block_size];
-
-    loop {
-        let read = reader.read(&mut buffer)?;
-        if read == 0 {
-            if !prev_block.is_empty() {
-                handle_block(&prev_block, true)?;
-            }
-            break;
-        }
-
-        if !prev_block.is_empty() {
-            handle_block(&prev_block, false)?;
-        }
-
-        prev_block = buffer[..read].to_vec();
-    }
-
-    Ok(())
-}
-
-
-/// Поблочная запись в файл
-pub fn write_blockwise(path: &str, blocks: &[Vec<u8>]) -> io::Result<()> {
-    let mut writer = BufWriter::new(File::create(path)?);
-    for block in blocks {
-        writer.write_all(block)?;
-    }
-    Ok(())
-}
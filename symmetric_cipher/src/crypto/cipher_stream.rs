@@ -0,0 +1,334 @@
+use crate::crypto::cipher_context::{CHUNK_SIZE, CipherContext, StreamState};
+use crate::crypto::cipher_error::CipherError;
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+/// Default cap on the input a GCM/EAX stream buffers before `finalize`.
+pub const MAX_AUTHENTICATED_BUFFER: usize = 64 * 1024 * 1024;
+
+const AUTHENTICATED_BUFFER_EXCEEDED: CipherError =
+    CipherError::InvalidParameter("GCM/EAX stream exceeds its buffer limit");
+
+/// Encrypts or decrypts one message fed in pieces, carrying the chaining state
+/// of the context's mode (IV / feedback register, CTR counter) from one piece
+/// to the next. Obtained from `CipherContext::encryptor`/`decryptor`; feed it
+/// with `update` and end the message with `finalize`, let `copy` /
+/// `copy_async` drive it from a reader, or wrap a reader or writer with
+/// [`reader`](Self::reader), [`writer`](Self::writer) and their async
+/// counterparts. Padding is applied or stripped only in `finalize`.
+///
+/// GCM/EAX are the exception to bounded memory: the tag covers the whole
+/// message, so the input is buffered until EOF and nothing is written before
+/// the tag is checked. The buffer is capped at [`MAX_AUTHENTICATED_BUFFER`]
+/// (see [`set_buffer_limit`](Self::set_buffer_limit)); a longer message fails
+/// with [`CipherError::InvalidParameter`]. Use the container format or the
+/// non-authenticated modes for larger payloads.
+pub struct CipherStream {
+    context: CipherContext,
+    encrypt: bool,
+    state: StreamState,
+    buffer_limit: usize,
+    /// Set when `update` had to drop input over the limit; `finalize` fails.
+    overflowed: bool,
+}
+
+impl CipherStream {
+    pub(crate) fn new(context: &CipherContext, encrypt: bool) -> Result<Self, CipherError> {
+        context.check_ready()?;
+
+        Ok(Self {
+            context: context.clone(),
+            encrypt,
+            state: context.start_stream(),
+            buffer_limit: MAX_AUTHENTICATED_BUFFER,
+            overflowed: false,
+        })
+    }
+
+    /// Changes how much input a GCM/EAX stream may buffer. Other modes keep
+    /// at most a few blocks (one sector for XTS) and ignore it.
+    pub fn set_buffer_limit(&mut self, bytes: usize) {
+        self.buffer_limit = bytes;
+    }
+
+    /// Feeds the next piece of the message and returns the output that is
+    /// ready. Input that does not fill a whole block is kept for the next call;
+    /// when decrypting a padded mode the last block is held back until
+    /// `finalize`. GCM/EAX input over the buffer limit is dropped and
+    /// reported by `finalize`; [`try_update`](Self::try_update) reports it at once.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.try_update(data).unwrap_or_else(|_| {
+            self.overflowed = true;
+            Vec::new()
+        })
+    }
+
+    /// Like [`update`](Self::update), but fails as soon as a GCM/EAX message
+    /// outgrows the buffer limit.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        if self.overflowed
+            || (self.context.is_authenticated_mode() && self.state.buffered() + data.len() > self.buffer_limit)
+        {
+            return Err(AUTHENTICATED_BUFFER_EXCEEDED);
+        }
+        Ok(self.context.stream_update(&mut self.state, data, self.encrypt))
+    }
+
+    /// Ends the message and returns the remaining output: the padded last
+    /// block, the unpadded plaintext tail, or the GCM/EAX result.
+    pub fn finalize(self) -> Result<Vec<u8>, CipherError> {
+        if self.overflowed {
+            return Err(AUTHENTICATED_BUFFER_EXCEEDED);
+        }
+        self.context.stream_finish(self.state, self.encrypt)
+    }
+
+    /// Pipes `reader` through the cipher into `writer` until EOF.
+    pub fn copy<R: Read, W: Write>(mut self, mut reader: R, mut writer: W) -> Result<(), CipherError> {
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+
+        loop {
+            let n = reader.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            writer.write_all(&self.try_update(&buffer[..n])?)?;
+        }
+
+        writer.write_all(&self.finalize()?)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Async counterpart of [`CipherStream::copy`] for sockets, stdin, gRPC
+    /// bodies and the like. The writer is flushed but not shut down.
+    pub async fn copy_async<R, W>(mut self, mut reader: R, mut writer: W) -> Result<(), CipherError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+
+        loop {
+            let n = reader.read(&mut buffer).await?;
+            if n == 0 {
+                break;
+            }
+            writer.write_all(&self.try_update(&buffer[..n])?).await?;
+        }
+
+        writer.write_all(&self.finalize()?).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    /// A `Read` that yields the output for everything read from `inner`.
+    pub fn reader<R: Read>(self, inner: R) -> CipherReader<R> {
+        CipherReader { inner, output: Output::new(self) }
+    }
+
+    /// A `Write` that processes what is written and passes the output on to
+    /// `inner`. Call [`CipherWriter::finish`] to write the last block.
+    pub fn writer<W: Write>(self, inner: W) -> CipherWriter<W> {
+        CipherWriter { inner, stream: self }
+    }
+
+    /// Async counterpart of [`reader`](Self::reader).
+    pub fn async_reader<R: AsyncRead + Unpin>(self, inner: R) -> AsyncCipherReader<R> {
+        AsyncCipherReader { inner, output: Output::new(self) }
+    }
+
+    /// Async counterpart of [`writer`](Self::writer); `shutdown` ends the
+    /// message and shuts `inner` down.
+    pub fn async_writer<W: AsyncWrite + Unpin>(self, inner: W) -> AsyncCipherWriter<W> {
+        AsyncCipherWriter { inner, stream: Some(self), pending: Vec::new(), written: 0 }
+    }
+}
+
+/// Processed bytes not yet handed to the reader, and the stream that makes
+/// more; `None` once the message is finalized.
+struct Output {
+    stream: Option<CipherStream>,
+    input: Vec<u8>,
+    ready: Vec<u8>,
+    pos: usize,
+}
+
+impl Output {
+    fn new(stream: CipherStream) -> Self {
+        Output { stream: Some(stream), input: Vec::new(), ready: Vec::new(), pos: 0 }
+    }
+
+    /// Copies ready output into `buf`; 0 if there is none.
+    fn take(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.ready.len() - self.pos);
+        buf[..n].copy_from_slice(&self.ready[self.pos..self.pos + n]);
+        self.pos += n;
+        n
+    }
+
+    fn has_ready(&self) -> bool {
+        self.pos < self.ready.len()
+    }
+
+    /// Buffer for the next read from the inner reader.
+    fn input_buffer(&mut self) -> &mut [u8] {
+        self.input.resize(CHUNK_SIZE, 0);
+        &mut self.input
+    }
+
+    /// Processes the `n` bytes just read into `input`; `n == 0` ends the message.
+    fn feed(&mut self, n: usize) -> io::Result<()> {
+        let Some(stream) = self.stream.as_mut() else {
+            return Ok(());
+        };
+        self.ready = if n == 0 {
+            self.stream.take().unwrap().finalize()?
+        } else {
+            stream.try_update(&self.input[..n])?
+        };
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+/// Reading side of a [`CipherStream`], from [`CipherStream::reader`].
+pub struct CipherReader<R> {
+    inner: R,
+    output: Output,
+}
+
+impl<R> CipherReader<R> {
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for CipherReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while !self.output.has_ready() && self.output.stream.is_some() && !buf.is_empty() {
+            let n = self.inner.read(self.output.input_buffer())?;
+            self.output.feed(n)?;
+        }
+        Ok(self.output.take(buf))
+    }
+}
+
+/// Writing side of a [`CipherStream`], from [`CipherStream::writer`].
+/// Dropping it without [`finish`](Self::finish) loses the last block.
+pub struct CipherWriter<W> {
+    inner: W,
+    stream: CipherStream,
+}
+
+impl<W: Write> CipherWriter<W> {
+    /// Ends the message, writes the rest of the output and returns `inner`.
+    pub fn finish(mut self) -> Result<W, CipherError> {
+        self.inner.write_all(&self.stream.finalize()?)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for CipherWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let output = self.stream.try_update(buf)?;
+        self.inner.write_all(&output)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Async reading side of a [`CipherStream`], from [`CipherStream::async_reader`].
+pub struct AsyncCipherReader<R> {
+    inner: R,
+    output: Output,
+}
+
+impl<R> AsyncCipherReader<R> {
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncCipherReader<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        while !this.output.has_ready() && this.output.stream.is_some() && buf.remaining() > 0 {
+            let mut input = ReadBuf::new(this.output.input_buffer());
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut input))?;
+            let n = input.filled().len();
+            this.output.feed(n)?;
+        }
+        let n = this.output.take(buf.initialize_unfilled());
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Async writing side of a [`CipherStream`], from [`CipherStream::async_writer`].
+/// Output is written to `inner` lazily: on the next write, `flush` or
+/// `shutdown`. Without `shutdown` the last block is never written.
+pub struct AsyncCipherWriter<W> {
+    inner: W,
+    /// `None` after `shutdown` has finalized the message.
+    stream: Option<CipherStream>,
+    pending: Vec<u8>,
+    written: usize,
+}
+
+impl<W> AsyncCipherWriter<W> {
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncCipherWriter<W> {
+    /// Writes out the output produced so far.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.written < self.pending.len() {
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.pending[self.written..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.written += n;
+        }
+        self.pending.clear();
+        self.written = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncCipherWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+        let stream = this
+            .stream
+            .as_mut()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "cipher stream is already finalized"))?;
+        this.pending = stream.try_update(buf)?;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+        if let Some(stream) = this.stream.take() {
+            this.pending = stream.finalize()?;
+            ready!(this.poll_drain(cx))?;
+        }
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
@@ -1,10 +1,27 @@
+use crate::crypto::cipher_error::CipherError;
+
 pub trait CipherAlgorithm {
     fn encrypt(&self, data: &[u8]) -> Vec<u8>;
     fn decrypt(&self, data: &[u8]) -> Vec<u8>;
 }
 
 pub trait SymmetricCipher: CipherAlgorithm {
-    fn set_key(&mut self, _: &[u8]) -> Result<(), &'static str>;
+    fn set_key(&mut self, _: &[u8]) -> Result<(), CipherError>;
+
+    /// Key lengths in bytes accepted by `set_key`; empty if unknown.
+    fn key_sizes(&self) -> &'static [usize] {
+        &[]
+    }
+
+    /// Fixes up a key derived from a passphrase so that `set_key` accepts it,
+    /// e.g. sets DES parity bits. The default leaves it unchanged.
+    fn prepare_derived_key(&self, _key: &mut [u8]) {}
+
+    /// Fails if the cipher cannot encrypt yet, e.g. because `set_key` has not
+    /// been called. Ciphers that are always keyed keep the default.
+    fn check_key(&self) -> Result<(), CipherError> {
+        Ok(())
+    }
 }
 
 pub trait SymmetricCipherWithRounds: SymmetricCipher {
@@ -13,4 +30,37 @@ pub trait SymmetricCipherWithRounds: SymmetricCipher {
     fn decrypt_block(&self, data: &[u8], round_key: &[u8]) -> Vec<u8>;
     fn block_size(&self) -> usize;
     fn export_round_keys(&self) -> Option<Vec<u8>>;
+
+    /// Encrypts exactly one block in place with the cipher's own key schedule.
+    /// The default goes through `encrypt_block` with the exported round keys
+    /// (empty if the cipher exports none) and allocates; the ciphers in this
+    /// workspace override it with stack-only implementations.
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        let round_keys = self.export_round_keys().unwrap_or_default();
+        let out = self.encrypt_block(block, &round_keys);
+        block.copy_from_slice(&out);
+    }
+
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        let round_keys = self.export_round_keys().unwrap_or_default();
+        let out = self.decrypt_block(block, &round_keys);
+        block.copy_from_slice(&out);
+    }
+
+    /// `blocks` must be a whole number of blocks.
+    fn encrypt_blocks(&self, blocks: &mut [u8]) {
+        let block_size = self.block_size();
+        debug_assert!(blocks.len().is_multiple_of(block_size));
+        for block in blocks.chunks_exact_mut(block_size) {
+            self.encrypt_block_inplace(block);
+        }
+    }
+
+    fn decrypt_blocks(&self, blocks: &mut [u8]) {
+        let block_size = self.block_size();
+        debug_assert!(blocks.len().is_multiple_of(block_size));
+        for block in blocks.chunks_exact_mut(block_size) {
+            self.decrypt_block_inplace(block);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CipherMode {
     ECB,
     CBC,
@@ -7,14 +7,33 @@ pub enum CipherMode {
     OFB,
     CTR,
     RandomDelta,
+    GCM,
+    EAX,
+    /// Length-preserving sector encryption; set up with `CipherContext::new_xts`.
+    XTS,
+    /// CBC with ciphertext stealing (NIST SP 800-38A Addendum): the ciphertext
+    /// is as long as the plaintext, which must be at least one block. The
+    /// padding mode is ignored. The variants differ only in the order of the
+    /// last two blocks: CS1 never swaps, CS2 swaps when the last block is
+    /// partial, CS3 (Kerberos) always swaps.
+    #[allow(non_camel_case_types)]
+    CBC_CS1,
+    #[allow(non_camel_case_types)]
+    CBC_CS2,
+    #[allow(non_camel_case_types)]
+    CBC_CS3,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaddingMode {
     Zeros,
+    #[allow(non_camel_case_types)]
     ANSI_X923,
     PKCS7,
     ISO10126,
+    /// No padding at all: the plaintext must already be whole blocks, as in
+    /// NIST test vectors. Stream and ciphertext-stealing modes ignore it.
+    None,
 }
 
 pub enum CipherInput {
@@ -30,7 +49,7 @@ pub enum CipherOutput {
 impl CipherOutput {
     pub fn as_buffer(&self) -> &Vec<u8> {
         match self {
-            CipherOutput::Buffer(buf) => &**buf,
+            CipherOutput::Buffer(buf) => buf,
             _ => panic!("Expected buffer"),
         }
     }
@@ -0,0 +1,624 @@
+use crate::crypto::cipher_context::CipherContext;
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_io::write_all;
+use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
+use crate::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use crate::crypto::deal::DEAL;
+use crate::crypto::des::DES;
+use crate::crypto::des_key_expansion::DesKeyExpansion;
+use crate::crypto::des_transformation::DesTransformation;
+use crate::crypto::triple_des::TripleDes;
+pub use crate::crypto::kdf::KdfParams;
+use crate::crypto::kdf::key_len_for;
+use crate::crypto::mac::HmacSha256;
+use crate::crypto::sha256::DIGEST_SIZE;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+/// Container layout, all integers little-endian:
+///
+/// ```text
+/// magic "SCNT" | version u8 | body length u16 | body | CRC-32 of everything before it
+/// ```
+///
+/// The body holds the algorithm id and its parameters, mode, padding, IV,
+/// sector size (XTS only), KDF parameters and the length of the trailing
+/// authentication tag. In GCM/EAX the ciphertext follows the header directly
+/// and the tag covers the header as associated data; in the other modes the
+/// header is followed by an HMAC-SHA256 of it, keyed from the cipher key, so
+/// that edits to the mode, padding, IV or KDF parameters are detected.
+pub const MAGIC: [u8; 4] = *b"SCNT";
+pub const VERSION: u8 = 2;
+
+const PREFIX_SIZE: usize = MAGIC.len() + 1 + 2;
+const CHECKSUM_SIZE: usize = 4;
+pub const HEADER_MAC_SIZE: usize = DIGEST_SIZE;
+
+/// Upper bounds on the KDF parameters a header may ask for, so that a crafted
+/// file cannot make the reader run PBKDF2 or scrypt without limit.
+pub const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+/// scrypt needs 128 · r · (2^log_n + p) bytes.
+pub const MAX_SCRYPT_MEMORY: u64 = 2 << 30;
+pub const MAX_SCRYPT_P: u32 = 16;
+
+/// A block cipher instance for the algorithm named in a header, with no key
+/// set yet. `None` means the algorithm is not available to the caller.
+pub type AlgorithmResolver =
+    dyn Fn(AlgorithmId) -> Option<Box<dyn SymmetricCipherWithRounds + Send + Sync>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgorithmId {
+    Des,
+    Deal,
+    /// `block_size` in bytes (16, 20, 24, 28 or 32); `poly` is the GF(2^8)
+    /// modulus, 0x11B for AES.
+    Rijndael { block_size: u8, poly: u16 },
+    Twofish,
+    Loki97,
+    TripleDes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerHeader {
+    pub algorithm: AlgorithmId,
+    pub mode: CipherMode,
+    pub padding: PaddingMode,
+    pub iv: Option<Vec<u8>>,
+    /// Used by XTS only.
+    pub sector_size: u32,
+    pub kdf: Option<KdfParams>,
+    /// Length of the tag at the end of the ciphertext (GCM/EAX), 0 if none.
+    /// Filled in by [`ContainerWriter::new`].
+    pub tag_len: u8,
+}
+
+/// The ciphers this crate implements itself.
+pub fn builtin_algorithm(id: AlgorithmId) -> Option<Box<dyn SymmetricCipherWithRounds + Send + Sync>> {
+    let des = || DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+    match id {
+        AlgorithmId::Des => Some(Box::new(des())),
+        AlgorithmId::Deal => Some(Box::new(DEAL::new())),
+        AlgorithmId::TripleDes => Some(Box::new(TripleDes::new(
+            Arc::new(DesKeyExpansion),
+            Arc::new(DesTransformation),
+        ))),
+        _ => None,
+    }
+}
+
+fn mode_id(mode: CipherMode) -> u8 {
+    match mode {
+        CipherMode::ECB => 0,
+        CipherMode::CBC => 1,
+        CipherMode::PCBC => 2,
+        CipherMode::CFB => 3,
+        CipherMode::OFB => 4,
+        CipherMode::CTR => 5,
+        CipherMode::RandomDelta => 6,
+        CipherMode::GCM => 7,
+        CipherMode::EAX => 8,
+        CipherMode::XTS => 9,
+        CipherMode::CBC_CS1 => 10,
+        CipherMode::CBC_CS2 => 11,
+        CipherMode::CBC_CS3 => 12,
+    }
+}
+
+fn mode_from_id(id: u8) -> Result<CipherMode, CipherError> {
+    Ok(match id {
+        0 => CipherMode::ECB,
+        1 => CipherMode::CBC,
+        2 => CipherMode::PCBC,
+        3 => CipherMode::CFB,
+        4 => CipherMode::OFB,
+        5 => CipherMode::CTR,
+        6 => CipherMode::RandomDelta,
+        7 => CipherMode::GCM,
+        8 => CipherMode::EAX,
+        9 => CipherMode::XTS,
+        10 => CipherMode::CBC_CS1,
+        11 => CipherMode::CBC_CS2,
+        12 => CipherMode::CBC_CS3,
+        _ => return Err(CipherError::InvalidContainer("unknown cipher mode")),
+    })
+}
+
+fn padding_id(padding: PaddingMode) -> u8 {
+    match padding {
+        PaddingMode::Zeros => 0,
+        PaddingMode::ANSI_X923 => 1,
+        PaddingMode::PKCS7 => 2,
+        PaddingMode::ISO10126 => 3,
+        PaddingMode::None => 4,
+    }
+}
+
+fn padding_from_id(id: u8) -> Result<PaddingMode, CipherError> {
+    Ok(match id {
+        0 => PaddingMode::Zeros,
+        1 => PaddingMode::ANSI_X923,
+        2 => PaddingMode::PKCS7,
+        3 => PaddingMode::ISO10126,
+        4 => PaddingMode::None,
+        _ => return Err(CipherError::InvalidContainer("unknown padding mode")),
+    })
+}
+
+/// CRC-32 (IEEE 802.3). It catches corruption, not forgery; deliberate edits
+/// are caught by the header MAC or the GCM/EAX tag once the key is known.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn kdf_within_limits(kdf: &KdfParams) -> bool {
+    match *kdf {
+        KdfParams::Pbkdf2HmacSha256 { iterations, .. } => iterations <= MAX_PBKDF2_ITERATIONS,
+        KdfParams::Scrypt { log_n, r, p, .. } => {
+            let memory = 1u64
+                .checked_shl(log_n as u32)
+                .and_then(|n| n.checked_add(p as u64))
+                .and_then(|blocks| blocks.checked_mul(128 * r as u64));
+            p <= MAX_SCRYPT_P && memory.is_some_and(|memory| memory <= MAX_SCRYPT_MEMORY)
+        }
+    }
+}
+
+/// Whether `id` names a cipher that can be built: Rijndael needs one of its
+/// five block sizes and an irreducible degree-8 modulus, or the S-box has
+/// bytes without an inverse.
+fn algorithm_is_valid(id: AlgorithmId) -> bool {
+    match id {
+        AlgorithmId::Rijndael { block_size, poly } => {
+            matches!(block_size, 16 | 20 | 24 | 28 | 32) && poly >> 8 == 1 && (2..32).all(|d| gf2_rem(poly, d) != 0)
+        }
+        _ => true,
+    }
+}
+
+/// Remainder of the GF(2)[x] division `a / b`, polynomials as bit masks.
+fn gf2_rem(mut a: u16, b: u16) -> u16 {
+    let deg = |p: u16| 15 - p.leading_zeros();
+    while a != 0 && deg(a) >= deg(b) {
+        a ^= b << (deg(a) - deg(b));
+    }
+    a
+}
+
+fn is_authenticated(mode: CipherMode) -> bool {
+    matches!(mode, CipherMode::GCM | CipherMode::EAX)
+}
+
+/// HMAC over the raw header for modes without a tag. The MAC key is derived
+/// from the cipher key rather than being the cipher key itself.
+fn header_mac(key: &[u8], raw_header: &[u8]) -> HmacSha256 {
+    let mut mac_key = HmacSha256::new(key);
+    mac_key.update(b"SCNT header MAC");
+    let mut mac = HmacSha256::new(&mac_key.finalize());
+    mac.update(raw_header);
+    mac
+}
+
+/// Sequential reader over the header body.
+struct Fields<'a>(&'a [u8]);
+
+impl<'a> Fields<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], CipherError> {
+        if self.0.len() < n {
+            return Err(CipherError::InvalidContainer("header body is too short"));
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, CipherError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, CipherError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, CipherError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// A byte string prefixed with its u8 length.
+    fn bytes(&mut self) -> Result<Vec<u8>, CipherError> {
+        let len = self.u8()? as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+}
+
+fn push_bytes(out: &mut Vec<u8>, bytes: &[u8]) -> Result<(), CipherError> {
+    let len = u8::try_from(bytes.len())
+        .map_err(|_| CipherError::InvalidParameter("IV and salt must be at most 255 bytes"))?;
+    out.push(len);
+    out.extend_from_slice(bytes);
+    Ok(())
+}
+
+impl ContainerHeader {
+    pub fn new(algorithm: AlgorithmId, mode: CipherMode, padding: PaddingMode, iv: Option<Vec<u8>>) -> Self {
+        Self {
+            algorithm,
+            mode,
+            padding,
+            iv,
+            sector_size: 0,
+            kdf: None,
+            tag_len: 0,
+        }
+    }
+
+    /// Serialized header, checksum included.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CipherError> {
+        let mut body = Vec::new();
+        match self.algorithm {
+            AlgorithmId::Des => body.push(0),
+            AlgorithmId::Deal => body.push(1),
+            AlgorithmId::Rijndael { block_size, poly } => {
+                body.push(2);
+                body.push(block_size);
+                body.extend_from_slice(&poly.to_le_bytes());
+            }
+            AlgorithmId::Twofish => body.push(3),
+            AlgorithmId::Loki97 => body.push(4),
+            AlgorithmId::TripleDes => body.push(5),
+        }
+        body.push(mode_id(self.mode));
+        body.push(padding_id(self.padding));
+        push_bytes(&mut body, self.iv.as_deref().unwrap_or_default())?;
+        if self.mode == CipherMode::XTS {
+            body.extend_from_slice(&self.sector_size.to_le_bytes());
+        }
+        match &self.kdf {
+            None => body.push(0),
+            Some(KdfParams::Pbkdf2HmacSha256 { salt, iterations }) => {
+                body.push(1);
+                push_bytes(&mut body, salt)?;
+                body.extend_from_slice(&iterations.to_le_bytes());
+            }
+            Some(KdfParams::Scrypt { salt, log_n, r, p }) => {
+                body.push(2);
+                push_bytes(&mut body, salt)?;
+                body.push(*log_n);
+                body.extend_from_slice(&r.to_le_bytes());
+                body.extend_from_slice(&p.to_le_bytes());
+            }
+        }
+        body.push(self.tag_len);
+
+        let mut out = Vec::with_capacity(PREFIX_SIZE + body.len() + CHECKSUM_SIZE);
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&(body.len() as u16).to_le_bytes());
+        out.extend_from_slice(&body);
+        out.extend_from_slice(&crc32(&out).to_le_bytes());
+        Ok(out)
+    }
+
+    /// Parses the header at the start of `data`; returns it with its length
+    /// in bytes (the ciphertext starts there).
+    pub fn parse(data: &[u8]) -> Result<(Self, usize), CipherError> {
+        if data.len() < PREFIX_SIZE {
+            return Err(CipherError::InvalidContainer("input is too short for a header"));
+        }
+        let body_len = Self::check_prefix(&data[..PREFIX_SIZE])?;
+        let total = PREFIX_SIZE + body_len + CHECKSUM_SIZE;
+        if data.len() < total {
+            return Err(CipherError::InvalidContainer("input is too short for a header"));
+        }
+        Ok((Self::parse_checked(&data[..total])?, total))
+    }
+
+    /// Reads exactly the header from `reader`, leaving it at the ciphertext.
+    /// Returns the header with its raw bytes.
+    pub async fn read_from<R: AsyncRead + Unpin>(reader: &mut R) -> Result<(Self, Vec<u8>), CipherError> {
+        let mut raw = vec![0u8; PREFIX_SIZE];
+        reader.read_exact(&mut raw).await?;
+        let body_len = Self::check_prefix(&raw)?;
+        raw.resize(PREFIX_SIZE + body_len + CHECKSUM_SIZE, 0);
+        reader.read_exact(&mut raw[PREFIX_SIZE..]).await?;
+        Ok((Self::parse_checked(&raw)?, raw))
+    }
+
+    /// Validates magic and version; returns the body length.
+    fn check_prefix(prefix: &[u8]) -> Result<usize, CipherError> {
+        if prefix[..MAGIC.len()] != MAGIC {
+            return Err(CipherError::InvalidContainer("bad magic bytes"));
+        }
+        if prefix[MAGIC.len()] != VERSION {
+            return Err(CipherError::InvalidContainer("unsupported container version"));
+        }
+        Ok(u16::from_le_bytes([prefix[5], prefix[6]]) as usize)
+    }
+
+    fn parse_checked(raw: &[u8]) -> Result<Self, CipherError> {
+        let (covered, checksum) = raw.split_at(raw.len() - CHECKSUM_SIZE);
+        if crc32(covered).to_le_bytes() != checksum {
+            return Err(CipherError::InvalidContainer("header checksum mismatch"));
+        }
+
+        let mut fields = Fields(&covered[PREFIX_SIZE..]);
+        let algorithm = match fields.u8()? {
+            0 => AlgorithmId::Des,
+            1 => AlgorithmId::Deal,
+            2 => AlgorithmId::Rijndael {
+                block_size: fields.u8()?,
+                poly: fields.u16()?,
+            },
+            3 => AlgorithmId::Twofish,
+            4 => AlgorithmId::Loki97,
+            5 => AlgorithmId::TripleDes,
+            _ => return Err(CipherError::InvalidContainer("unknown algorithm")),
+        };
+        if !algorithm_is_valid(algorithm) {
+            return Err(CipherError::InvalidContainer("invalid algorithm parameters"));
+        }
+        let mode = mode_from_id(fields.u8()?)?;
+        let padding = padding_from_id(fields.u8()?)?;
+        let iv = Some(fields.bytes()?).filter(|iv| !iv.is_empty());
+        let sector_size = if mode == CipherMode::XTS { fields.u32()? } else { 0 };
+        let kdf = match fields.u8()? {
+            0 => None,
+            1 => Some(KdfParams::Pbkdf2HmacSha256 {
+                salt: fields.bytes()?,
+                iterations: fields.u32()?,
+            }),
+            2 => Some(KdfParams::Scrypt {
+                salt: fields.bytes()?,
+                log_n: fields.u8()?,
+                r: fields.u32()?,
+                p: fields.u32()?,
+            }),
+            _ => return Err(CipherError::InvalidContainer("unknown key derivation function")),
+        };
+        if kdf.as_ref().is_some_and(|kdf| !kdf_within_limits(kdf)) {
+            return Err(CipherError::InvalidContainer("key derivation parameters exceed the limits"));
+        }
+        let tag_len = fields.u8()?;
+        if !fields.0.is_empty() {
+            return Err(CipherError::InvalidContainer("trailing bytes in header body"));
+        }
+
+        Ok(Self {
+            algorithm,
+            mode,
+            padding,
+            iv,
+            sector_size,
+            kdf,
+            tag_len,
+        })
+    }
+
+    /// An unkeyed instance of the header's algorithm. The header is not yet
+    /// authenticated here, so a bad id or a resolver that cannot build it is
+    /// a malformed container.
+    fn instantiate(&self, resolve: &AlgorithmResolver) -> Result<Box<dyn SymmetricCipherWithRounds + Send + Sync>, CipherError> {
+        if !algorithm_is_valid(self.algorithm) {
+            return Err(CipherError::InvalidContainer("invalid algorithm parameters"));
+        }
+        builtin_algorithm(self.algorithm)
+            .or_else(|| resolve(self.algorithm))
+            .ok_or(CipherError::InvalidContainer("container algorithm is not available"))
+    }
+
+    /// Turns `passphrase` into the key with the header's KDF parameters.
+    fn derive_key(&self, passphrase: &[u8], resolve: &AlgorithmResolver) -> Result<Vec<u8>, CipherError> {
+        let kdf = self
+            .kdf
+            .as_ref()
+            .ok_or(CipherError::InvalidParameter("container has no key derivation parameters"))?;
+        if !kdf_within_limits(kdf) {
+            return Err(CipherError::InvalidParameter("key derivation parameters exceed the container limits"));
+        }
+        let algorithm = self.instantiate(resolve)?;
+        let mut key_len = key_len_for(&*algorithm)?;
+        if self.mode == CipherMode::XTS {
+            key_len *= 2;
+        }
+        let mut key = kdf.derive_key(passphrase, key_len)?;
+        algorithm.prepare_derived_key(&mut key);
+        Ok(key)
+    }
+
+    /// Builds a keyed `CipherContext` matching this header. `raw` is the
+    /// serialized header, bound as associated data in GCM/EAX.
+    fn context(&self, raw: &[u8], key: &[u8], resolve: &AlgorithmResolver) -> Result<CipherContext, CipherError> {
+        let algorithm = self.instantiate(resolve)?;
+        let tag_len = if is_authenticated(self.mode) { algorithm.block_size() } else { 0 };
+        if self.tag_len as usize != tag_len {
+            return Err(CipherError::InvalidContainer("tag length does not match the mode"));
+        }
+
+        let mut context = if self.mode == CipherMode::XTS {
+            let mut context = CipherContext::new_xts(algorithm, self.instantiate(resolve)?, self.sector_size as usize);
+            context.set_iv(self.iv.clone());
+            context
+        } else {
+            CipherContext::new(algorithm, self.mode, self.padding, self.iv.clone(), Vec::new())
+        };
+        context.set_key(key)?;
+        if is_authenticated(self.mode) {
+            context.set_associated_data(raw);
+        }
+        Ok(context)
+    }
+}
+
+/// Writes containers: the header (and its MAC), then the ciphertext of the input.
+pub struct ContainerWriter {
+    header: ContainerHeader,
+    /// Everything written before the ciphertext.
+    preamble: Vec<u8>,
+    context: CipherContext,
+}
+
+impl ContainerWriter {
+    /// The algorithm is taken from [`builtin_algorithm`] or, failing that,
+    /// from `resolve`. `header.tag_len` is set from the mode.
+    pub fn new(mut header: ContainerHeader, key: &[u8], resolve: &AlgorithmResolver) -> Result<Self, CipherError> {
+        header.tag_len = 0;
+        if is_authenticated(header.mode) {
+            let cipher = header.instantiate(resolve)?;
+            header.tag_len = cipher.block_size() as u8;
+        }
+
+        let mut preamble = header.to_bytes()?;
+        let context = header.context(&preamble, key, resolve)?;
+        if !is_authenticated(header.mode) {
+            let mac = header_mac(key, &preamble).finalize();
+            preamble.extend_from_slice(&mac);
+        }
+        Ok(Self {
+            header,
+            preamble,
+            context,
+        })
+    }
+
+    /// Derives the key from `passphrase` with `header.kdf`, which must be set
+    /// (e.g. to `KdfParams::default()`, which picks a fresh salt).
+    pub fn with_passphrase(
+        header: ContainerHeader,
+        passphrase: &[u8],
+        resolve: &AlgorithmResolver,
+    ) -> Result<Self, CipherError> {
+        let key = header.derive_key(passphrase, resolve)?;
+        Self::new(header, &key, resolve)
+    }
+
+    pub fn header(&self) -> &ContainerHeader {
+        &self.header
+    }
+
+    pub async fn encrypt(&self, input: CipherInput, output: &mut CipherOutput) -> Result<(), CipherError> {
+        match (input, output) {
+            (CipherInput::File(input_path), CipherOutput::File(output_path)) => {
+                let reader = tokio::fs::File::open(input_path).await?;
+                let mut writer = tokio::fs::File::create(output_path.as_str()).await?;
+                writer.write_all(&self.preamble).await?;
+                self.context.encryptor()?.copy_async(reader, writer).await
+            }
+            (input, output) => {
+                let mut body = CipherOutput::Buffer(Box::default());
+                self.context.encrypt(input, &mut body).await?;
+                let mut data = self.preamble.clone();
+                data.extend_from_slice(body.as_buffer());
+                Ok(write_all(output, &data)?)
+            }
+        }
+    }
+}
+
+enum Body {
+    Bytes(Vec<u8>),
+    File(tokio::fs::File),
+}
+
+/// Reads containers. The header is available before the key is needed, so
+/// the caller can derive the key from the stored KDF parameters first.
+pub struct ContainerReader {
+    header: ContainerHeader,
+    raw_header: Vec<u8>,
+    /// Present unless the mode is GCM/EAX; checked once the key is known.
+    header_mac: Option<Vec<u8>>,
+    body: Body,
+}
+
+impl ContainerReader {
+    pub async fn open(input: CipherInput) -> Result<Self, CipherError> {
+        match input {
+            CipherInput::Bytes(mut data) => {
+                let (header, len) = ContainerHeader::parse(&data)?;
+                let mut body = data.split_off(len);
+                let header_mac = if is_authenticated(header.mode) {
+                    None
+                } else {
+                    if body.len() < HEADER_MAC_SIZE {
+                        return Err(CipherError::InvalidContainer("input is too short for the header MAC"));
+                    }
+                    let ciphertext = body.split_off(HEADER_MAC_SIZE);
+                    Some(std::mem::replace(&mut body, ciphertext))
+                };
+                Ok(Self {
+                    header,
+                    raw_header: data,
+                    header_mac,
+                    body: Body::Bytes(body),
+                })
+            }
+            CipherInput::File(path) => {
+                let mut file = tokio::fs::File::open(path).await?;
+                let (header, raw_header) = ContainerHeader::read_from(&mut file).await?;
+                let header_mac = if is_authenticated(header.mode) {
+                    None
+                } else {
+                    let mut mac = vec![0u8; HEADER_MAC_SIZE];
+                    file.read_exact(&mut mac).await?;
+                    Some(mac)
+                };
+                Ok(Self {
+                    header,
+                    raw_header,
+                    header_mac,
+                    body: Body::File(file),
+                })
+            }
+        }
+    }
+
+    pub fn header(&self) -> &ContainerHeader {
+        &self.header
+    }
+
+    /// Decrypts the ciphertext following the header with a context configured
+    /// from it. The algorithm is resolved as in [`ContainerWriter::new`].
+    /// A header that does not match its MAC (edited, or a wrong key) yields
+    /// `AuthenticationFailed` before anything is decrypted.
+    pub async fn decrypt(
+        self,
+        key: &[u8],
+        resolve: &AlgorithmResolver,
+        output: &mut CipherOutput,
+    ) -> Result<(), CipherError> {
+        if let Some(mac) = &self.header_mac {
+            header_mac(key, &self.raw_header).verify(mac)?;
+        }
+        let context = self.header.context(&self.raw_header, key, resolve)?;
+        match (self.body, output) {
+            (Body::Bytes(data), output) => context.decrypt(CipherInput::Bytes(data), output).await,
+            (Body::File(file), CipherOutput::File(output_path)) => {
+                let writer = tokio::fs::File::create(output_path.as_str()).await?;
+                context.decryptor()?.copy_async(file, writer).await
+            }
+            (Body::File(file), CipherOutput::Buffer(buf)) => {
+                let mut result = Vec::new();
+                context.decryptor()?.copy_async(file, &mut result).await?;
+                **buf = result;
+                Ok(())
+            }
+        }
+    }
+
+    /// [`ContainerReader::decrypt`] with the key derived from `passphrase`
+    /// using the KDF parameters stored in the header.
+    pub async fn decrypt_with_passphrase(
+        self,
+        passphrase: &[u8],
+        resolve: &AlgorithmResolver,
+        output: &mut CipherOutput,
+    ) -> Result<(), CipherError> {
+        let key = self.header.derive_key(passphrase, resolve)?;
+        self.decrypt(&key, resolve, output).await
+    }
+}
@@ -1,46 +1,148 @@
+use crate::crypto::cipher_error::CipherError;
 use crate::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
-pub use crate::crypto::deal_key_expansion::DealKeyExpansion;
+pub use crate::crypto::deal_key_expansion::{DealKeyExpansion, ToyDealKeyExpansion};
+use crate::crypto::deal_key_expansion::default_rounds;
 use crate::crypto::des::DES;
 use crate::crypto::des_adapter::DesAdapter;
+use crate::crypto::des_key_expansion::DesKeyExpansion;
+use crate::crypto::des_transformation::DesTransformation;
 use crate::crypto::encryption_transformation::EncryptionTransformation;
 use crate::crypto::feistel_network::FeistelNetwork;
 use crate::crypto::key_expansion::KeyExpansion;
+use crate::crypto::registry::{BoxedCipher, CipherDescriptor};
 use std::sync::Arc;
 
+/// Longest round count `set_rounds` accepts; the schedule constants run out
+/// after 64 extra rounds anyway.
+pub const MAX_ROUNDS: usize = 64;
+
+pub const DESCRIPTOR: CipherDescriptor = CipherDescriptor {
+    name: "deal",
+    key_sizes: &[16, 24, 32],
+    block_size: 16,
+    default_rounds: 6,
+    constructor: |key| {
+        let mut cipher = DEAL::new();
+        cipher.set_key(key)?;
+        Ok(Box::new(cipher) as BoxedCipher)
+    },
+};
+
+/// DEAL: a Feistel network over 128-bit blocks whose round function is DES
+/// keyed with the round key. `DEAL::new_toy` keeps the earlier 64-bit-block
+/// variant with a cyclic 192-bit schedule.
 pub struct DEAL {
     feistel_network: FeistelNetwork,
     key: Vec<u8>,
     round_key: Vec<Vec<u8>>,
-    key_expansion: DealKeyExpansion,
+    /// One DES instance per round, keyed with that round's key, for the
+    /// in-place block methods.
+    round_des: Vec<DES>,
+    key_expansion: Arc<dyn KeyExpansion + Send + Sync>,
+    transformation: Arc<dyn EncryptionTransformation + Send + Sync>,
+    toy: bool,
+    rounds: Option<usize>,
 }
 
 impl DEAL {
-    pub fn new(des: DES) -> Self {
-        let key_expansion_struct = DealKeyExpansion;
-        let key_exp_arc: Arc<dyn KeyExpansion + Send + Sync> =
-            Arc::new(key_expansion_struct.clone());
+    /// DEAL-128/192/256 as specified: 16-byte blocks and 6, 6 or 8 rounds
+    /// depending on the key length.
+    pub fn new() -> Self {
+        Self::build(Arc::new(DealKeyExpansion::new()), false)
+    }
 
-        let des_adapter_arc: Arc<dyn EncryptionTransformation + Send + Sync> =
-            Arc::new(DesAdapter::new());
+    /// The original toy configuration: 8-byte blocks, a 24-byte key and 32
+    /// rounds that cycle through its three DES keys.
+    pub fn new_toy() -> Self {
+        Self::build(Arc::new(ToyDealKeyExpansion), true)
+    }
 
-        let feistel_network = FeistelNetwork::new(32, key_exp_arc.clone(), des_adapter_arc.clone());
+    fn build(key_expansion: Arc<dyn KeyExpansion + Send + Sync>, toy: bool) -> Self {
+        let transformation: Arc<dyn EncryptionTransformation + Send + Sync> =
+            Arc::new(DesAdapter::new());
+        let feistel_network = FeistelNetwork::new(0, key_expansion.clone(), transformation.clone());
 
         DEAL {
             feistel_network,
             key: Vec::new(),
             round_key: Vec::new(),
-            key_expansion: key_expansion_struct,
+            round_des: Vec::new(),
+            key_expansion,
+            transformation,
+            toy,
+            rounds: None,
+        }
+    }
+
+    /// Overrides the spec round count. Takes effect with the next `set_key`
+    /// and is not available for the toy variant.
+    pub fn set_rounds(&mut self, rounds: usize) -> Result<(), CipherError> {
+        if self.toy {
+            return Err(CipherError::InvalidParameter("the toy DEAL has a fixed round count"));
+        }
+        if rounds == 0 || rounds > MAX_ROUNDS {
+            return Err(CipherError::InvalidParameter("DEAL round count must be 1..=64"));
+        }
+        self.rounds = Some(rounds);
+        self.key_expansion = Arc::new(DealKeyExpansion::with_rounds(rounds));
+        Ok(())
+    }
+
+    /// Round count for the current key (or the one `key_len` would get).
+    pub fn rounds(&self, key_len: usize) -> usize {
+        match (self.toy, self.rounds) {
+            (true, _) => 32,
+            (false, Some(rounds)) => rounds,
+            (false, None) => default_rounds(key_len),
         }
     }
 
     pub fn encrypt(&self, block: &[u8], key: &[u8]) -> Vec<u8> {
         let round_keys = self.key_expansion.generate_round_keys(key);
-        self.feistel_network.encrypt_with_round_keys(block, &round_keys)
+        self.network(round_keys.len()).encrypt_with_round_keys(block, &round_keys)
     }
 
     pub fn decrypt(&self, block: &[u8], key: &[u8]) -> Vec<u8> {
         let round_keys = self.key_expansion.generate_round_keys(key);
-        self.feistel_network.decrypt_with_round_keys(block, &round_keys)
+        self.network(round_keys.len()).decrypt_with_round_keys(block, &round_keys)
+    }
+
+    fn network(&self, rounds: usize) -> FeistelNetwork {
+        FeistelNetwork::new(rounds, self.key_expansion.clone(), self.transformation.clone())
+    }
+
+    /// The Feistel rounds of `encrypt_block`/`decrypt_block` on `block` in
+    /// place: the round function is DES on a stack buffer, zero-padded for
+    /// the toy variant's 4-byte halves as in `DesAdapter`.
+    fn process_inplace(&self, block: &mut [u8], encrypt: bool) {
+        assert_eq!(block.len(), self.block_size(), "DEAL block has the wrong length");
+        let (left, right) = block.split_at_mut(block.len() / 2);
+        let half = left.len();
+        let round = |target: &mut [u8], source: &[u8], des: &DES| {
+            let mut f = [0u8; 8];
+            f[..half].copy_from_slice(source);
+            des.encrypt_block_inplace(&mut f);
+            for (t, x) in target.iter_mut().zip(&f[..half]) {
+                *t ^= x;
+            }
+        };
+        if encrypt {
+            for des in &self.round_des {
+                round(left, right, des);
+                left.swap_with_slice(right);
+            }
+        } else {
+            for des in self.round_des.iter().rev() {
+                round(right, left, des);
+                left.swap_with_slice(right);
+            }
+        }
+    }
+}
+
+impl Default for DEAL {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -55,14 +157,31 @@ impl CipherAlgorithm for DEAL {
 }
 
 impl SymmetricCipher for DEAL {
-    fn set_key(&mut self, key: &[u8]) -> Result<(), &'static str> {
-        if key.len() != 24 {
-            return Err("DEAL key must be 24 bytes (192 bits)");
+    fn set_key(&mut self, key: &[u8]) -> Result<(), CipherError> {
+        if !self.key_sizes().contains(&key.len()) {
+            return Err(CipherError::InvalidKeyLength {
+                expected: self.key_sizes(),
+                actual: key.len(),
+            });
         }
         self.key = key.to_vec();
         self.round_key = self.key_expansion.generate_round_keys(key);
+        self.feistel_network = self.network(self.round_key.len());
+        self.round_des = self
+            .round_key
+            .iter()
+            .map(|round_key| {
+                let mut des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+                des.set_key_with_rounds(round_key);
+                des
+            })
+            .collect();
         Ok(())
     }
+
+    fn key_sizes(&self) -> &'static [usize] {
+        if self.toy { &[24] } else { &[16, 24, 32] }
+    }
 }
 
 impl SymmetricCipherWithRounds for DEAL {
@@ -70,16 +189,24 @@ impl SymmetricCipherWithRounds for DEAL {
         self.set_key(key).unwrap();
     }
 
-    fn encrypt_block(&self, data: &[u8], round_key: &[u8]) -> Vec<u8> {
+    fn encrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
         self.feistel_network.encrypt_with_round_keys(data, &self.round_key)
     }
 
-    fn decrypt_block(&self, data: &[u8], round_key: &[u8]) -> Vec<u8> {
+    fn decrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
         self.feistel_network.decrypt_with_round_keys(data, &self.round_key)
     }
 
     fn block_size(&self) -> usize {
-        8
+        if self.toy { 8 } else { 16 }
+    }
+
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        self.process_inplace(block, true);
+    }
+
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        self.process_inplace(block, false);
     }
 
     fn export_round_keys(&self) -> Option<Vec<u8>> {
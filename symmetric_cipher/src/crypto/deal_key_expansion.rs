@@ -1,8 +1,86 @@
+use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
+use crate::crypto::des::DES;
+use crate::crypto::des_key_expansion::DesKeyExpansion;
+use crate::crypto::des_transformation::DesTransformation;
 use crate::crypto::key_expansion::KeyExpansion;
+use std::sync::Arc;
 
-pub struct DealKeyExpansion;
+/// Fixed DES key that encrypts the key schedule (Knudsen, "DEAL - A 128-bit
+/// Block Cipher").
+const SCHEDULE_KEY: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB, 0xCD, 0xEF];
+
+/// Spec round count: 6 rounds for 128/192-bit keys, 8 for 256-bit keys.
+pub fn default_rounds(key_len: usize) -> usize {
+    if key_len == 32 { 8 } else { 6 }
+}
+
+/// DEAL key schedule. The key is split into s = 2, 3 or 4 DES-sized blocks
+/// K1..Ks which are encrypted in CBC mode under the fixed key:
+///
+///   RK1 = E(K1), RKi = E(K((i-1) mod s + 1) ^ <c> ^ RK(i-1))
+///
+/// where <c> is zero for the first pass over the key blocks and then the
+/// 64-bit integers 1, 2, 4, 8, ... for every further round.
+#[derive(Clone)]
+pub struct DealKeyExpansion {
+    rounds: Option<usize>,
+}
+
+impl DealKeyExpansion {
+    /// Schedule for the spec round count of the key length.
+    pub fn new() -> Self {
+        DealKeyExpansion { rounds: None }
+    }
+
+    pub fn with_rounds(rounds: usize) -> Self {
+        DealKeyExpansion { rounds: Some(rounds) }
+    }
+}
+
+impl Default for DealKeyExpansion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl KeyExpansion for DealKeyExpansion {
+    fn generate_round_keys(&self, key: &[u8]) -> Vec<Vec<u8>> {
+        assert!(
+            matches!(key.len(), 16 | 24 | 32),
+            "DEAL requires a 128-, 192- or 256-bit key"
+        );
+        let rounds = self.rounds.unwrap_or_else(|| default_rounds(key.len()));
+        let blocks: Vec<&[u8]> = key.chunks(8).collect();
+        assert!(rounds <= blocks.len() + 64, "too many DEAL rounds for the key schedule");
+
+        let mut des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+        des.set_key_with_rounds(&SCHEDULE_KEY);
+
+        let mut chain = [0u8; 8];
+        (0..rounds)
+            .map(|i| {
+                let constant = match i.checked_sub(blocks.len()) {
+                    Some(extra) => 1u64 << extra,
+                    None => 0,
+                };
+                let mut input = constant.to_be_bytes();
+                for ((b, k), c) in input.iter_mut().zip(blocks[i % blocks.len()]).zip(&chain) {
+                    *b ^= k ^ c;
+                }
+                let round_key = des.encrypt(&input);
+                chain.copy_from_slice(&round_key);
+                round_key
+            })
+            .collect()
+    }
+}
+
+/// The original toy schedule: a 192-bit key whose three DES keys are used
+/// cyclically for 32 rounds over a 64-bit block.
+#[derive(Clone)]
+pub struct ToyDealKeyExpansion;
+
+impl KeyExpansion for ToyDealKeyExpansion {
     fn generate_round_keys(&self, key: &[u8]) -> Vec<Vec<u8>> {
         assert_eq!(key.len(), 24, "DEAL requires 192-bit key (24 bytes)");
 
@@ -19,11 +97,3 @@ impl KeyExpansion for DealKeyExpansion {
             .collect()
     }
 }
-
-
-impl Clone for DealKeyExpansion {
-    fn clone(&self) -> Self {
-        DealKeyExpansion
-    }
-}
-
@@ -1,15 +1,30 @@
+use crate::crypto::cipher_error::CipherError;
 use crate::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
+use crate::crypto::des_key_expansion::DesKeyExpansion;
 use crate::crypto::des_tables::{FP, IP};
+use crate::crypto::des_transformation::DesTransformation;
 use crate::crypto::encryption_transformation::EncryptionTransformation;
 use crate::crypto::feistel_network::FeistelNetwork;
 use crate::crypto::key_expansion::KeyExpansion;
-use crate::crypto::utils::shift_bits_little_endian;
+use crate::crypto::registry::{BoxedCipher, CipherDescriptor};
+use crate::crypto::utils::{permute_bits, permute_bits_into};
 use std::sync::Arc;
 
+pub const DESCRIPTOR: CipherDescriptor = CipherDescriptor {
+    name: "des",
+    key_sizes: &[8],
+    block_size: 8,
+    default_rounds: 16,
+    constructor: |key| {
+        let mut cipher = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+        cipher.set_key(key)?;
+        Ok(Box::new(cipher) as BoxedCipher)
+    },
+};
+
 pub struct DES {
     feistel_network: FeistelNetwork,
     key_expansion: Arc<dyn KeyExpansion + Send + Sync>,
-    transformation: Arc<dyn EncryptionTransformation + Send + Sync>,
     round_key: Vec<Vec<u8>>,
     key: Vec<u8>,
 }
@@ -20,30 +35,35 @@ impl DES {
         transformation: Arc<dyn EncryptionTransformation + Send + Sync>,
     ) -> Self {
         let feistel_network =
-            FeistelNetwork::new(16, key_expansion.clone(), transformation.clone());
+            FeistelNetwork::new(16, key_expansion.clone(), transformation);
 
         DES {
             feistel_network,
             key_expansion,
-            transformation,
             round_key: Vec::new(),
             key: Vec::new(),
         }
     }
 
+    // The network swaps halves after every round, including the last one, so
+    // undo that before FP to get FP(R16 || L16) as in FIPS 46-3.
     pub fn encrypt(&self, block: &[u8]) -> Vec<u8> {
-        let permuted = shift_bits_little_endian(block, &IP, true, 1);
+        let permuted = permute_bits(block, &IP);
         let result = self.feistel_network.encrypt_with_round_keys(&permuted, &self.round_key);
-        shift_bits_little_endian(&result, &FP, true, 1)
+        permute_bits(&swap_halves(&result), &FP)
     }
 
     pub fn decrypt(&self, block: &[u8]) -> Vec<u8> {
-        let permuted = shift_bits_little_endian(block, &IP, true, 1);
+        let permuted = swap_halves(&permute_bits(block, &IP));
         let result = self.feistel_network.decrypt_with_round_keys(&permuted, &self.round_key);
-        shift_bits_little_endian(&result, &FP, true, 1)
+        permute_bits(&result, &FP)
     }
 }
 
+fn swap_halves(block: &[u8]) -> Vec<u8> {
+    [&block[4..], &block[..4]].concat()
+}
+
 impl CipherAlgorithm for DES {
     fn encrypt(&self, block: &[u8]) -> Vec<u8> {
         DES::encrypt(self, block)
@@ -54,14 +74,21 @@ impl CipherAlgorithm for DES {
 }
 
 impl SymmetricCipher for DES {
-    fn set_key(&mut self, key: &[u8]) -> Result<(), &'static str> {
+    fn set_key(&mut self, key: &[u8]) -> Result<(), CipherError> {
         if key.len() != 8 {
-            return Err("DES key must be 8 bytes");
+            return Err(CipherError::InvalidKeyLength {
+                expected: self.key_sizes(),
+                actual: key.len(),
+            });
         }
         self.key = key.to_vec();
         self.round_key = self.key_expansion.generate_round_keys(key);
         Ok(())
     }
+
+    fn key_sizes(&self) -> &'static [usize] {
+        &[8]
+    }
 }
 
 impl SymmetricCipherWithRounds for DES {
@@ -71,11 +98,11 @@ impl SymmetricCipherWithRounds for DES {
         self.round_key = self.key_expansion.generate_round_keys(key);
     }
 
-    fn encrypt_block(&self, data: &[u8], round_key: &[u8]) -> Vec<u8> {
+    fn encrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
         self.encrypt(data)
     }
 
-    fn decrypt_block(&self, data: &[u8], round_key: &[u8]) -> Vec<u8> {
+    fn decrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
         self.decrypt(data)
     }
 
@@ -83,6 +110,24 @@ impl SymmetricCipherWithRounds for DES {
         8
     }
 
+    // Same steps as `encrypt`/`decrypt` on a stack copy of the block; with
+    // `DesTransformation` nothing is allocated.
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        let mut state = [0u8; 8];
+        permute_bits_into(block, &IP, &mut state);
+        self.feistel_network.encrypt_inplace(&mut state, &self.round_key);
+        state.rotate_left(4);
+        permute_bits_into(&state, &FP, block);
+    }
+
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        let mut state = [0u8; 8];
+        permute_bits_into(block, &IP, &mut state);
+        state.rotate_left(4);
+        self.feistel_network.decrypt_inplace(&mut state, &self.round_key);
+        permute_bits_into(&state, &FP, block);
+    }
+
     fn export_round_keys(&self) -> Option<Vec<u8>> {
         None
     }
@@ -23,12 +23,18 @@ impl DesAdapter {
 }
 
 impl EncryptionTransformation for DesAdapter {
+    // DEAL with an 8-byte block hands DES 4-byte halves: zero-pad them to a
+    // DES block and keep the leading bytes of the result.
     fn transform(&self, data: &[u8], round_key: &[u8]) -> Vec<u8> {
         assert_eq!(round_key.len(), 8, "DES round key must be 8 bytes");
         TL_DES.with(|cell| {
             let mut des = cell.borrow_mut();
             des.set_key_with_rounds(round_key);
-            des.encrypt(data)
+            let mut block = [0u8; 8];
+            block[..data.len()].copy_from_slice(data);
+            let mut out = des.encrypt(&block);
+            out.truncate(data.len());
+            out
         })
     }
 }
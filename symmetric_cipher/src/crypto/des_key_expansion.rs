@@ -1,39 +1,37 @@
 use crate::crypto::des_tables::{PC1, PC2};
 use crate::crypto::key_expansion::KeyExpansion;
-use crate::crypto::utils::{shift_bits_little_endian, bytes_to_bits, bits_to_bytes};
-use bitvec::prelude::BitVec;
+use crate::crypto::utils::permute_bits;
 
 const SHIFT_BITS: [usize; 16] = [
     1, 1, 2, 2, 2, 2, 2, 2,
     1, 2, 2, 2, 2, 2, 2, 1,
 ];
 
+const HALF_MASK: u32 = (1 << 28) - 1;
+
 pub struct DesKeyExpansion;
 
+fn rotate_28(half: u32, shift: usize) -> u32 {
+    ((half << shift) | (half >> (28 - shift))) & HALF_MASK
+}
+
 impl KeyExpansion for DesKeyExpansion {
     fn generate_round_keys(&self, key: &[u8]) -> Vec<Vec<u8>> {
         assert_eq!(key.len(), 8, "DES key must be 8 bytes, it is in des generate round keys");
 
-        // key
-        let permuted = shift_bits_little_endian(key, &PC1, true, 1);
-        let bits = bytes_to_bits(&permuted); // BitVec длины 56
+        // PC-1 отбрасывает биты чётности: 56 бит = C (28) || D (28)
+        let permuted = permute_bits(key, &PC1);
+        let cd = permuted.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+        let mut c = (cd >> 28) as u32;
+        let mut d = cd as u32 & HALF_MASK;
 
-        // Split
-        let mut c = bits.iter().by_vals().take(28).collect::<BitVec>();
-        let mut d = bits.iter().by_vals().skip(28).take(28).collect::<BitVec>();
-
-        // shift
         let mut round_keys = Vec::with_capacity(16);
         for &shift in &SHIFT_BITS {
-            c.rotate_left(shift);
-            d.rotate_left(shift);
-
-            let mut cd = BitVec::with_capacity(56);
-            cd.extend(c.iter().by_vals());
-            cd.extend(d.iter().by_vals());
+            c = rotate_28(c, shift);
+            d = rotate_28(d, shift);
 
-            let cd_bytes = bits_to_bytes(&cd);
-            let subkey = shift_bits_little_endian(&cd_bytes, &PC2, true, 1);
+            let cd = ((c as u64) << 28) | d as u64;
+            let subkey = permute_bits(&cd.to_be_bytes()[1..], &PC2);
             round_keys.push(subkey);
         }
 
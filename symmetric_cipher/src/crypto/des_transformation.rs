@@ -1,53 +1,33 @@
 use crate::crypto::des_tables::{E, P, S_BOXES};
 use crate::crypto::encryption_transformation::EncryptionTransformation;
-use crate::crypto::utils::shift_bits_little_endian;
+use crate::crypto::utils::permute_bits_into;
 pub struct DesTransformation;
 
-fn get_bit(data: &[u8], bit_pos: usize) -> u8 {
-    let byte = bit_pos / 8;
-    let bit = bit_pos % 8;
-    (data[byte] >> bit) & 1
-}
-
-fn set_next_4_bits(dest: &mut [u8; 4], val: u8, start_bit: usize) {
-    for i in 0..4 {
-        let bit_val = (val >> (3 - i)) & 1;
-        let bit_pos = start_bit + i;
-        let byte = bit_pos / 8;
-        let bit = bit_pos % 8;
-        dest[byte] |= bit_val << bit;
-    }
-}
-
-fn xor_parts(a: &[u8], b: &[u8]) -> Vec<u8> {
-    a.iter().zip(b.iter()).map(|(x, y)| x ^ y).collect()
-}
-
 impl EncryptionTransformation for DesTransformation {
     fn transform(&self, r_block: &[u8], round_key: &[u8]) -> Vec<u8> {
+        let mut out = vec![0u8; 4];
+        self.transform_into(r_block, round_key, &mut out);
+        out
+    }
 
-        let expanded = shift_bits_little_endian(r_block, &E, true, 1);
-
-        let mixed = xor_parts(&expanded, round_key);
-
-        let mut s_result = [0u8; 4];
-        let mut bit_index = 0;
-
-        for box_i in 0..8 {
-            let start = box_i * 6;
-            let row = (get_bit(&mixed, start) << 1) | get_bit(&mixed, start + 5);
-            let mut col = 0;
-            for j in 1..5 {
-                col = (col << 1) | get_bit(&mixed, start + j);
-            }
-            let s_val = S_BOXES[box_i][(row * 16 + col) as usize];
-            set_next_4_bits(&mut s_result, s_val as u8, bit_index);
-            bit_index += 4;
+    fn transform_into(&self, r_block: &[u8], round_key: &[u8], out: &mut [u8]) {
+        let mut expanded = [0u8; 6];
+        permute_bits_into(r_block, &E, &mut expanded);
+
+        let mixed = expanded
+            .iter()
+            .zip(round_key)
+            .fold(0u64, |acc, (&e, &k)| (acc << 8) | (e ^ k) as u64);
+
+        // Каждый S-блок берёт 6 бит: крайние биты — строка, средние 4 — столбец
+        let mut s_result = 0u32;
+        for (box_i, s_box) in S_BOXES.iter().enumerate() {
+            let six = (mixed >> (42 - 6 * box_i)) as usize & 0x3F;
+            let row = ((six >> 4) & 0b10) | (six & 1);
+            let col = (six >> 1) & 0xF;
+            s_result |= (s_box[row * 16 + col] as u32) << (28 - 4 * box_i);
         }
 
-
-        let permuted = shift_bits_little_endian(&s_result, &P, true, 1);
-
-        permuted
+        permute_bits_into(&s_result.to_be_bytes(), &P, out);
     }
 }
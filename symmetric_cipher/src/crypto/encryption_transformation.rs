@@ -1,3 +1,11 @@
 pub trait EncryptionTransformation {
     fn transform(&self, input_block: &[u8], round_key: &[u8]) -> Vec<u8>;
-}
\ No newline at end of file
+
+    /// Writes the first `out.len()` bytes of `transform` into `out`. The
+    /// default allocates; override it to keep in-place block encryption
+    /// allocation-free.
+    fn transform_into(&self, input_block: &[u8], round_key: &[u8], out: &mut [u8]) {
+        let result = self.transform(input_block, round_key);
+        out.copy_from_slice(&result[..out.len()]);
+    }
+}
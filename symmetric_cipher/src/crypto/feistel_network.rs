@@ -4,6 +4,7 @@ use crate::crypto::key_expansion::KeyExpansion;
 
 pub struct FeistelNetwork {
     num_round: usize,
+    #[allow(dead_code)]
     key_expansion: Arc<dyn KeyExpansion + Send + Sync>,
     transformation: Arc<dyn EncryptionTransformation + Send + Sync>,
 }
@@ -29,8 +30,8 @@ impl FeistelNetwork {
         let mut left = left.to_vec();
         let mut right = right.to_vec();
 
-        for index in 0..self.num_round {
-            let feistel_out = self.transformation.transform(&right, &round_keys[index]);
+        for round_key in &round_keys[..self.num_round] {
+            let feistel_out = self.transformation.transform(&right, round_key);
             let new_right = left
                 .iter()
                 .zip(feistel_out.iter())
@@ -50,8 +51,8 @@ impl FeistelNetwork {
         let mut left = left.to_vec();
         let mut right = right.to_vec();
 
-        for index in (0..self.num_round).rev() {
-            let feistel_out = self.transformation.transform(&left, &round_keys[index]);
+        for round_key in round_keys[..self.num_round].iter().rev() {
+            let feistel_out = self.transformation.transform(&left, round_key);
             let new_left = right
                 .iter()
                 .zip(feistel_out.iter())
@@ -64,4 +65,42 @@ impl FeistelNetwork {
         [left, right].concat()
     }
 
+    /// Same rounds as `encrypt_with_round_keys`, on `block` in place. The
+    /// round function output goes through a stack buffer, so halves are
+    /// limited to `MAX_HALF` bytes.
+    pub fn encrypt_inplace(&self, block: &mut [u8], round_keys: &[Vec<u8>]) {
+        let (left, right) = split_halves(block);
+        for round_key in &round_keys[..self.num_round] {
+            self.mix(left, right, round_key);
+            left.swap_with_slice(right);
+        }
+    }
+
+    pub fn decrypt_inplace(&self, block: &mut [u8], round_keys: &[Vec<u8>]) {
+        let (left, right) = split_halves(block);
+        for round_key in round_keys[..self.num_round].iter().rev() {
+            self.mix(right, left, round_key);
+            left.swap_with_slice(right);
+        }
+    }
+
+    /// target ^= F(source).
+    fn mix(&self, target: &mut [u8], source: &[u8], round_key: &[u8]) {
+        let mut feistel_out = [0u8; MAX_HALF];
+        let feistel_out = &mut feistel_out[..source.len()];
+        self.transformation.transform_into(source, round_key, feistel_out);
+        for (t, f) in target.iter_mut().zip(feistel_out.iter()) {
+            *t ^= f;
+        }
+    }
+}
+
+/// Longest half block the in-place methods handle.
+pub const MAX_HALF: usize = 32;
+
+fn split_halves(block: &mut [u8]) -> (&mut [u8], &mut [u8]) {
+    assert_eq!(block.len() % 2, 0, "Block size must be even");
+    assert!(block.len() / 2 <= MAX_HALF, "Block is too large for the in-place Feistel network");
+    let half = block.len() / 2;
+    block.split_at_mut(half)
 }
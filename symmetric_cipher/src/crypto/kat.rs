@@ -0,0 +1,353 @@
+use crate::crypto::cipher_context::CipherContext;
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
+use crate::crypto::cipher_types::{CipherMode, PaddingMode};
+use crate::crypto::registry::BoxedCipher;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KatVector {
+    /// `None` outside `[ENCRYPT]`/`[DECRYPT]`: both directions are checked.
+    pub direction: Option<Direction>,
+    pub count: usize,
+    /// Three TDES keys are joined into the shortest equivalent keying option:
+    /// 8 bytes if all are equal, 16 if KEY3 = KEY1, 24 otherwise.
+    pub key: Vec<u8>,
+    pub iv: Option<Vec<u8>>,
+    pub plaintext: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    /// Line of the `COUNT`/`I` field, for error messages.
+    pub line: usize,
+}
+
+/// Mode a vector file exercises. Everything except CFB8 runs through
+/// `CipherContext` without padding; CFB8 is not one of its modes and is
+/// computed here from single-block encryptions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KatMode {
+    Ecb,
+    Cbc,
+    /// Full-block CFB: CFB128 for AES, CFB64 for TDES.
+    Cfb,
+    Cfb8,
+    Ofb,
+}
+
+impl KatMode {
+    /// From the CAVP file naming (`CBCVarTxt128.rsp`, `TECBMMT2.rsp`,
+    /// `CFB8GFSbox256.rsp`, ...) or `ecb_*.txt`.
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        let name = match name.strip_prefix('t') {
+            Some(rest) if ["ecb", "cbc", "cfb", "ofb"].iter().any(|m| rest.starts_with(m)) => rest,
+            _ => name.as_str(),
+        };
+        [
+            ("ecb", KatMode::Ecb),
+            ("cbc", KatMode::Cbc),
+            ("cfb128", KatMode::Cfb),
+            ("cfb64", KatMode::Cfb),
+            ("cfb8", KatMode::Cfb8),
+            ("ofb", KatMode::Ofb),
+        ]
+        .into_iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, mode)| mode)
+    }
+
+    fn cipher_mode(self) -> CipherMode {
+        match self {
+            KatMode::Ecb => CipherMode::ECB,
+            KatMode::Cbc => CipherMode::CBC,
+            KatMode::Cfb | KatMode::Cfb8 => CipherMode::CFB,
+            KatMode::Ofb => CipherMode::OFB,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum KatError {
+    Io(std::io::Error),
+    /// The file name does not say which mode the vectors are for.
+    UnknownMode,
+    Parse { line: usize, message: &'static str },
+    /// The cipher or the mode refused the vector (bad key length, weak key...).
+    Cipher { line: usize, count: usize, error: CipherError },
+    Mismatch {
+        line: usize,
+        count: usize,
+        direction: Direction,
+        expected: Vec<u8>,
+        actual: Vec<u8>,
+    },
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl fmt::Display for KatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KatError::Io(err) => write!(f, "I/O error: {}", err),
+            KatError::UnknownMode => write!(f, "cannot tell the mode from the file name"),
+            KatError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            KatError::Cipher { line, count, error } => write!(f, "line {} (COUNT = {}): {}", line, count, error),
+            KatError::Mismatch {
+                line,
+                count,
+                direction,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "line {} (COUNT = {}, {:?}): expected {}, got {}",
+                line,
+                count,
+                direction,
+                hex(expected),
+                hex(actual)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KatError {}
+
+/// Outcome of a file: how many encryptions/decryptions were checked and
+/// which of them failed.
+#[derive(Debug, Default)]
+pub struct KatReport {
+    pub checked: usize,
+    pub failures: Vec<KatError>,
+}
+
+/// Builds a cipher keyed with the vector's key.
+pub type NewCipher<'a> = dyn Fn(&[u8]) -> Result<BoxedCipher, CipherError> + 'a;
+
+#[derive(Clone, Default)]
+struct Fields {
+    count: Option<(usize, usize)>,
+    key: Option<Vec<u8>>,
+    tdes_keys: [Option<Vec<u8>>; 3],
+    iv: Option<Vec<u8>>,
+    plaintext: Option<Vec<u8>>,
+    ciphertext: Option<Vec<u8>>,
+}
+
+impl Fields {
+    fn finish(self, direction: Option<Direction>) -> Result<Option<KatVector>, KatError> {
+        let Some((count, line)) = self.count else {
+            return Ok(None);
+        };
+        let missing = |message| KatError::Parse { line, message };
+
+        let key = match self.tdes_keys {
+            [None, None, None] => self.key.ok_or(missing("vector has no key"))?,
+            [Some(k1), Some(k2), Some(k3)] => {
+                if k1 == k2 && k2 == k3 {
+                    k1
+                } else if k1 == k3 {
+                    [k1, k2].concat()
+                } else {
+                    [k1, k2, k3].concat()
+                }
+            }
+            _ => return Err(missing("KEY1, KEY2 and KEY3 must all be given")),
+        };
+
+        Ok(Some(KatVector {
+            direction,
+            count,
+            key,
+            iv: self.iv,
+            plaintext: self.plaintext.ok_or(missing("vector has no plaintext"))?,
+            ciphertext: self.ciphertext.ok_or(missing("vector has no ciphertext"))?,
+            line,
+        }))
+    }
+}
+
+fn parse_hex(value: &str, line: usize) -> Result<Vec<u8>, KatError> {
+    let invalid = || KatError::Parse {
+        line,
+        message: "value is not hex",
+    };
+    if !value.is_ascii() || !value.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).map_err(|_| invalid()))
+        .collect()
+}
+
+/// Reads the vectors of a NIST CAVP `.rsp` file (AESAVS, TDESAVS) or of an
+/// `ecb_tbl.txt` from the AES candidate submissions.
+///
+/// Both are `NAME = value` lines. A vector starts at `COUNT` (CAVP) or `I`
+/// (submissions) and takes `KEY`/`KEYs` or `KEY1`..`KEY3`, `IV`,
+/// `PLAINTEXT`/`PT` and `CIPHERTEXT`/`CT`. `[ENCRYPT]` and `[DECRYPT]` set
+/// the direction that is checked; vectors outside such a section are checked
+/// both ways. Fields given before the first vector of a block (as in
+/// `ecb_vk.txt`/`ecb_vt.txt`, where the plaintext or key is stated once)
+/// apply to every vector up to the next section, `KEYSIZE` or `====` line.
+/// `#` comments, other sections and unknown fields are skipped.
+pub fn parse(text: &str) -> Result<Vec<KatVector>, KatError> {
+    let mut vectors = Vec::new();
+    let mut direction = None;
+    let mut shared = Fields::default();
+    let mut fields = Fields::default();
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.chars().all(|c| c == '=') {
+            vectors.extend(std::mem::take(&mut fields).finish(direction)?);
+            shared = Fields::default();
+            continue;
+        }
+
+        if let Some(section) = trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            vectors.extend(std::mem::take(&mut fields).finish(direction)?);
+            shared = Fields::default();
+            match section.trim().to_ascii_uppercase().as_str() {
+                "ENCRYPT" => direction = Some(Direction::Encrypt),
+                "DECRYPT" => direction = Some(Direction::Decrypt),
+                _ => {}
+            }
+            continue;
+        }
+
+        let Some((name, value)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let target = if fields.count.is_some() { &mut fields } else { &mut shared };
+        match name.trim().to_ascii_uppercase().as_str() {
+            "COUNT" | "I" => {
+                vectors.extend(std::mem::take(&mut fields).finish(direction)?);
+                let count = value.parse().map_err(|_| KatError::Parse {
+                    line,
+                    message: "COUNT is not a number",
+                })?;
+                fields = Fields {
+                    count: Some((count, line)),
+                    ..shared.clone()
+                };
+            }
+            "KEYSIZE" => {
+                vectors.extend(std::mem::take(&mut fields).finish(direction)?);
+                shared = Fields::default();
+            }
+            "KEY" | "KEYS" => target.key = Some(parse_hex(value, line)?),
+            "KEY1" => target.tdes_keys[0] = Some(parse_hex(value, line)?),
+            "KEY2" => target.tdes_keys[1] = Some(parse_hex(value, line)?),
+            "KEY3" => target.tdes_keys[2] = Some(parse_hex(value, line)?),
+            "IV" => target.iv = Some(parse_hex(value, line)?),
+            "PLAINTEXT" | "PT" => target.plaintext = Some(parse_hex(value, line)?),
+            "CIPHERTEXT" | "CT" => target.ciphertext = Some(parse_hex(value, line)?),
+            _ => {}
+        }
+    }
+
+    vectors.extend(fields.finish(direction)?);
+    Ok(vectors)
+}
+
+/// CFB with 8-bit feedback (SP 800-38A, 6.3 with s = 8).
+fn cfb8(cipher: &dyn SymmetricCipherWithRounds, iv: &[u8], data: &[u8], encrypt: bool) -> Vec<u8> {
+    let mut register = iv.to_vec();
+    let mut keystream = vec![0u8; register.len()];
+    data.iter()
+        .map(|&byte| {
+            keystream.copy_from_slice(&register);
+            cipher.encrypt_block_inplace(&mut keystream);
+            let out = byte ^ keystream[0];
+            register.rotate_left(1);
+            *register.last_mut().unwrap() = if encrypt { out } else { byte };
+            out
+        })
+        .collect()
+}
+
+fn process(cipher: BoxedCipher, mode: KatMode, iv: Option<&[u8]>, data: &[u8], encrypt: bool) -> Result<Vec<u8>, CipherError> {
+    if mode == KatMode::Cfb8 {
+        let iv = iv.ok_or(CipherError::InvalidIvLength {
+            expected: cipher.block_size(),
+            actual: 0,
+        })?;
+        return Ok(cfb8(&*cipher, iv, data, encrypt));
+    }
+
+    let context = CipherContext::new(cipher, mode.cipher_mode(), PaddingMode::None, iv.map(<[u8]>::to_vec), Vec::new());
+    let mut stream = if encrypt { context.encryptor()? } else { context.decryptor()? };
+    let mut output = stream.update(data);
+    output.extend(stream.finalize()?);
+    Ok(output)
+}
+
+/// Checks one vector in the direction(s) it asks for; returns the failures.
+pub fn check(vector: &KatVector, mode: KatMode, new_cipher: &NewCipher) -> Vec<KatError> {
+    let directions = match vector.direction {
+        Some(direction) => vec![direction],
+        None => vec![Direction::Encrypt, Direction::Decrypt],
+    };
+
+    directions
+        .into_iter()
+        .filter_map(|direction| {
+            let (input, expected) = match direction {
+                Direction::Encrypt => (&vector.plaintext, &vector.ciphertext),
+                Direction::Decrypt => (&vector.ciphertext, &vector.plaintext),
+            };
+            let cipher_error = |error| KatError::Cipher {
+                line: vector.line,
+                count: vector.count,
+                error,
+            };
+            let actual = new_cipher(&vector.key)
+                .and_then(|cipher| process(cipher, mode, vector.iv.as_deref(), input, direction == Direction::Encrypt));
+            match actual {
+                Err(error) => Some(cipher_error(error)),
+                Ok(actual) if &actual != expected => Some(KatError::Mismatch {
+                    line: vector.line,
+                    count: vector.count,
+                    direction,
+                    expected: expected.clone(),
+                    actual,
+                }),
+                Ok(_) => None,
+            }
+        })
+        .collect()
+}
+
+/// Parses `text` and checks every vector in it. Only malformed input is an
+/// `Err`; failing vectors are listed in the report.
+pub fn run(text: &str, mode: KatMode, new_cipher: &NewCipher) -> Result<KatReport, KatError> {
+    let mut report = KatReport::default();
+    for vector in parse(text)? {
+        report.checked += vector.direction.map_or(2, |_| 1);
+        report.failures.extend(check(&vector, mode, new_cipher));
+    }
+    Ok(report)
+}
+
+/// [`run`] on a file, with the mode taken from its name.
+pub fn run_file(path: &Path, new_cipher: &NewCipher) -> Result<KatReport, KatError> {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let mode = KatMode::from_file_name(name).ok_or(KatError::UnknownMode)?;
+    let text = std::fs::read_to_string(path).map_err(KatError::Io)?;
+    run(&text, mode, new_cipher)
+}
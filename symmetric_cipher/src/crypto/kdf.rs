@@ -0,0 +1,196 @@
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::SymmetricCipher;
+use crate::crypto::mac::HmacSha256;
+use crate::crypto::sha256::DIGEST_SIZE;
+use rand::RngCore;
+use rayon::prelude::*;
+
+pub const SALT_SIZE: usize = 16;
+/// OWASP's 2023 recommendation for PBKDF2-HMAC-SHA256.
+pub const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+/// N = 2^17, r = 8, p = 1: 128 MiB of memory per derivation.
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 17;
+pub const DEFAULT_SCRYPT_R: u32 = 8;
+pub const DEFAULT_SCRYPT_P: u32 = 1;
+
+/// A KDF together with its salt and cost. Stored in container headers so that
+/// a passphrase can be turned back into the same key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KdfParams {
+    Pbkdf2HmacSha256 { salt: Vec<u8>, iterations: u32 },
+    Scrypt { salt: Vec<u8>, log_n: u8, r: u32, p: u32 },
+}
+
+pub fn generate_salt() -> Vec<u8> {
+    let mut salt = vec![0u8; SALT_SIZE];
+    rand::rng().fill_bytes(&mut salt);
+    salt
+}
+
+/// PBKDF2 (RFC 8018, 5.2) with HMAC-SHA256 as the PRF.
+pub fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, key_len: usize) -> Vec<u8> {
+    let prf = HmacSha256::new(password);
+    let mut key = Vec::with_capacity(key_len.next_multiple_of(DIGEST_SIZE));
+
+    for block_index in 1..=key_len.div_ceil(DIGEST_SIZE) as u32 {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&block_index.to_be_bytes());
+        let mut u = mac.finalize();
+        let mut t = u;
+
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(&u);
+            u = mac.finalize();
+            t.iter_mut().zip(&u).for_each(|(t, u)| *t ^= u);
+        }
+        key.extend_from_slice(&t);
+    }
+
+    key.truncate(key_len);
+    key
+}
+
+/// scrypt (RFC 7914) with N = 2^`log_n`. Needs 128·r·N bytes of memory; the
+/// `p` lanes run in parallel.
+pub fn scrypt(
+    password: &[u8],
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+    key_len: usize,
+) -> Result<Vec<u8>, CipherError> {
+    // RFC 7914, 2: N < 2^(128·r/8) and r·p < 2^30.
+    if log_n == 0 || log_n as u64 >= 16 * r as u64 || log_n >= usize::BITS as u8 {
+        return Err(CipherError::InvalidParameter("scrypt N must be 2^k with 0 < k < 16·r"));
+    }
+    if r == 0 || p == 0 || r as u64 * p as u64 >= 1 << 30 {
+        return Err(CipherError::InvalidParameter("scrypt r and p must be positive with r·p < 2^30"));
+    }
+
+    let lane_len = 128 * r as usize;
+    let n = 1usize << log_n;
+    n.checked_mul(lane_len)
+        .filter(|&bytes| bytes <= isize::MAX as usize)
+        .ok_or(CipherError::InvalidParameter("scrypt memory cost is too large"))?;
+
+    let mut b = pbkdf2_hmac_sha256(password, salt, 1, lane_len * p as usize);
+    b.par_chunks_mut(lane_len).for_each(|lane| ro_mix(lane, n));
+    Ok(pbkdf2_hmac_sha256(password, &b, 1, key_len))
+}
+
+fn ro_mix(lane: &mut [u8], n: usize) {
+    let words = lane.len() / 4;
+    let mut x: Vec<u32> = lane
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+        .collect();
+    let mut v = Vec::with_capacity(n * words);
+    let mut scratch = vec![0u32; words];
+
+    for _ in 0..n {
+        v.extend_from_slice(&x);
+        block_mix(&mut x, &mut scratch);
+    }
+    for _ in 0..n {
+        // Integerify: the first 64 bits of the last 64-byte block, mod N.
+        let last = words - 16;
+        let j = (x[last] as u64 | (x[last + 1] as u64) << 32) as usize & (n - 1);
+        x.iter_mut().zip(&v[j * words..(j + 1) * words]).for_each(|(x, v)| *x ^= v);
+        block_mix(&mut x, &mut scratch);
+    }
+
+    for (out, word) in lane.chunks_exact_mut(4).zip(&x) {
+        out.copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+/// scryptBlockMix: even-indexed outputs first, then odd-indexed ones.
+fn block_mix(b: &mut [u32], y: &mut [u32]) {
+    let blocks = b.len() / 16;
+    let mut x: [u32; 16] = b[b.len() - 16..].try_into().unwrap();
+
+    for i in 0..blocks {
+        x.iter_mut().zip(&b[i * 16..(i + 1) * 16]).for_each(|(x, b)| *x ^= b);
+        salsa20_8(&mut x);
+        let dst = (i / 2 + (i % 2) * (blocks / 2)) * 16;
+        y[dst..dst + 16].copy_from_slice(&x);
+    }
+    b.copy_from_slice(y);
+}
+
+fn salsa20_8(block: &mut [u32; 16]) {
+    let mut x = *block;
+    let quarter = |x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize| {
+        x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+        x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+        x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+        x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+    };
+
+    for _ in 0..4 {
+        quarter(&mut x, 0, 4, 8, 12);
+        quarter(&mut x, 5, 9, 13, 1);
+        quarter(&mut x, 10, 14, 2, 6);
+        quarter(&mut x, 15, 3, 7, 11);
+        quarter(&mut x, 0, 1, 2, 3);
+        quarter(&mut x, 5, 6, 7, 4);
+        quarter(&mut x, 10, 11, 8, 9);
+        quarter(&mut x, 15, 12, 13, 14);
+    }
+
+    for (out, x) in block.iter_mut().zip(x) {
+        *out = out.wrapping_add(x);
+    }
+}
+
+/// The longest key the cipher accepts, which is what a KDF should produce.
+pub fn key_len_for<C: SymmetricCipher + ?Sized>(cipher: &C) -> Result<usize, CipherError> {
+    cipher
+        .key_sizes()
+        .iter()
+        .copied()
+        .max()
+        .ok_or(CipherError::InvalidParameter("cipher does not report its key sizes"))
+}
+
+impl KdfParams {
+    /// PBKDF2-HMAC-SHA256 with a fresh random salt.
+    pub fn pbkdf2(iterations: u32) -> Self {
+        KdfParams::Pbkdf2HmacSha256 {
+            salt: generate_salt(),
+            iterations,
+        }
+    }
+
+    /// scrypt with a fresh random salt.
+    pub fn scrypt(log_n: u8, r: u32, p: u32) -> Self {
+        KdfParams::Scrypt {
+            salt: generate_salt(),
+            log_n,
+            r,
+            p,
+        }
+    }
+
+    pub fn derive_key(&self, passphrase: &[u8], key_len: usize) -> Result<Vec<u8>, CipherError> {
+        match self {
+            KdfParams::Pbkdf2HmacSha256 { salt, iterations } => {
+                if *iterations == 0 {
+                    return Err(CipherError::InvalidParameter("PBKDF2 needs at least one iteration"));
+                }
+                Ok(pbkdf2_hmac_sha256(passphrase, salt, *iterations, key_len))
+            }
+            KdfParams::Scrypt { salt, log_n, r, p } => scrypt(passphrase, salt, *log_n, *r, *p, key_len),
+        }
+    }
+}
+
+impl Default for KdfParams {
+    /// scrypt with the default cost and a fresh random salt.
+    fn default() -> Self {
+        Self::scrypt(DEFAULT_SCRYPT_LOG_N, DEFAULT_SCRYPT_R, DEFAULT_SCRYPT_P)
+    }
+}
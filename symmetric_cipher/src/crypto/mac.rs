@@ -0,0 +1,130 @@
+use crate::crypto::aead::{check_doubling_block_size, constant_time_eq, dbl};
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
+use crate::crypto::sha256::{BLOCK_SIZE, DIGEST_SIZE, Sha256, sha256};
+
+/// CMAC / OMAC1 (NIST SP 800-38B) over any block cipher with a key already
+/// set. The tag is one cipher block long.
+#[derive(Clone)]
+pub struct Cmac<'a> {
+    cipher: &'a dyn SymmetricCipherWithRounds,
+    k1: Vec<u8>,
+    k2: Vec<u8>,
+    state: Vec<u8>,
+    /// Up to one block of input; the last block is only processed in
+    /// `finalize`, where it gets the K1/K2 treatment.
+    buffer: Vec<u8>,
+}
+
+impl<'a> Cmac<'a> {
+    /// Fails with [`CipherError::InvalidParameter`] for blocks over 32 bytes,
+    /// which have no subkey doubling constant.
+    pub fn new(cipher: &'a dyn SymmetricCipherWithRounds) -> Result<Self, CipherError> {
+        let block_size = cipher.block_size();
+        check_doubling_block_size(block_size)?;
+        let mut l = vec![0u8; block_size];
+        cipher.encrypt_block_inplace(&mut l);
+        let k1 = dbl(&l);
+        let k2 = dbl(&k1);
+
+        Ok(Self {
+            cipher,
+            k1,
+            k2,
+            state: vec![0u8; block_size],
+            buffer: Vec::with_capacity(block_size),
+        })
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        let block_size = self.state.len();
+
+        while !data.is_empty() {
+            if self.buffer.len() == block_size {
+                xor_in_place(&mut self.state, &self.buffer);
+                self.cipher.encrypt_block_inplace(&mut self.state);
+                self.buffer.clear();
+            }
+            let take = (block_size - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+        }
+    }
+
+    pub fn finalize(mut self) -> Vec<u8> {
+        let block_size = self.state.len();
+
+        let subkey = if self.buffer.len() == block_size {
+            &self.k1
+        } else {
+            self.buffer.push(0x80);
+            self.buffer.resize(block_size, 0);
+            &self.k2
+        };
+        xor_in_place(&mut self.buffer, subkey);
+        xor_in_place(&mut self.state, &self.buffer);
+        self.cipher.encrypt_block_inplace(&mut self.state);
+        self.state
+    }
+
+    /// Checks `tag` in constant time. A shorter `tag` is compared against
+    /// the leading bytes of the full one (truncated MAC).
+    pub fn verify(self, tag: &[u8]) -> Result<(), CipherError> {
+        verify_truncated(&self.finalize(), tag)
+    }
+}
+
+/// HMAC-SHA256 (RFC 2104) with the keyed inner and outer states precomputed,
+/// so that cloning it restarts the MAC without rehashing the key.
+#[derive(Clone)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    pub fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; BLOCK_SIZE];
+        if key.len() > BLOCK_SIZE {
+            block[..DIGEST_SIZE].copy_from_slice(&sha256(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Sha256::new();
+        inner.update(&block.map(|b| b ^ 0x36));
+        let mut outer = Sha256::new();
+        outer.update(&block.map(|b| b ^ 0x5c));
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; DIGEST_SIZE] {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+
+    /// Checks `tag` in constant time; see [`Cmac::verify`] for short tags.
+    pub fn verify(self, tag: &[u8]) -> Result<(), CipherError> {
+        verify_truncated(&self.finalize(), tag)
+    }
+}
+
+/// Tags shorter than 4 bytes are rejected outright rather than accepted
+/// with a trivially guessable comparison.
+fn verify_truncated(expected: &[u8], tag: &[u8]) -> Result<(), CipherError> {
+    if tag.len() < 4 || tag.len() > expected.len() || !constant_time_eq(&expected[..tag.len()], tag) {
+        return Err(CipherError::AuthenticationFailed);
+    }
+    Ok(())
+}
+
+fn xor_in_place(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
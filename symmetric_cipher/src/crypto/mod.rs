@@ -2,6 +2,7 @@ pub mod cipher_context;
 pub mod feistel_network;
 pub mod des;
 pub mod deal;
+pub mod triple_des;
 pub mod key_expansion;
 pub mod encryption_transformation;
 pub mod cipher_traits;
@@ -13,6 +14,17 @@ pub mod des_tables;
 pub mod des_transformation;
 pub mod des_key_expansion;
 pub mod deal_key_expansion;
+pub mod aead;
+pub mod cipher_error;
+pub mod cipher_stream;
+pub mod xts;
+pub mod container;
+pub mod sha256;
+pub mod kdf;
+pub mod mac;
+pub mod cipher_builder;
+pub mod registry;
+pub mod kat;
 
 use std::sync::Arc;
 use crate::crypto::key_expansion::KeyExpansion;
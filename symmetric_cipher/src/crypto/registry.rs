@@ -0,0 +1,95 @@
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
+
+pub type BoxedCipher = Box<dyn SymmetricCipherWithRounds + Send + Sync>;
+
+/// Builds a keyed cipher. The registry checks the key length against
+/// [`CipherDescriptor::key_sizes`] before calling it.
+pub type CipherConstructor = fn(&[u8]) -> Result<BoxedCipher, CipherError>;
+
+/// What a cipher crate tells the registry about one of its algorithms.
+#[derive(Clone, Copy)]
+pub struct CipherDescriptor {
+    /// Lower-case lookup name, e.g. `"twofish"`.
+    pub name: &'static str,
+    pub key_sizes: &'static [usize],
+    pub block_size: usize,
+    /// Round count for the shortest key; longer keys may use more.
+    pub default_rounds: usize,
+    pub constructor: CipherConstructor,
+}
+
+impl std::fmt::Debug for CipherDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CipherDescriptor")
+            .field("name", &self.name)
+            .field("key_sizes", &self.key_sizes)
+            .field("block_size", &self.block_size)
+            .field("default_rounds", &self.default_rounds)
+            .finish()
+    }
+}
+
+/// Name-indexed set of cipher descriptors, listed in registration order.
+#[derive(Debug, Clone, Default)]
+pub struct CipherRegistry {
+    descriptors: Vec<CipherDescriptor>,
+}
+
+impl CipherRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ciphers of this crate enabled by its `des`, `triple-des` and
+    /// `deal` features.
+    pub fn with_builtin() -> Self {
+        let builtin = [
+            (cfg!(feature = "des"), crate::crypto::des::DESCRIPTOR),
+            (cfg!(feature = "triple-des"), crate::crypto::triple_des::DESCRIPTOR),
+            (cfg!(feature = "deal"), crate::crypto::deal::DESCRIPTOR),
+        ];
+        let descriptors = builtin
+            .into_iter()
+            .filter_map(|(enabled, descriptor)| enabled.then_some(descriptor))
+            .collect();
+        CipherRegistry { descriptors }
+    }
+
+    /// Fails if an algorithm with the same name is already registered.
+    pub fn register(&mut self, descriptor: CipherDescriptor) -> Result<(), CipherError> {
+        if self.get(descriptor.name).is_some() {
+            return Err(CipherError::InvalidParameter("cipher name is already registered"));
+        }
+        self.descriptors.push(descriptor);
+        Ok(())
+    }
+
+    /// Case-insensitive lookup.
+    pub fn get(&self, name: &str) -> Option<&CipherDescriptor> {
+        self.descriptors.iter().find(|d| d.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn algorithms(&self) -> &[CipherDescriptor] {
+        &self.descriptors
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.descriptors.iter().map(|d| d.name)
+    }
+
+    /// A cipher with `key` already set.
+    pub fn create(&self, name: &str, key: &[u8]) -> Result<BoxedCipher, CipherError> {
+        let descriptor = self
+            .get(name)
+            .ok_or(CipherError::InvalidParameter("unknown cipher algorithm"))?;
+        if !descriptor.key_sizes.contains(&key.len()) {
+            return Err(CipherError::InvalidKeyLength {
+                expected: descriptor.key_sizes,
+                actual: key.len(),
+            });
+        }
+        (descriptor.constructor)(key)
+    }
+}
@@ -0,0 +1,205 @@
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
+use crate::crypto::des::DES;
+use crate::crypto::encryption_transformation::EncryptionTransformation;
+use crate::crypto::des_key_expansion::DesKeyExpansion;
+use crate::crypto::des_transformation::DesTransformation;
+use crate::crypto::key_expansion::KeyExpansion;
+use crate::crypto::registry::{BoxedCipher, CipherDescriptor};
+use std::sync::Arc;
+
+pub const DESCRIPTOR: CipherDescriptor = CipherDescriptor {
+    name: "triple-des",
+    key_sizes: &[8, 16, 24],
+    block_size: 8,
+    default_rounds: 48,
+    constructor: |key| {
+        let mut cipher = TripleDes::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+        cipher.set_key(key)?;
+        Ok(Box::new(cipher) as BoxedCipher)
+    },
+};
+
+/// The 4 weak and 12 semi-weak DES keys (FIPS 74, 3.6), with odd parity.
+const WEAK_KEYS: [[u8; 8]; 16] = [
+    [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01],
+    [0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE, 0xFE],
+    [0xE0, 0xE0, 0xE0, 0xE0, 0xF1, 0xF1, 0xF1, 0xF1],
+    [0x1F, 0x1F, 0x1F, 0x1F, 0x0E, 0x0E, 0x0E, 0x0E],
+    [0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE],
+    [0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01, 0xFE, 0x01],
+    [0x1F, 0xE0, 0x1F, 0xE0, 0x0E, 0xF1, 0x0E, 0xF1],
+    [0xE0, 0x1F, 0xE0, 0x1F, 0xF1, 0x0E, 0xF1, 0x0E],
+    [0x01, 0xE0, 0x01, 0xE0, 0x01, 0xF1, 0x01, 0xF1],
+    [0xE0, 0x01, 0xE0, 0x01, 0xF1, 0x01, 0xF1, 0x01],
+    [0x1F, 0xFE, 0x1F, 0xFE, 0x0E, 0xFE, 0x0E, 0xFE],
+    [0xFE, 0x1F, 0xFE, 0x1F, 0xFE, 0x0E, 0xFE, 0x0E],
+    [0x01, 0x1F, 0x01, 0x1F, 0x01, 0x0E, 0x01, 0x0E],
+    [0x1F, 0x01, 0x1F, 0x01, 0x0E, 0x01, 0x0E, 0x01],
+    [0xE0, 0xFE, 0xE0, 0xFE, 0xF1, 0xFE, 0xF1, 0xFE],
+    [0xFE, 0xE0, 0xFE, 0xE0, 0xFE, 0xF1, 0xFE, 0xF1],
+];
+
+/// Every byte of a DES key carries 7 key bits and an odd-parity bit (the LSB).
+pub fn has_odd_parity(key: &[u8]) -> bool {
+    key.iter().all(|b| b.count_ones() % 2 == 1)
+}
+
+/// Sets the parity bit of every byte so that `has_odd_parity` holds.
+pub fn adjust_parity(key: &mut [u8]) {
+    for b in key.iter_mut() {
+        let key_bits = *b & 0xFE;
+        *b = key_bits | ((key_bits.count_ones() as u8 & 1) ^ 1);
+    }
+}
+
+/// Weak or semi-weak single DES key; parity bits are ignored.
+pub fn is_weak_key(key: &[u8]) -> bool {
+    WEAK_KEYS
+        .iter()
+        .any(|weak| weak.iter().zip(key).all(|(w, k)| w & 0xFE == k & 0xFE))
+}
+
+/// Which of the three DES keys are independent (NIST SP 800-67, 3.1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyingOption {
+    /// 24-byte key: K1, K2, K3 all independent.
+    Option1,
+    /// 16-byte key: K3 = K1.
+    Option2,
+    /// 8-byte key: K1 = K2 = K3, which degenerates to single DES. Kept for
+    /// interoperability only.
+    Option3,
+}
+
+/// Triple DES (TDEA) in EDE form: C = E_K3(D_K2(E_K1(P))).
+pub struct TripleDes {
+    des: [DES; 3],
+    keying_option: Option<KeyingOption>,
+}
+
+impl TripleDes {
+    pub fn new(
+        key_expansion: Arc<dyn KeyExpansion + Send + Sync>,
+        transformation: Arc<dyn EncryptionTransformation + Send + Sync>,
+    ) -> Self {
+        let des = || DES::new(key_expansion.clone(), transformation.clone());
+        TripleDes {
+            des: [des(), des(), des()],
+            keying_option: None,
+        }
+    }
+
+    /// Keying option of the current key; `None` before `set_key`.
+    pub fn keying_option(&self) -> Option<KeyingOption> {
+        self.keying_option
+    }
+
+    pub fn encrypt(&self, block: &[u8]) -> Vec<u8> {
+        let [k1, k2, k3] = &self.des;
+        k3.encrypt(&k2.decrypt(&k1.encrypt(block)))
+    }
+
+    pub fn decrypt(&self, block: &[u8]) -> Vec<u8> {
+        let [k1, k2, k3] = &self.des;
+        k1.decrypt(&k2.encrypt(&k3.decrypt(block)))
+    }
+}
+
+impl CipherAlgorithm for TripleDes {
+    fn encrypt(&self, block: &[u8]) -> Vec<u8> {
+        TripleDes::encrypt(self, block)
+    }
+    fn decrypt(&self, block: &[u8]) -> Vec<u8> {
+        TripleDes::decrypt(self, block)
+    }
+}
+
+impl SymmetricCipher for TripleDes {
+    /// Rejects keys whose bytes lack odd parity (use [`adjust_parity`] for
+    /// keys from systems that ignore it), weak and semi-weak components, and
+    /// 16/24-byte keys that collapse to single DES (K1 = K2 or K2 = K3).
+    fn set_key(&mut self, key: &[u8]) -> Result<(), CipherError> {
+        let keying_option = match key.len() {
+            24 => KeyingOption::Option1,
+            16 => KeyingOption::Option2,
+            8 => KeyingOption::Option3,
+            _ => {
+                return Err(CipherError::InvalidKeyLength {
+                    expected: self.key_sizes(),
+                    actual: key.len(),
+                });
+            }
+        };
+        if !has_odd_parity(key) {
+            return Err(CipherError::InvalidParameter("DES key bytes must have odd parity"));
+        }
+
+        let k1 = &key[..8];
+        let (k2, k3) = match keying_option {
+            KeyingOption::Option1 => (&key[8..16], &key[16..24]),
+            KeyingOption::Option2 => (&key[8..16], k1),
+            KeyingOption::Option3 => (k1, k1),
+        };
+        if [k1, k2, k3].iter().any(|k| is_weak_key(k)) {
+            return Err(CipherError::InvalidParameter("weak or semi-weak DES key"));
+        }
+        if keying_option != KeyingOption::Option3 && (k1 == k2 || k2 == k3) {
+            return Err(CipherError::InvalidParameter(
+                "Triple DES key components must differ (K1 != K2, K2 != K3)",
+            ));
+        }
+
+        let [d1, d2, d3] = &mut self.des;
+        d1.set_key(k1)?;
+        d2.set_key(k2)?;
+        d3.set_key(k3)?;
+        self.keying_option = Some(keying_option);
+        Ok(())
+    }
+
+    fn key_sizes(&self) -> &'static [usize] {
+        &[8, 16, 24]
+    }
+
+    fn prepare_derived_key(&self, key: &mut [u8]) {
+        adjust_parity(key);
+    }
+}
+
+impl SymmetricCipherWithRounds for TripleDes {
+    fn set_key_with_rounds(&mut self, key: &[u8]) {
+        self.set_key(key).unwrap();
+    }
+
+    fn encrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
+        self.encrypt(data)
+    }
+
+    fn decrypt_block(&self, data: &[u8], _round_key: &[u8]) -> Vec<u8> {
+        self.decrypt(data)
+    }
+
+    fn block_size(&self) -> usize {
+        8
+    }
+
+    // EDE over the DES in-place methods, so the block never leaves `block`.
+    fn encrypt_block_inplace(&self, block: &mut [u8]) {
+        let [k1, k2, k3] = &self.des;
+        k1.encrypt_block_inplace(block);
+        k2.decrypt_block_inplace(block);
+        k3.encrypt_block_inplace(block);
+    }
+
+    fn decrypt_block_inplace(&self, block: &mut [u8]) {
+        let [k1, k2, k3] = &self.des;
+        k3.decrypt_block_inplace(block);
+        k2.encrypt_block_inplace(block);
+        k1.decrypt_block_inplace(block);
+    }
+
+    fn export_round_keys(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
@@ -1,3 +1,4 @@
+use crate::crypto::cipher_error::CipherError;
 use crate::crypto::cipher_types::PaddingMode;
 use bitvec::prelude::BitVec;
 use rand::TryRngCore;
@@ -34,6 +35,25 @@ pub fn shift_bits_little_endian(
     result
 }
 
+/// Standard bit permutation as written in FIPS 46-3: bits are numbered from 1
+/// starting at the most significant bit of the first byte, and output bit `i`
+/// is input bit `p_block[i]`.
+pub fn permute_bits(data: &[u8], p_block: &[usize]) -> Vec<u8> {
+    let mut result = vec![0u8; p_block.len().div_ceil(8)];
+    permute_bits_into(data, p_block, &mut result);
+    result
+}
+
+/// [`permute_bits`] into a caller-provided buffer of `p_block.len() / 8`
+/// bytes (rounded up); `out` must not overlap `data`.
+pub fn permute_bits_into(data: &[u8], p_block: &[usize], out: &mut [u8]) {
+    out.fill(0);
+    for (i, &pos) in p_block.iter().enumerate() {
+        let bit = (data[(pos - 1) / 8] >> (7 - (pos - 1) % 8)) & 1;
+        out[i / 8] |= bit << (7 - i % 8);
+    }
+}
+
 pub fn bytes_to_bits(input: &[u8]) -> BitVec {
     let mut bits = BitVec::with_capacity(input.len() * 8);
     for &byte in input {
@@ -59,29 +79,19 @@ pub fn bits_to_bytes(bits: &BitVec) -> Vec<u8> {
     bytes
 }
 
+/// Pads `data` to a multiple of `block_size`. PKCS7, ANSI X9.23 and ISO 10126
+/// always add at least one byte, so block-aligned input gains a whole padding block.
 pub fn apply_padding(mut data: Vec<u8>, block_size: usize, padding: PaddingMode) -> Vec<u8> {
-    let padding_length = if data.len() % block_size == 0 && !data.is_empty() {
-        if matches!(
-            padding,
-            PaddingMode::PKCS7 | PaddingMode::ANSI_X923 | PaddingMode::ISO10126
-        ) {
-            if data.len() % block_size == 0 {
+    let padding_length = block_size - (data.len() % block_size);
+
+    match padding {
+        PaddingMode::None => {}
+        PaddingMode::Zeros => {
+            if !data.is_empty() && padding_length == block_size {
                 return data;
             }
-            block_size
-        } else {
-            0
+            data.extend(vec![0; padding_length]);
         }
-    } else {
-        block_size - (data.len() % block_size)
-    };
-
-    if padding_length == 0 {
-        return data;
-    }
-
-    match padding {
-        PaddingMode::Zeros => data.extend(vec![0; padding_length]),
         PaddingMode::ANSI_X923 => {
             data.extend(vec![0; padding_length - 1]);
             data.push(padding_length as u8);
@@ -94,93 +104,45 @@ pub fn apply_padding(mut data: Vec<u8>, block_size: usize, padding: PaddingMode)
             let mut padding = vec![0u8; padding_length - 1];
             rng.try_fill_bytes(&mut padding)
                 .expect("Failed to fill ISO10126 padding");
-            data.extend(padding.into_iter());
+            data.extend(padding);
             data.push(padding_length as u8);
         }
     }
     data
 }
 
-pub fn is_full_padding_block(data: &[u8], block_size: usize, padding: &PaddingMode) -> bool {
-    if data.len() != block_size {
-        return false;
+/// Strips the padding from the last decrypted block(s). Zero padding is
+/// ambiguous and never fails; the other schemes are validated.
+pub fn remove_padding(mut data: Vec<u8>, padding: PaddingMode) -> Result<Vec<u8>, CipherError> {
+    if padding == PaddingMode::None {
+        return Ok(data);
     }
-
-    match padding {
-        PaddingMode::PKCS7 => {
-            let pad_len = data[block_size - 1] as usize;
-            pad_len == block_size && data.iter().all(|&b| b == pad_len as u8)
+    if let PaddingMode::Zeros = padding {
+        while data.last() == Some(&0u8) {
+            data.pop();
         }
-        PaddingMode::ANSI_X923 => {
-            let pad_len = data[block_size - 1] as usize;
-            pad_len == block_size && data[..block_size - 1].iter().all(|&b| b == 0)
-        }
-        PaddingMode::ISO10126 => {
-            let pad_len = data[block_size - 1] as usize;
-            pad_len == block_size // остальное может быть любым
-        }
-        _ => false
+        return Ok(data);
     }
-}
-
 
-pub fn remove_padding(mut data: Vec<u8>, padding: PaddingMode) -> Vec<u8> {
-    //eprintln!("remove_padding called on: {:?}", data);
-    let block_size = data.len();
-    let is_full_padding_block = is_full_padding_block(&data, block_size, &padding);
-
-    if is_full_padding_block {
-        return Vec::new();
+    let pad_len = *data.last().ok_or(CipherError::InvalidPadding)? as usize;
+    if pad_len == 0 || pad_len > data.len() {
+        return Err(CipherError::InvalidPadding);
     }
 
-    match padding {
-        PaddingMode::Zeros => {
-            while data.last() == Some(&0u8) {
-                data.pop();
-            }
-        }
-        PaddingMode::PKCS7 => {
-            if let Some(&last_byte) = data.last() {
-                let pad_len = last_byte as usize;
-                if pad_len == 0 || pad_len > block_size || pad_len > data.len() {
-                    //eprintln!("PKCS7: Invalid padding length: {}", pad_len);
-                    return data;
-                }
-                if data[data.len() - pad_len..].iter().all(|&b| b == last_byte) {
-                    data.truncate(data.len() - pad_len);
-                }
-            }
-        }
-        PaddingMode::ANSI_X923 => {
-            if let Some(&last_byte) = data.last() {
-                let pad_len = last_byte as usize;
-                if pad_len == 0 || pad_len > block_size || pad_len > data.len() {
-                    //eprintln!("ANSI_X923: Invalid padding length: {}", pad_len);
-                    return data;
-                }
-                let pad_region = &data[data.len() - pad_len..data.len() - 1];
-                if pad_region.iter().all(|&b| b == 0) {
-                    data.truncate(data.len() - pad_len);
-                }
-            }
-        }
-        PaddingMode::ISO10126 => {
-            if let Some(&last_byte) = data.last() {
-                let pad_len = last_byte as usize;
-                if pad_len == 0 || pad_len > block_size || pad_len > data.len() {
-                    //eprintln!("ISO10126: Invalid padding length: {}", pad_len);
-                    return data;
-                }
-                data.truncate(data.len() - pad_len);
-            }
-        }
+    let pad_region = &data[data.len() - pad_len..data.len() - 1];
+    let well_formed = match padding {
+        PaddingMode::PKCS7 => pad_region.iter().all(|&b| b as usize == pad_len),
+        PaddingMode::ANSI_X923 => pad_region.iter().all(|&b| b == 0),
+        _ => true,
+    };
+    if !well_formed {
+        return Err(CipherError::InvalidPadding);
     }
 
-    data
+    data.truncate(data.len() - pad_len);
+    Ok(data)
 }
 
-
-
 pub fn normalize_block(
     mut block: Vec<u8>,
     block_size: usize,
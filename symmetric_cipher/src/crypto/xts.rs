@@ -0,0 +1,124 @@
+use crate::crypto::cipher_error::CipherError;
+use crate::crypto::cipher_traits::SymmetricCipherWithRounds;
+use rayon::prelude::*;
+
+/// XTS is defined for 128-bit block ciphers only.
+pub const XTS_BLOCK_SIZE: usize = 16;
+
+/// Multiplies the tweak by α in GF(2^128), little-endian byte order (IEEE 1619, 5.2).
+pub fn mul_alpha(tweak: &mut [u8; XTS_BLOCK_SIZE]) {
+    let value = u128::from_le_bytes(*tweak);
+    let carry = value >> 127;
+    *tweak = ((value << 1) ^ (carry * 0x87)).to_le_bytes();
+}
+
+fn xor_in_place(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+/// C = E(P xor T) xor T, or the inverse.
+fn xex_block(
+    cipher: &dyn SymmetricCipherWithRounds,
+    block: &mut [u8],
+    tweak: &[u8; XTS_BLOCK_SIZE],
+    encrypt: bool,
+) {
+    xor_in_place(block, tweak);
+    if encrypt {
+        cipher.encrypt_block_inplace(block);
+    } else {
+        cipher.decrypt_block_inplace(block);
+    }
+    xor_in_place(block, tweak);
+}
+
+/// Encrypts or decrypts one data unit (sector). The output has the length of
+/// the input; a partial last block is handled with ciphertext stealing, so the
+/// unit must hold at least one full block.
+pub fn process_sector(
+    data_cipher: &dyn SymmetricCipherWithRounds,
+    tweak_cipher: &dyn SymmetricCipherWithRounds,
+    sector_index: u128,
+    data: &[u8],
+    encrypt: bool,
+) -> Result<Vec<u8>, CipherError> {
+    if data.len() < XTS_BLOCK_SIZE {
+        return Err(if encrypt {
+            CipherError::InvalidParameter("XTS data unit must be at least one 16-byte block")
+        } else {
+            CipherError::TruncatedCiphertext
+        });
+    }
+
+    let mut tweak = sector_index.to_le_bytes();
+    tweak_cipher.encrypt_block_inplace(&mut tweak);
+
+    let tail = data.len() % XTS_BLOCK_SIZE;
+    // With stealing, the last full block is processed together with the tail.
+    let bulk = data.len() / XTS_BLOCK_SIZE - usize::from(tail != 0);
+
+    let mut output = data.to_vec();
+
+    let mut tweaks = Vec::with_capacity(bulk * XTS_BLOCK_SIZE);
+    for _ in 0..bulk {
+        tweaks.extend_from_slice(&tweak);
+        mul_alpha(&mut tweak);
+    }
+    let region = &mut output[..bulk * XTS_BLOCK_SIZE];
+    xor_in_place(region, &tweaks);
+    if encrypt {
+        data_cipher.encrypt_blocks(region);
+    } else {
+        data_cipher.decrypt_blocks(region);
+    }
+    xor_in_place(region, &tweaks);
+
+    if tail != 0 {
+        let mut next_tweak = tweak;
+        mul_alpha(&mut next_tweak);
+        // Decryption undoes the two steps in reverse, so the tweaks swap.
+        let (first, second) = if encrypt {
+            (tweak, next_tweak)
+        } else {
+            (next_tweak, tweak)
+        };
+
+        let start = bulk * XTS_BLOCK_SIZE;
+        let (last_full, partial) = output[start..].split_at_mut(XTS_BLOCK_SIZE);
+
+        xex_block(data_cipher, last_full, &first, encrypt);
+
+        let mut stolen = [0u8; XTS_BLOCK_SIZE];
+        stolen[..tail].copy_from_slice(partial);
+        stolen[tail..].copy_from_slice(&last_full[tail..]);
+        partial.copy_from_slice(&last_full[..tail]);
+
+        xex_block(data_cipher, &mut stolen, &second, encrypt);
+        last_full.copy_from_slice(&stolen);
+    }
+
+    Ok(output)
+}
+
+/// Splits `data` into `sector_size`-byte units numbered from `first_sector`
+/// and processes them in parallel. Only the last unit may be shorter.
+pub fn process_sectors(
+    data_cipher: &(dyn SymmetricCipherWithRounds + Sync),
+    tweak_cipher: &(dyn SymmetricCipherWithRounds + Sync),
+    first_sector: u128,
+    sector_size: usize,
+    data: &[u8],
+    encrypt: bool,
+) -> Result<Vec<u8>, CipherError> {
+    let sectors = data
+        .par_chunks(sector_size)
+        .enumerate()
+        .map(|(i, sector)| {
+            process_sector(data_cipher, tweak_cipher, first_sector + i as u128, sector, encrypt)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(sectors.concat())
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use rand::{RngCore, rng};
+use symmetric_cipher::crypto::aead::{dbl, gf128_mul};
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    rng().fill_bytes(&mut buf);
+    buf
+}
+
+fn des_context(mode: CipherMode, nonce: Option<Vec<u8>>) -> CipherContext {
+    let des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+    let mut ctx = CipherContext::new(Box::new(des), mode, PaddingMode::PKCS7, nonce, Vec::new());
+    ctx.set_key(&[0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]).unwrap();
+    ctx
+}
+
+#[test]
+fn test_gf128_mul_identity() {
+    // В битовом порядке GCM единица поля — старший бит.
+    let one = 1u128 << 127;
+    let x = 0x66e94bd4ef8a2c3b884cfa59ca342b2eu128;
+    assert_eq!(gf128_mul(x, one), x);
+    assert_eq!(gf128_mul(one, x), x);
+    assert_eq!(gf128_mul(x, 0), 0);
+}
+
+#[test]
+fn test_dbl_reduces_on_carry() {
+    let mut block = vec![0u8; 16];
+    block[0] = 0x80;
+    let mut expected = vec![0u8; 16];
+    expected[15] = 0x87;
+    assert_eq!(dbl(&block), expected);
+
+    assert_eq!(dbl(&[0x00, 0x01]), vec![0x00, 0x02]);
+    // x^16 + x^5 + x^3 + x + 1 и x^96 + x^10 + x^9 + x^6 + 1
+    assert_eq!(dbl(&[0x80, 0x00]), vec![0x00, 0x2B]);
+    let mut block = vec![0u8; 12];
+    block[0] = 0x80;
+    let mut expected = vec![0u8; 12];
+    expected[10..].copy_from_slice(&[0x06, 0x41]);
+    assert_eq!(dbl(&block), expected);
+}
+
+#[test]
+fn test_gcm_requires_128_bit_block() {
+    let ctx = des_context(CipherMode::GCM, Some(random_bytes(12)));
+    let result = ctx.encrypt_authenticated(b"data", b"");
+    assert!(matches!(result, Err(CipherError::InvalidParameter(_))));
+}
+
+#[test]
+fn test_eax_requires_nonce() {
+    let ctx = des_context(CipherMode::EAX, None);
+    let result = ctx.encrypt_authenticated(b"data", b"");
+    assert!(matches!(result, Err(CipherError::InvalidIvLength { actual: 0, .. })));
+}
+
+#[tokio::test]
+async fn test_eax_des_roundtrip_through_context() {
+    for len in [0, 1, 7, 8, 9, 100, 1024] {
+        let data = random_bytes(len);
+        let mut ctx = des_context(CipherMode::EAX, Some(random_bytes(8)));
+        ctx.set_associated_data(b"message header");
+
+        let mut encrypted = CipherOutput::Buffer(Box::default());
+        ctx.encrypt(CipherInput::Bytes(data.clone()), &mut encrypted).await.unwrap();
+        let ciphertext = encrypted.as_buffer().clone();
+        assert_eq!(ciphertext.len(), len + 8, "ciphertext must carry an 8-byte tag");
+
+        let mut decrypted = CipherOutput::Buffer(Box::default());
+        ctx.decrypt(CipherInput::Bytes(ciphertext), &mut decrypted).await.unwrap();
+        assert_eq!(*decrypted.as_buffer(), data);
+    }
+}
+
+#[tokio::test]
+async fn test_eax_wrong_associated_data_is_auth_error() {
+    let mut ctx = des_context(CipherMode::EAX, Some(random_bytes(8)));
+    ctx.set_associated_data(b"header v1");
+
+    let mut encrypted = CipherOutput::Buffer(Box::default());
+    ctx.encrypt(CipherInput::Bytes(b"secret payload".to_vec()), &mut encrypted)
+        .await
+        .unwrap();
+
+    ctx.set_associated_data(b"header v2");
+    let mut decrypted = CipherOutput::Buffer(Box::default());
+    let result = ctx
+        .decrypt(CipherInput::Bytes(encrypted.as_buffer().clone()), &mut decrypted)
+        .await;
+    assert!(matches!(result, Err(CipherError::AuthenticationFailed)));
+}
+
+#[tokio::test]
+async fn test_eax_input_shorter_than_tag_is_truncated() {
+    let ctx = des_context(CipherMode::EAX, Some(random_bytes(8)));
+    let mut decrypted = CipherOutput::Buffer(Box::default());
+    let result = ctx
+        .decrypt(CipherInput::Bytes(vec![0u8; 3]), &mut decrypted)
+        .await;
+    assert!(matches!(result, Err(CipherError::TruncatedCiphertext)));
+}
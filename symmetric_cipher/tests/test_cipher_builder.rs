@@ -0,0 +1,247 @@
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_builder::{
+    BitPermutation, CipherBuilder, CustomCipher, FnKeyExpansion, FnTransformation, SBox,
+    SharedKeyExpansion, SharedTransformation,
+};
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::feistel_network::FeistelNetwork;
+use symmetric_cipher::crypto::mac::Cmac;
+
+/// S-блок и перестановка из учебника Heys, "A Tutorial on Linear and
+/// Differential Cryptanalysis".
+const HEYS_SBOX: [u8; 16] = [0xE, 0x4, 0xD, 0x1, 0x2, 0xF, 0xB, 0x8, 0x3, 0xA, 0x6, 0xC, 0x5, 0x9, 0x0, 0x7];
+const HEYS_PERMUTATION: [usize; 16] = [1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15, 4, 8, 12, 16];
+
+/// Раундовые ключи: i-й ключ — байты ключа плюс номер раунда.
+fn schedule(count: usize, len: usize) -> SharedKeyExpansion {
+    Arc::new(FnKeyExpansion(move |key: &[u8]| {
+        (0..count)
+            .map(|i| (0..len).map(|j| key[j % key.len()].wrapping_add((i as u8).wrapping_mul(31))).collect())
+            .collect()
+    }))
+}
+
+/// Нелинейная раундовая функция, длина выхода задаётся явно.
+fn round_function(out_len: usize) -> SharedTransformation {
+    Arc::new(FnTransformation(move |input: &[u8], key: &[u8]| {
+        (0..out_len)
+            .map(|i| {
+                let x = input[i % input.len()] ^ key[i % key.len()];
+                x.rotate_left(3).wrapping_mul(0x1D) ^ input[(i + 1) % input.len()]
+            })
+            .collect()
+    }))
+}
+
+fn round_trip(cipher: &CustomCipher) {
+    let plaintext: Vec<u8> = (0..cipher.block_size() as u8).map(|b| b.wrapping_mul(17)).collect();
+    let mut block = plaintext.clone();
+    cipher.encrypt_block_inplace(&mut block);
+    assert_ne!(block, plaintext);
+    cipher.decrypt_block_inplace(&mut block);
+    assert_eq!(block, plaintext);
+}
+
+#[test]
+fn test_balanced_feistel_matches_feistel_network() {
+    let key = b"builder key";
+    let mut cipher = CipherBuilder::new(8)
+        .rounds(5)
+        .key_expansion(schedule(5, 4))
+        .feistel(round_function(4))
+        .build()
+        .unwrap();
+    cipher.set_key(key).unwrap();
+
+    let network = FeistelNetwork::new(5, schedule(5, 4), round_function(4));
+    let round_keys = schedule(5, 4).generate_round_keys(key);
+    let block = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
+
+    let expected = network.encrypt_with_round_keys(&block, &round_keys);
+    assert_eq!(cipher.encrypt_block(&block, &[]), expected);
+    assert_eq!(cipher.decrypt_block(&expected, &[]), block);
+}
+
+#[test]
+fn test_unbalanced_feistel_round_trip() {
+    for source_len in [1, 3, 6, 11] {
+        let mut cipher = CipherBuilder::new(12)
+            .rounds(16)
+            .key_expansion(schedule(16, 8))
+            .unbalanced_feistel(source_len, round_function(12 - source_len))
+            .build()
+            .unwrap();
+        cipher.set_key(&[7; 8]).unwrap();
+        round_trip(&cipher);
+    }
+}
+
+#[test]
+fn test_lai_massey_round_trip() {
+    for rounds in [1, 2, 9] {
+        let mut cipher = CipherBuilder::new(16)
+            .rounds(rounds)
+            .key_sizes(&[16])
+            .key_expansion(schedule(rounds, 8))
+            .lai_massey(round_function(8))
+            .build()
+            .unwrap();
+        cipher.set_key(&[0xA5; 16]).unwrap();
+        round_trip(&cipher);
+    }
+}
+
+#[test]
+fn test_spn_heys_cipher() {
+    let build = |rounds| {
+        CipherBuilder::new(2)
+            .rounds(rounds)
+            .key_expansion(Arc::new(FnKeyExpansion(|key: &[u8]| key.chunks(2).map(<[u8]>::to_vec).collect())))
+            .spn(SBox::new(&HEYS_SBOX).unwrap(), Some(BitPermutation::new(&HEYS_PERMUTATION).unwrap()))
+            .build()
+            .unwrap()
+    };
+
+    // Один раунд с нулевыми ключами — только подстановка
+    let mut one_round = build(1);
+    one_round.set_key(&[0; 4]).unwrap();
+    assert_eq!(one_round.encrypt_block(&[0x01, 0x23], &[]), vec![0xE4, 0xD1]);
+
+    let mut cipher = build(4);
+    cipher.set_key(&hex_literal::hex!("3A94 A6D2 0C5F 1B77 E809")).unwrap();
+    round_trip(&cipher);
+    for value in 0..=u16::MAX {
+        let block = value.to_be_bytes();
+        let encrypted = cipher.encrypt_block(&block, &[]);
+        assert_eq!(cipher.decrypt_block(&encrypted, &[]), block);
+    }
+}
+
+#[tokio::test]
+async fn test_custom_cipher_in_context() {
+    let cipher = CipherBuilder::new(8)
+        .rounds(8)
+        .key_sizes(&[16])
+        .key_expansion(schedule(8, 16))
+        .unbalanced_feistel(2, round_function(6))
+        .build()
+        .unwrap();
+    let key = [0x42; 16];
+    let data: Vec<u8> = (0..=255).collect();
+
+    let mut ctx = CipherContext::new(Box::new(cipher), CipherMode::CBC, PaddingMode::PKCS7, Some(vec![9; 8]), vec![]);
+    ctx.set_key(&key).unwrap();
+
+    let mut encrypted = CipherOutput::Buffer(Box::default());
+    ctx.encrypt(CipherInput::Bytes(data.clone()), &mut encrypted).await.unwrap();
+    let mut decrypted = CipherOutput::Buffer(Box::default());
+    ctx.decrypt(CipherInput::Bytes(encrypted.as_buffer().clone()), &mut decrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted.as_buffer(), &data);
+}
+
+#[test]
+fn test_builder_rejects_bad_configurations() {
+    assert!(matches!(SBox::new(&[0; 16]), Err(CipherError::InvalidParameter(_))));
+    assert!(matches!(SBox::new(&[1, 2, 3]), Err(CipherError::InvalidParameter(_))));
+    assert!(matches!(BitPermutation::new(&[1, 1, 2]), Err(CipherError::InvalidParameter(_))));
+
+    let invalid = [
+        CipherBuilder::new(8).key_expansion(schedule(4, 4)).feistel(round_function(4)),
+        CipherBuilder::new(8).rounds(4).feistel(round_function(4)),
+        CipherBuilder::new(8).rounds(4).key_expansion(schedule(4, 4)),
+        CipherBuilder::new(8).rounds(4).key_expansion(schedule(4, 4)).unbalanced_feistel(8, round_function(1)),
+        CipherBuilder::new(6).rounds(4).key_expansion(schedule(4, 3)).lai_massey(round_function(3)),
+        CipherBuilder::new(4)
+            .rounds(4)
+            .key_expansion(schedule(5, 4))
+            .spn(SBox::new(&HEYS_SBOX).unwrap(), Some(BitPermutation::new(&HEYS_PERMUTATION).unwrap())),
+    ];
+    for builder in invalid {
+        assert!(matches!(builder.build(), Err(CipherError::InvalidParameter(_))));
+    }
+
+    let mut cipher = CipherBuilder::new(2)
+        .rounds(4)
+        .key_sizes(&[10])
+        .key_expansion(schedule(4, 2))
+        .spn(SBox::new(&HEYS_SBOX).unwrap(), None)
+        .build()
+        .unwrap();
+    assert!(matches!(cipher.set_key(&[0; 8]), Err(CipherError::InvalidKeyLength { actual: 8, .. })));
+    assert!(matches!(cipher.set_key(&[0; 10]), Err(CipherError::InvalidParameter(_))), "SPN needs rounds + 1 keys");
+}
+
+#[tokio::test]
+async fn test_unkeyed_custom_cipher_is_an_error() {
+    let cipher = CipherBuilder::new(8)
+        .rounds(4)
+        .key_expansion(schedule(4, 4))
+        .feistel(round_function(4))
+        .build()
+        .unwrap();
+    assert!(matches!(cipher.check_key(), Err(CipherError::InvalidParameter(_))));
+    assert_eq!(cipher.export_round_keys(), None);
+
+    // Контекст без set_key возвращает ошибку вместо паники
+    for mode in [CipherMode::ECB, CipherMode::CBC, CipherMode::CTR] {
+        let ctx = CipherContext::new(Box::new(cipher.clone()), mode, PaddingMode::PKCS7, Some(vec![0; 8]), vec![]);
+        let mut out = CipherOutput::Buffer(Box::default());
+        let result = ctx.encrypt(CipherInput::Bytes(vec![1; 16]), &mut out).await;
+        assert!(matches!(result, Err(CipherError::InvalidParameter(_))), "{:?}", mode);
+        let result = ctx.decrypt(CipherInput::Bytes(vec![1; 16]), &mut out).await;
+        assert!(matches!(result, Err(CipherError::InvalidParameter(_))), "{:?}", mode);
+        assert!(ctx.encryptor().is_err());
+    }
+}
+
+#[test]
+fn test_eax_and_cmac_for_any_block_size() {
+    let feistel = |block_size: usize, key: u8| {
+        let mut cipher = CipherBuilder::new(block_size)
+            .rounds(4)
+            .key_expansion(schedule(4, block_size / 2))
+            .feistel(round_function(block_size / 2))
+            .build()
+            .unwrap();
+        cipher.set_key(&[key; 16]).unwrap();
+        cipher
+    };
+    let data: Vec<u8> = (0..100).collect();
+
+    for block_size in [2, 12] {
+        // Ключи с установленным старшим битом L = E_K(0) проходят через
+        // редукцию в dbl — для таких блоков раньше была паника
+        let mut carries = 0;
+        for key in 0..16 {
+            let cipher = feistel(block_size, key);
+            let mut l = vec![0u8; block_size];
+            cipher.encrypt_block_inplace(&mut l);
+            carries += l[0] >> 7;
+            assert!(Cmac::new(&cipher).is_ok());
+
+            let nonce = vec![key; block_size];
+            let ctx = CipherContext::new(Box::new(cipher), CipherMode::EAX, PaddingMode::PKCS7, Some(nonce), vec![]);
+            let (ciphertext, mut tag) = ctx.encrypt_authenticated(&data, b"header").unwrap();
+            assert_eq!(tag.len(), block_size);
+            assert_eq!(ctx.decrypt_authenticated(&ciphertext, b"header", &tag).unwrap(), data);
+            tag[0] ^= 1;
+            assert!(matches!(
+                ctx.decrypt_authenticated(&ciphertext, b"header", &tag),
+                Err(CipherError::AuthenticationFailed)
+            ));
+        }
+        assert!(carries > 0 && carries < 16, "{} of 16 keys carried", carries);
+    }
+
+    // Для блоков длиннее 32 байт нет константы удвоения
+    let cipher = feistel(40, 1);
+    assert!(matches!(Cmac::new(&cipher), Err(CipherError::InvalidParameter(_))));
+    let ctx = CipherContext::new(Box::new(cipher), CipherMode::EAX, PaddingMode::PKCS7, Some(vec![1; 40]), vec![]);
+    assert!(matches!(ctx.encrypt_authenticated(&data, b""), Err(CipherError::InvalidParameter(_))));
+    assert!(matches!(ctx.encryptor(), Err(CipherError::InvalidParameter(_))));
+}
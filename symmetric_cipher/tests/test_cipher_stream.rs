@@ -0,0 +1,278 @@
+use rand::{RngCore, rng};
+use std::io::Read;
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+use tokio::io::AsyncWriteExt;
+
+// GCM требует 128-битного блока, поэтому с DES проверяется только EAX.
+const ALL_MODES: [CipherMode; 8] = [
+    CipherMode::ECB,
+    CipherMode::CBC,
+    CipherMode::PCBC,
+    CipherMode::CFB,
+    CipherMode::OFB,
+    CipherMode::CTR,
+    CipherMode::RandomDelta,
+    CipherMode::EAX,
+];
+
+/// Отдаёт данные порциями разной длины, чтобы границы чтения не совпадали с блоками.
+struct RaggedReader {
+    data: Vec<u8>,
+    pos: usize,
+    step: usize,
+}
+
+impl Read for RaggedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.step = self.step % 13 + 1;
+        let n = self.step.min(buf.len()).min(self.data.len() - self.pos);
+        buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+fn ragged(data: &[u8]) -> RaggedReader {
+    RaggedReader { data: data.to_vec(), pos: 0, step: 0 }
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    rng().fill_bytes(&mut buf);
+    buf
+}
+
+fn des_context(mode: CipherMode) -> CipherContext {
+    let des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+    let mut ctx = CipherContext::new(
+        Box::new(des),
+        mode,
+        PaddingMode::PKCS7,
+        Some(vec![0x5A; 8]),
+        Vec::new(),
+    );
+    ctx.set_key(&[0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]).unwrap();
+    ctx
+}
+
+#[tokio::test]
+async fn test_stream_matches_one_shot_for_all_modes() {
+    for mode in ALL_MODES {
+        for len in [0, 5, 8, 61, 200] {
+            let ctx = des_context(mode);
+            let data = random_bytes(len);
+
+            let mut expected = CipherOutput::Buffer(Box::default());
+            ctx.encrypt(CipherInput::Bytes(data.clone()), &mut expected).await.unwrap();
+
+            let mut streamed = Vec::new();
+            ctx.encryptor()
+                .unwrap()
+                .copy(ragged(&data), &mut streamed)
+                .unwrap();
+            assert_eq!(&streamed, expected.as_buffer(), "mode {:?} len {}", mode, len);
+
+            let mut decrypted = Vec::new();
+            ctx.decryptor()
+                .unwrap()
+                .copy(ragged(&streamed), &mut decrypted)
+                .unwrap();
+            assert_eq!(decrypted, data, "mode {:?} len {}", mode, len);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_update_finalize_chains_across_pieces() {
+    for mode in ALL_MODES {
+        let ctx = des_context(mode);
+        let data = random_bytes(333);
+
+        let mut expected = CipherOutput::Buffer(Box::default());
+        ctx.encrypt(CipherInput::Bytes(data.clone()), &mut expected).await.unwrap();
+
+        // Одно сообщение, разбитое на куски произвольной длины, как файл в мессенджере.
+        let mut encryptor = ctx.encryptor().unwrap();
+        let mut ciphertext = Vec::new();
+        for piece in data.chunks(37) {
+            ciphertext.extend(encryptor.update(piece));
+        }
+        ciphertext.extend(encryptor.finalize().unwrap());
+        assert_eq!(&ciphertext, expected.as_buffer(), "mode {:?}", mode);
+
+        let mut decryptor = ctx.decryptor().unwrap();
+        let mut decrypted = Vec::new();
+        for piece in ciphertext.chunks(50) {
+            decrypted.extend(decryptor.update(piece));
+        }
+        decrypted.extend(decryptor.finalize().unwrap());
+        assert_eq!(decrypted, data, "mode {:?}", mode);
+    }
+}
+
+#[test]
+fn test_decryptor_holds_back_padding_block() {
+    let ctx = des_context(CipherMode::CBC);
+    let mut encryptor = ctx.encryptor().unwrap();
+    let mut ciphertext = encryptor.update(&[7u8; 16]);
+    assert_eq!(ciphertext.len(), 16);
+    ciphertext.extend(encryptor.finalize().unwrap());
+    assert_eq!(ciphertext.len(), 24);
+
+    let mut decryptor = ctx.decryptor().unwrap();
+    assert_eq!(decryptor.update(&ciphertext), vec![7u8; 16]);
+    assert!(decryptor.finalize().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_async_stream_through_duplex_pipe() {
+    let ctx = des_context(CipherMode::CBC);
+    let data = random_bytes(3 * 1024 + 3);
+
+    // Небольшой буфер канала заставляет шифратор работать порциями.
+    let (mut plain_tx, plain_rx) = tokio::io::duplex(64);
+    let source = data.clone();
+    let producer = tokio::spawn(async move {
+        plain_tx.write_all(&source).await.unwrap();
+    });
+
+    let mut ciphertext = Vec::new();
+    ctx.encryptor()
+        .unwrap()
+        .copy_async(plain_rx, &mut ciphertext)
+        .await
+        .unwrap();
+    producer.await.unwrap();
+
+    let mut decrypted = Vec::new();
+    ctx.decryptor()
+        .unwrap()
+        .copy_async(ciphertext.as_slice(), &mut decrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted, data);
+}
+
+#[test]
+fn test_stream_reports_truncated_ciphertext() {
+    let ctx = des_context(CipherMode::CBC);
+    let mut ciphertext = Vec::new();
+    ctx.encryptor()
+        .unwrap()
+        .copy(ragged(b"some plaintext"), &mut ciphertext)
+        .unwrap();
+    ciphertext.pop();
+
+    let result = ctx.decryptor()
+        .unwrap()
+        .copy(ragged(&ciphertext), &mut Vec::new());
+    assert!(matches!(result, Err(CipherError::TruncatedCiphertext)));
+}
+
+#[test]
+fn test_stream_rejects_bad_iv_up_front() {
+    let des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+    let ctx = CipherContext::new(
+        Box::new(des),
+        CipherMode::CBC,
+        PaddingMode::PKCS7,
+        Some(vec![0; 3]),
+        Vec::new(),
+    );
+
+    assert!(matches!(
+        ctx.encryptor(),
+        Err(CipherError::InvalidIvLength { expected: 8, actual: 3 })
+    ));
+}
+
+#[tokio::test]
+async fn test_read_write_adapters_match_one_shot() {
+    for mode in ALL_MODES {
+        let ctx = des_context(mode);
+        let data = random_bytes(333);
+
+        let mut expected = CipherOutput::Buffer(Box::default());
+        ctx.encrypt(CipherInput::Bytes(data.clone()), &mut expected).await.unwrap();
+
+        // Write: запись кусками, хвост дописывает finish
+        let mut writer = ctx.encryptor().unwrap().writer(Vec::new());
+        for piece in data.chunks(37) {
+            std::io::Write::write_all(&mut writer, piece).unwrap();
+        }
+        let ciphertext = writer.finish().unwrap();
+        assert_eq!(&ciphertext, expected.as_buffer(), "mode {:?}", mode);
+
+        // Read: чтение маленьким буфером из рваного источника
+        let mut reader = ctx.decryptor().unwrap().reader(ragged(&ciphertext));
+        let mut decrypted = Vec::new();
+        let mut buf = [0u8; 5];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            decrypted.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(decrypted, data, "mode {:?}", mode);
+    }
+}
+
+#[tokio::test]
+async fn test_async_read_write_adapters() {
+    use tokio::io::AsyncReadExt;
+
+    for mode in [CipherMode::CBC, CipherMode::CTR, CipherMode::EAX] {
+        let ctx = des_context(mode);
+        let data = random_bytes(3 * 1024 + 3);
+
+        // Шифратор пишет в канал, расшифровщик читает из другого конца.
+        let (cipher_tx, cipher_rx) = tokio::io::duplex(64);
+        let mut writer = ctx.encryptor().unwrap().async_writer(cipher_tx);
+        let source = data.clone();
+        let producer = tokio::spawn(async move {
+            for piece in source.chunks(100) {
+                writer.write_all(piece).await.unwrap();
+            }
+            writer.shutdown().await.unwrap();
+        });
+
+        let mut reader = ctx.decryptor().unwrap().async_reader(cipher_rx);
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).await.unwrap();
+        producer.await.unwrap();
+        assert_eq!(decrypted, data, "mode {:?}", mode);
+    }
+}
+
+#[test]
+fn test_authenticated_stream_buffer_is_limited() {
+    let ctx = des_context(CipherMode::EAX);
+
+    let mut encryptor = ctx.encryptor().unwrap();
+    encryptor.set_buffer_limit(100);
+    assert!(encryptor.try_update(&[0; 60]).unwrap().is_empty());
+    assert!(matches!(encryptor.try_update(&[0; 60]), Err(CipherError::InvalidParameter(_))));
+
+    // update не может вернуть ошибку сразу, её сообщает finalize
+    let mut encryptor = ctx.encryptor().unwrap();
+    encryptor.set_buffer_limit(100);
+    encryptor.update(&[0; 101]);
+    assert!(matches!(encryptor.finalize(), Err(CipherError::InvalidParameter(_))));
+
+    let mut encryptor = ctx.encryptor().unwrap();
+    encryptor.set_buffer_limit(100);
+    let result = encryptor.copy(ragged(&[0; 200]), &mut Vec::new());
+    assert!(matches!(result, Err(CipherError::InvalidParameter(_))));
+
+    // Остальные режимы держат лишь несколько блоков и предел не учитывают
+    let mut encryptor = des_context(CipherMode::CBC).encryptor().unwrap();
+    encryptor.set_buffer_limit(1);
+    assert_eq!(encryptor.try_update(&[0; 200]).unwrap().len(), 200);
+}
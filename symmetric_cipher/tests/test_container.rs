@@ -0,0 +1,239 @@
+use rand::{RngCore, rng};
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::SymmetricCipherWithRounds;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::container::{
+    AlgorithmId, ContainerHeader, ContainerReader, ContainerWriter, KdfParams, MAX_PBKDF2_ITERATIONS, MAX_SCRYPT_P,
+    VERSION, builtin_algorithm,
+};
+use tempfile::NamedTempFile;
+
+const DES_KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    rng().fill_bytes(&mut buf);
+    buf
+}
+
+async fn seal(header: ContainerHeader, key: &[u8], data: &[u8]) -> Vec<u8> {
+    let writer = ContainerWriter::new(header, key, &builtin_algorithm).unwrap();
+    let mut out = CipherOutput::Buffer(Box::default());
+    writer.encrypt(CipherInput::Bytes(data.to_vec()), &mut out).await.unwrap();
+    out.as_buffer().clone()
+}
+
+async fn open(container: Vec<u8>, key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let reader = ContainerReader::open(CipherInput::Bytes(container)).await?;
+    let mut out = CipherOutput::Buffer(Box::default());
+    reader.decrypt(key, &builtin_algorithm, &mut out).await?;
+    Ok(out.as_buffer().clone())
+}
+
+#[test]
+fn test_header_round_trip() {
+    let mut header = ContainerHeader::new(
+        AlgorithmId::Rijndael { block_size: 24, poly: 0x11B },
+        CipherMode::CBC_CS3,
+        PaddingMode::ISO10126,
+        Some(random_bytes(24)),
+    );
+    header.kdf = Some(KdfParams::Scrypt { salt: random_bytes(16), log_n: 14, r: 8, p: 1 });
+
+    let mut bytes = header.to_bytes().unwrap();
+    let header_len = bytes.len();
+    bytes.extend_from_slice(b"ciphertext");
+
+    let (parsed, len) = ContainerHeader::parse(&bytes).unwrap();
+    assert_eq!(parsed, header);
+    assert_eq!(len, header_len);
+}
+
+#[tokio::test]
+async fn test_container_configures_context_from_header() {
+    let data = random_bytes(250);
+    let cases = [
+        (AlgorithmId::Des, CipherMode::CBC, PaddingMode::PKCS7),
+        (AlgorithmId::Des, CipherMode::CTR, PaddingMode::Zeros),
+        (AlgorithmId::Des, CipherMode::EAX, PaddingMode::PKCS7),
+        (AlgorithmId::Deal, CipherMode::CBC_CS1, PaddingMode::PKCS7),
+    ];
+
+    for (algorithm, mode, padding) in cases {
+        let (iv, key) = match algorithm {
+            AlgorithmId::Deal => (random_bytes(16), random_bytes(24)),
+            _ => (random_bytes(8), DES_KEY.to_vec()),
+        };
+        let header = ContainerHeader::new(algorithm, mode, padding, Some(iv));
+        let container = seal(header.clone(), &key, &data).await;
+
+        let reader = ContainerReader::open(CipherInput::Bytes(container.clone())).await.unwrap();
+        assert_eq!(reader.header().mode, mode);
+        assert_eq!(reader.header().iv, header.iv);
+        assert_eq!(open(container, &key).await.unwrap(), data, "{:?} {:?}", algorithm, mode);
+    }
+}
+
+#[tokio::test]
+async fn test_container_file_round_trip() {
+    let data = random_bytes(3 * 1024 + 5);
+    let plain = NamedTempFile::new().unwrap();
+    std::fs::write(plain.path(), &data).unwrap();
+    let sealed = NamedTempFile::new().unwrap();
+    let restored = NamedTempFile::new().unwrap();
+    let path = |f: &NamedTempFile| f.path().to_str().unwrap().to_string();
+
+    let header = ContainerHeader::new(AlgorithmId::Des, CipherMode::CBC, PaddingMode::PKCS7, Some(random_bytes(8)));
+    let writer = ContainerWriter::new(header, &DES_KEY, &builtin_algorithm).unwrap();
+    writer
+        .encrypt(CipherInput::File(path(&plain)), &mut CipherOutput::File(path(&sealed)))
+        .await
+        .unwrap();
+
+    // Файл-контейнер читается и целиком из памяти, и потоково из файла.
+    let container = std::fs::read(sealed.path()).unwrap();
+    assert_eq!(open(container, &DES_KEY).await.unwrap(), data);
+
+    let reader = ContainerReader::open(CipherInput::File(path(&sealed))).await.unwrap();
+    reader
+        .decrypt(&DES_KEY, &builtin_algorithm, &mut CipherOutput::File(path(&restored)))
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(restored.path()).unwrap(), data);
+}
+
+#[tokio::test]
+async fn test_container_rejects_bad_headers() {
+    let header = ContainerHeader::new(AlgorithmId::Des, CipherMode::CBC, PaddingMode::PKCS7, Some(random_bytes(8)));
+    let container = seal(header, &DES_KEY, b"attack at dawn").await;
+
+    let mut wrong_magic = container.clone();
+    wrong_magic[0] ^= 1;
+    assert!(matches!(open(wrong_magic, &DES_KEY).await, Err(CipherError::InvalidContainer(_))));
+
+    let mut future_version = container.clone();
+    future_version[4] = VERSION + 1;
+    assert!(matches!(open(future_version, &DES_KEY).await, Err(CipherError::InvalidContainer(_))));
+
+    // Подмена режима ловится контрольной суммой.
+    let mut tampered = container.clone();
+    tampered[8] ^= 1;
+    assert!(matches!(open(tampered, &DES_KEY).await, Err(CipherError::InvalidContainer(_))));
+
+    assert!(matches!(open(container[..5].to_vec(), &DES_KEY).await, Err(CipherError::InvalidContainer(_))));
+}
+
+#[tokio::test]
+async fn test_header_mac_detects_forged_header() {
+    let header = ContainerHeader::new(AlgorithmId::Des, CipherMode::CBC, PaddingMode::PKCS7, Some(random_bytes(8)));
+    let container = seal(header, &DES_KEY, b"attack at dawn").await;
+    let (parsed, header_len) = ContainerHeader::parse(&container).unwrap();
+    assert_eq!(open(container.clone(), &DES_KEY).await.unwrap(), b"attack at dawn");
+
+    // CRC-32 пересчитывается кем угодно: подмена IV, режима или padding
+    // с верной контрольной суммой ловится только MAC заголовка.
+    let forgeries = [
+        ContainerHeader { iv: Some(vec![0; 8]), ..parsed.clone() },
+        ContainerHeader { mode: CipherMode::CFB, ..parsed.clone() },
+        ContainerHeader { padding: PaddingMode::Zeros, ..parsed.clone() },
+    ];
+    for forged_header in forgeries {
+        let mut forged = forged_header.to_bytes().unwrap();
+        forged.extend_from_slice(&container[header_len..]);
+        assert!(matches!(open(forged, &DES_KEY).await, Err(CipherError::AuthenticationFailed)));
+    }
+
+    let wrong_key = [0x0E, 0x32, 0x92, 0x32, 0xEA, 0x6D, 0x0D, 0x73];
+    assert!(matches!(open(container.clone(), &wrong_key).await, Err(CipherError::AuthenticationFailed)));
+
+    let truncated = container[..header_len + 10].to_vec();
+    assert!(matches!(open(truncated, &DES_KEY).await, Err(CipherError::InvalidContainer(_))));
+}
+
+#[tokio::test]
+async fn test_authenticated_container_detects_forged_header() {
+    let header = ContainerHeader::new(AlgorithmId::Des, CipherMode::EAX, PaddingMode::PKCS7, Some(random_bytes(8)));
+    let container = seal(header.clone(), &DES_KEY, b"attack at dawn").await;
+    let (parsed, header_len) = ContainerHeader::parse(&container).unwrap();
+    assert_eq!(parsed.tag_len, 8);
+
+    // Заголовок пересобран с верной контрольной суммой, но другой солью:
+    // тег EAX покрывает заголовок, так что подделка всё равно видна.
+    let mut forged_header = parsed;
+    forged_header.kdf = Some(KdfParams::Pbkdf2HmacSha256 { salt: vec![0; 16], iterations: 1 });
+    let mut forged = forged_header.to_bytes().unwrap();
+    forged.extend_from_slice(&container[header_len..]);
+    assert!(matches!(open(forged, &DES_KEY).await, Err(CipherError::AuthenticationFailed)));
+}
+
+#[tokio::test]
+async fn test_kdf_parameters_are_bounded() {
+    // Заголовок с корректной контрольной суммой, но с параметрами KDF, которые
+    // потребовали бы терабайт памяти или часы вычислений.
+    let oversized = [
+        KdfParams::Pbkdf2HmacSha256 { salt: random_bytes(16), iterations: MAX_PBKDF2_ITERATIONS + 1 },
+        KdfParams::Pbkdf2HmacSha256 { salt: random_bytes(16), iterations: u32::MAX },
+        KdfParams::Scrypt { salt: random_bytes(16), log_n: 32, r: 8, p: 1 },
+        KdfParams::Scrypt { salt: random_bytes(16), log_n: 255, r: 8, p: 1 },
+        KdfParams::Scrypt { salt: random_bytes(16), log_n: 14, r: u32::MAX, p: 1 },
+        KdfParams::Scrypt { salt: random_bytes(16), log_n: 14, r: 8, p: MAX_SCRYPT_P + 1 },
+    ];
+    for kdf in oversized {
+        let mut header = ContainerHeader::new(AlgorithmId::Des, CipherMode::CBC, PaddingMode::PKCS7, Some(random_bytes(8)));
+        header.kdf = Some(kdf.clone());
+        let bytes = header.to_bytes().unwrap();
+        assert!(matches!(ContainerHeader::parse(&bytes), Err(CipherError::InvalidContainer(_))), "{:?}", kdf);
+        assert!(matches!(
+            ContainerWriter::with_passphrase(header, b"pw", &builtin_algorithm),
+            Err(CipherError::InvalidParameter(_))
+        ));
+    }
+
+    // Значения по умолчанию и граничные значения проходят.
+    for kdf in [
+        KdfParams::default(),
+        KdfParams::pbkdf2(MAX_PBKDF2_ITERATIONS),
+        KdfParams::scrypt(20, 8, 1),
+    ] {
+        let mut header = ContainerHeader::new(AlgorithmId::Des, CipherMode::CBC, PaddingMode::PKCS7, Some(random_bytes(8)));
+        header.kdf = Some(kdf);
+        assert!(ContainerHeader::parse(&header.to_bytes().unwrap()).is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_unavailable_algorithm() {
+    let header = ContainerHeader::new(AlgorithmId::Twofish, CipherMode::ECB, PaddingMode::PKCS7, None);
+    assert!(matches!(
+        ContainerWriter::new(header, &[0; 16], &builtin_algorithm),
+        Err(CipherError::InvalidContainer(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_invalid_algorithm_is_rejected_before_resolving() {
+    // Заголовок ещё не проверен MAC, поэтому недопустимые параметры Rijndael
+    // отвергаются до вызова resolver: x^8 + 1 приводим, 0x11B с блоком 17 байт.
+    let panicking = |_: AlgorithmId| -> Option<Box<dyn SymmetricCipherWithRounds + Send + Sync>> {
+        panic!("resolver called for an invalid algorithm")
+    };
+    for algorithm in [
+        AlgorithmId::Rijndael { block_size: 16, poly: 0x101 },
+        AlgorithmId::Rijndael { block_size: 16, poly: 0x1B },
+        AlgorithmId::Rijndael { block_size: 17, poly: 0x11B },
+    ] {
+        let mut header = ContainerHeader::new(algorithm, CipherMode::CBC, PaddingMode::PKCS7, Some(random_bytes(16)));
+        header.kdf = Some(KdfParams::pbkdf2(1));
+        let mut bytes = header.to_bytes().unwrap();
+        assert!(matches!(ContainerHeader::parse(&bytes), Err(CipherError::InvalidContainer(_))), "{:?}", algorithm);
+        bytes.extend_from_slice(&random_bytes(64));
+        assert!(matches!(
+            ContainerReader::open(CipherInput::Bytes(bytes)).await,
+            Err(CipherError::InvalidContainer(_))
+        ));
+        assert!(matches!(
+            ContainerWriter::with_passphrase(header, b"pw", &panicking),
+            Err(CipherError::InvalidContainer(_))
+        ));
+    }
+}
@@ -0,0 +1,119 @@
+use rand::{RngCore, rng};
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+
+const BLOCK: usize = 8;
+const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+const IV: [u8; 8] = [0xA0, 0xB1, 0xC2, 0xD3, 0xE4, 0xF5, 0x06, 0x17];
+
+fn des_context(mode: CipherMode) -> CipherContext {
+    let mut ctx = CipherContext::new(
+        Box::new(DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation))),
+        mode,
+        PaddingMode::PKCS7,
+        Some(IV.to_vec()),
+        Vec::new(),
+    );
+    ctx.set_key(&KEY).unwrap();
+    ctx
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    rng().fill_bytes(&mut buf);
+    buf
+}
+
+async fn encrypt(ctx: &CipherContext, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let mut out = CipherOutput::Buffer(Box::default());
+    ctx.encrypt(CipherInput::Bytes(data.to_vec()), &mut out).await?;
+    Ok(out.as_buffer().clone())
+}
+
+async fn decrypt(ctx: &CipherContext, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let mut out = CipherOutput::Buffer(Box::default());
+    ctx.decrypt(CipherInput::Bytes(data.to_vec()), &mut out).await?;
+    Ok(out.as_buffer().clone())
+}
+
+#[tokio::test]
+async fn test_cts_round_trip_keeps_length() {
+    for mode in [CipherMode::CBC_CS1, CipherMode::CBC_CS2, CipherMode::CBC_CS3] {
+        let ctx = des_context(mode);
+        for len in BLOCK..=5 * BLOCK {
+            let data = random_bytes(len);
+            let ciphertext = encrypt(&ctx, &data).await.unwrap();
+            assert_eq!(ciphertext.len(), len, "mode {:?} len {}", mode, len);
+            assert_eq!(decrypt(&ctx, &ciphertext).await.unwrap(), data, "mode {:?} len {}", mode, len);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_cts_variants_differ_only_in_last_blocks_order() {
+    let cs1 = des_context(CipherMode::CBC_CS1);
+    let cs2 = des_context(CipherMode::CBC_CS2);
+    let cs3 = des_context(CipherMode::CBC_CS3);
+
+    // Длина кратна блоку: CS1 и CS2 совпадают с обычным CBC без дополнения,
+    // CS3 меняет местами два последних блока.
+    let data = random_bytes(4 * BLOCK);
+    let plain_cbc = encrypt(&des_context(CipherMode::CBC), &data).await.unwrap();
+    let c1 = encrypt(&cs1, &data).await.unwrap();
+    assert_eq!(c1, plain_cbc[..data.len()]);
+    assert_eq!(encrypt(&cs2, &data).await.unwrap(), c1);
+    let c3 = encrypt(&cs3, &data).await.unwrap();
+    assert_eq!(c3[..2 * BLOCK], c1[..2 * BLOCK]);
+    assert_eq!(c3[2 * BLOCK..3 * BLOCK], c1[3 * BLOCK..]);
+    assert_eq!(c3[3 * BLOCK..], c1[2 * BLOCK..3 * BLOCK]);
+
+    // Неполный последний блок: CS2 и CS3 совпадают, обрезанный
+    // предпоследний блок у них стоит в конце.
+    let data = random_bytes(3 * BLOCK + 3);
+    let c1 = encrypt(&cs1, &data).await.unwrap();
+    let c2 = encrypt(&cs2, &data).await.unwrap();
+    assert_eq!(encrypt(&cs3, &data).await.unwrap(), c2);
+    let head = 2 * BLOCK;
+    assert_eq!(c2[..head], c1[..head]);
+    assert_eq!(c2[head..head + BLOCK], c1[head + 3..]);
+    assert_eq!(c2[head + BLOCK..], c1[head..head + 3]);
+}
+
+#[test]
+fn test_cts_streaming_matches_one_shot() {
+    let ctx = des_context(CipherMode::CBC_CS3);
+    let data = random_bytes(20 * BLOCK + 5);
+
+    let mut encryptor = ctx.encryptor().unwrap();
+    let mut streamed = Vec::new();
+    for piece in data.chunks(3) {
+        streamed.extend(encryptor.update(piece));
+    }
+    streamed.extend(encryptor.finalize().unwrap());
+
+    let mut encryptor = ctx.encryptor().unwrap();
+    let mut whole = encryptor.update(&data);
+    whole.extend(encryptor.finalize().unwrap());
+    assert_eq!(streamed, whole);
+
+    let mut decryptor = ctx.decryptor().unwrap();
+    let mut decrypted = Vec::new();
+    for piece in streamed.chunks(11) {
+        decrypted.extend(decryptor.update(piece));
+    }
+    decrypted.extend(decryptor.finalize().unwrap());
+    assert_eq!(decrypted, data);
+}
+
+#[tokio::test]
+async fn test_cts_rejects_input_shorter_than_block() {
+    let ctx = des_context(CipherMode::CBC_CS1);
+    assert!(matches!(encrypt(&ctx, &[1, 2, 3]).await, Err(CipherError::InvalidParameter(_))));
+    assert!(matches!(decrypt(&ctx, &[1, 2, 3]).await, Err(CipherError::TruncatedCiphertext)));
+    assert_eq!(encrypt(&ctx, &[]).await.unwrap(), Vec::<u8>::new());
+}
@@ -1,13 +1,30 @@
-use symmetric_cipher::crypto::cipher_traits::SymmetricCipher;
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::deal::{DEAL, DealKeyExpansion};
 use symmetric_cipher::crypto::des::DES;
 use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
 use symmetric_cipher::crypto::des_transformation::DesTransformation;
+use symmetric_cipher::crypto::key_expansion::KeyExpansion;
+
+fn des() -> DES {
+    DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation))
+}
+
+fn des_with_key(key: &[u8]) -> DES {
+    let mut des = des();
+    des.set_key(key).unwrap();
+    des
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
 
 #[test]
 fn test_deal_encrypt_decrypt() {
-    use symmetric_cipher::crypto::deal::DEAL;
-    use symmetric_cipher::crypto::des::DES;
-
     let key = hex_literal::hex!(
         "13 34 57 79 9B BC DF F1
          13 34 57 79 9B BC DF F1
@@ -15,16 +32,108 @@ fn test_deal_encrypt_decrypt() {
     );
     let plaintext = hex_literal::hex!("01 23 45 67 89 AB CD EF");
 
-    let des = DES::new(
-        std::sync::Arc::new(DesKeyExpansion),
-        std::sync::Arc::new(DesTransformation),
-    );
-
-    let mut deal = DEAL::new(des);
+    let mut deal = DEAL::new_toy();
     deal.set_key(&key).unwrap();
+    assert_eq!(deal.block_size(), 8);
 
     let ciphertext = deal.encrypt(&plaintext, &key);
     let decrypted = deal.decrypt(&ciphertext, &key);
 
     assert_eq!(decrypted, plaintext);
 }
+
+#[test]
+fn test_deal_variants_round_trip() {
+    let plaintext = hex_literal::hex!("00 11 22 33 44 55 66 77 88 99 AA BB CC DD EE FF");
+
+    for (key_len, rounds) in [(16, 6), (24, 6), (32, 8)] {
+        let key: Vec<u8> = (0..key_len as u8).collect();
+        let mut deal = DEAL::new();
+        deal.set_key(&key).unwrap();
+        assert_eq!(deal.block_size(), 16);
+        assert_eq!(deal.rounds(key_len), rounds);
+
+        let mut block = plaintext;
+        deal.encrypt_block_inplace(&mut block);
+        assert_ne!(block, plaintext, "DEAL-{}", key_len * 8);
+        deal.decrypt_block_inplace(&mut block);
+        assert_eq!(block, plaintext, "DEAL-{}", key_len * 8);
+    }
+}
+
+/// Ключевое расписание DEAL-128: RK1 = E(K1), RK2 = E(K2 ^ RK1),
+/// RK3 = E(K1 ^ <1> ^ RK2), ..., RK6 = E(K2 ^ <8> ^ RK5), E — DES
+/// на фиксированном ключе 0x1234567890ABCDEF.
+#[test]
+fn test_deal_128_key_schedule() {
+    let key = hex_literal::hex!("01 23 45 67 89 AB CD EF FE DC BA 98 76 54 32 10");
+    let (k1, k2) = key.split_at(8);
+    let fixed = des_with_key(&hex_literal::hex!("12 34 56 78 90 AB CD EF"));
+
+    let mut expected: Vec<Vec<u8>> = Vec::new();
+    let mut chain = vec![0u8; 8];
+    for (i, k) in [k1, k2, k1, k2, k1, k2].into_iter().enumerate() {
+        let constant = if i < 2 { 0u64 } else { 1 << (i - 2) };
+        chain = fixed.encrypt(&xor(&xor(k, &constant.to_be_bytes()), &chain));
+        expected.push(chain.clone());
+    }
+
+    assert_eq!(DealKeyExpansion::new().generate_round_keys(&key), expected);
+}
+
+/// Раундовая функция — DES с раундовым ключом над правой половиной.
+#[test]
+fn test_deal_round_function_is_des() {
+    let key: Vec<u8> = (0..24).collect();
+    let mut deal = DEAL::new();
+    deal.set_rounds(1).unwrap();
+    deal.set_key(&key).unwrap();
+
+    let block = hex_literal::hex!("00 11 22 33 44 55 66 77 88 99 AA BB CC DD EE FF");
+    let (left, right) = block.split_at(8);
+    let rk1 = &DealKeyExpansion::with_rounds(1).generate_round_keys(&key)[0];
+
+    let mut out = block;
+    deal.encrypt_block_inplace(&mut out);
+    assert_eq!(&out[..8], right);
+    assert_eq!(out[8..], xor(left, &des_with_key(rk1).encrypt(right))[..]);
+}
+
+#[test]
+fn test_deal_rejects_bad_parameters() {
+    let mut deal = DEAL::new();
+    assert!(matches!(
+        deal.set_key(&[0u8; 8]),
+        Err(CipherError::InvalidKeyLength { actual: 8, .. })
+    ));
+    assert!(matches!(deal.set_rounds(0), Err(CipherError::InvalidParameter(_))));
+
+    let mut toy = DEAL::new_toy();
+    assert!(matches!(toy.set_key(&[0u8; 16]), Err(CipherError::InvalidKeyLength { .. })));
+    assert!(matches!(toy.set_rounds(8), Err(CipherError::InvalidParameter(_))));
+}
+
+#[tokio::test]
+async fn test_deal_cbc_context() {
+    let key: Vec<u8> = (0..32).collect();
+    let data: Vec<u8> = (0..100u8).collect();
+
+    let mut ctx = CipherContext::new(
+        Box::new(DEAL::new()),
+        CipherMode::CBC,
+        PaddingMode::PKCS7,
+        Some(vec![0x42; 16]),
+        vec![],
+    );
+    ctx.set_key(&key).unwrap();
+
+    let mut encrypted = CipherOutput::Buffer(Box::default());
+    ctx.encrypt(CipherInput::Bytes(data.clone()), &mut encrypted).await.unwrap();
+    assert_eq!(encrypted.as_buffer().len(), 112);
+
+    let mut decrypted = CipherOutput::Buffer(Box::default());
+    ctx.decrypt(CipherInput::Bytes(encrypted.as_buffer().clone()), &mut decrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted.as_buffer(), &data);
+}
@@ -5,14 +5,14 @@ mod tests {
     use symmetric_cipher::crypto::des::DES;
     use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
     use symmetric_cipher::crypto::des_transformation::DesTransformation;
-    use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher};
+    use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
     use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
 
     #[test]
     fn test_des_nist_vector() {
         let key = hex_literal::hex!("13 34 57 79 9B BC DF F1");
         let plaintext = hex_literal::hex!("01 23 45 67 89 AB CD EF");
-        let expected_ciphertext = hex_literal::hex!("73 D3 B6 CE E5 D2 A7 13");
+        let expected_ciphertext = hex_literal::hex!("85 E8 13 54 0F 0A B4 05");
 
         let des = DES::new(
             Arc::new(DesKeyExpansion),
@@ -29,6 +29,43 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    #[test]
+    fn test_des_known_answers() {
+        // (ключ, открытый текст, шифртекст), сверено с OpenSSL des-ecb
+        let vectors = [
+            (
+                hex_literal::hex!("01 23 45 67 89 AB CD EF"),
+                hex_literal::hex!("01 23 45 67 89 AB CD EF"),
+                hex_literal::hex!("56 CC 09 E7 CF DC 4C EF"),
+            ),
+            (
+                hex_literal::hex!("0E 32 92 32 EA 6D 0D 73"),
+                hex_literal::hex!("87 87 87 87 87 87 87 87"),
+                hex_literal::hex!("00 00 00 00 00 00 00 00"),
+            ),
+        ];
+        let mut des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+
+        for (key, plaintext, ciphertext) in vectors {
+            des.set_key(&key).unwrap();
+            assert_eq!(des.encrypt(&plaintext), ciphertext);
+            assert_eq!(des.decrypt(&ciphertext), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_des_inplace_matches_vector() {
+        let mut des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+        des.set_key(&hex_literal::hex!("13 34 57 79 9B BC DF F1")).unwrap();
+
+        let mut block = hex_literal::hex!("01 23 45 67 89 AB CD EF");
+        des.encrypt_block_inplace(&mut block);
+        assert_eq!(block, hex_literal::hex!("85 E8 13 54 0F 0A B4 05"));
+
+        des.decrypt_block_inplace(&mut block);
+        assert_eq!(block, hex_literal::hex!("01 23 45 67 89 AB CD EF"));
+    }
+
     #[tokio::test]
     async fn test_des_cbc_ansi_x923_encrypt_decrypt() {
         let key = b"12345678"; 
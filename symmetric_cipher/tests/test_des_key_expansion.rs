@@ -4,7 +4,7 @@ use symmetric_cipher::crypto::key_expansion::KeyExpansion;
 #[test]
 fn test_key_expansion_round1() {
     let key = hex_literal::hex!("133457799BBCDFF1");
-    let expected_k1 = hex_literal::hex!("3E40F3D8FDE5");
+    let expected_k1 = hex_literal::hex!("1B02EFFC7072");
     let k1 = DesKeyExpansion
         .generate_round_keys(&key)[0]
         .clone();
@@ -3,9 +3,9 @@ use symmetric_cipher::crypto::encryption_transformation::EncryptionTransformatio
 
 #[test]
 fn test_f_function_example() {
-    let r = hex_literal::hex!("F0AAF123");
+    let r = hex_literal::hex!("F0AAF0AA");
     let k = hex_literal::hex!("1B02EFFC7072");
-    let expected = hex_literal::hex!("847B4BC0");
+    let expected = hex_literal::hex!("234AA9BB");
     let out = DesTransformation.transform(&r, &k);
     assert_eq!(out, expected);
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::deal::DEAL;
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+use symmetric_cipher::crypto::triple_des::TripleDes;
+
+/// Считает выделения памяти текущего потока.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// In-place шифрование совпадает с encrypt_block и не выделяет память.
+fn check(cipher: &dyn SymmetricCipherWithRounds) {
+    let plaintext: Vec<u8> = (0..cipher.block_size() as u8).map(|b| b.wrapping_mul(29)).collect();
+    let mut expected = Vec::new();
+    // Счётчик работает: обычный путь выделяет память
+    assert!(allocations_during(|| expected = cipher.encrypt_block(&plaintext, &[])) > 0);
+
+    let mut block = plaintext.clone();
+    assert_eq!(allocations_during(|| cipher.encrypt_block_inplace(&mut block)), 0);
+    assert_eq!(block, expected);
+    assert_eq!(allocations_during(|| cipher.decrypt_block_inplace(&mut block)), 0);
+    assert_eq!(block, plaintext);
+}
+
+fn des() -> DES {
+    DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation))
+}
+
+#[test]
+fn test_des_inplace_does_not_allocate() {
+    let mut des = des();
+    des.set_key(&hex_literal::hex!("13 34 57 79 9B BC DF F1")).unwrap();
+    check(&des);
+}
+
+#[test]
+fn test_deal_inplace_does_not_allocate() {
+    for len in [16, 24, 32] {
+        let mut deal = DEAL::new();
+        deal.set_key(&vec![0x5C; len]).unwrap();
+        check(&deal);
+    }
+    let mut toy = DEAL::new_toy();
+    toy.set_key(&[0x3A; 24]).unwrap();
+    check(&toy);
+}
+
+#[test]
+fn test_triple_des_inplace_does_not_allocate() {
+    let mut tdes = TripleDes::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+    tdes.set_key(&hex_literal::hex!("01 23 45 67 89 AB CD EF 23 45 67 89 AB CD EF 01 45 67 89 AB CD EF 01 23")).unwrap();
+    check(&tdes);
+}
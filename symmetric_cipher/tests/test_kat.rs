@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::kat::{self, Direction, KatError, KatMode};
+use symmetric_cipher::crypto::registry::{BoxedCipher, CipherRegistry};
+
+/// TDESAVS KAT-файлы используют ключ 0101...01 (все три ключа равны), а
+/// TripleDes такие ключи отвергает, поэтому 8-байтовые ключи идут в DES.
+fn tdes(key: &[u8]) -> Result<BoxedCipher, CipherError> {
+    let registry = CipherRegistry::with_builtin();
+    registry.create(if key.len() == 8 { "des" } else { "triple-des" }, key)
+}
+
+fn vector_file(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors").join(name)
+}
+
+fn assert_file_passes(name: &str) {
+    let report = kat::run_file(&vector_file(name), &tdes).unwrap_or_else(|e| panic!("{}: {}", name, e));
+    assert!(report.checked > 0, "{}: no vectors", name);
+    let failures: Vec<String> = report.failures.iter().map(ToString::to_string).collect();
+    assert!(failures.is_empty(), "{}:\n{}", name, failures.join("\n"));
+}
+
+#[test]
+fn test_tdes_ecb_known_answers() {
+    for name in ["TECBvartext.rsp", "TECBinvperm.rsp", "TECBvarkey.rsp"] {
+        assert_file_passes(name);
+    }
+}
+
+#[test]
+fn test_tdes_cbc_known_answers() {
+    for name in ["TCBCvartext.rsp", "TCBCinvperm.rsp", "TCBCvarkey.rsp"] {
+        assert_file_passes(name);
+    }
+}
+
+#[test]
+fn test_tdes_multi_block_messages() {
+    for name in ["TECBMMT2.rsp", "TECBMMT3.rsp", "TCBCMMT2.rsp", "TCBCMMT3.rsp"] {
+        assert_file_passes(name);
+    }
+}
+
+#[test]
+fn test_parse_sections_and_tdes_keys() {
+    let text = "\
+# CAVS 11.1
+[ENCRYPT]
+
+COUNT = 0
+KEY1 = 0123456789abcdef
+KEY2 = 23456789abcdef01
+KEY3 = 0123456789abcdef
+PLAINTEXT = 00000000
+CIPHERTEXT = ffffffff
+
+[DECRYPT]
+COUNT = 0
+KEYs = 0101010101010101
+CIPHERTEXT = 01
+PLAINTEXT = 02
+";
+    let vectors = kat::parse(text).unwrap();
+    assert_eq!(vectors.len(), 2);
+
+    // KEY3 = KEY1: второй вариант ключа, 16 байт.
+    assert_eq!(vectors[0].direction, Some(Direction::Encrypt));
+    assert_eq!(vectors[0].key.len(), 16);
+    assert_eq!(vectors[0].ciphertext, vec![0xff; 4]);
+    assert_eq!(vectors[0].line, 4);
+
+    assert_eq!(vectors[1].direction, Some(Direction::Decrypt));
+    assert_eq!(vectors[1].key, vec![1; 8]);
+    assert_eq!(vectors[1].plaintext, vec![2]);
+}
+
+#[test]
+fn test_parse_submission_table() {
+    // Формат ecb_tbl.txt: без секций, поля без пробелов вокруг '='.
+    let text = "Algorithm Name: TEST\n==========\nKEYSIZE=128\n\nI=1\nKEY=00\nPT=0A\nCT=0B\n\nI=2\nKEY=01\nPT=0C\nCT=0D\n";
+    let vectors = kat::parse(text).unwrap();
+    assert_eq!(vectors.len(), 2);
+    assert_eq!(vectors[1].count, 2);
+    assert_eq!(vectors[1].direction, None);
+    assert_eq!(vectors[1].plaintext, vec![0x0c]);
+}
+
+#[test]
+fn test_parse_shared_fields() {
+    // ecb_vk.txt/ecb_vt.txt: общий PT или KEY задаётся один раз до первого I
+    // и действует до следующего KEYSIZE.
+    let text = "==========\nKEYSIZE=128\n\nPT=00\n\nI=1\nKEY=80\nCT=01\n\nI=2\nKEY=40\nCT=02\n\
+                ==========\nKEYSIZE=192\n\nKEY=00\n\nI=1\nPT=80\nCT=03\n";
+    let vectors = kat::parse(text).unwrap();
+    assert_eq!(vectors.len(), 3);
+    assert_eq!(vectors[1].plaintext, vec![0x00]);
+    assert_eq!(vectors[1].key, vec![0x40]);
+    assert_eq!(vectors[2].key, vec![0x00]);
+    assert_eq!(vectors[2].plaintext, vec![0x80]);
+
+    // Без общего PT второй блок неполон.
+    assert!(kat::parse("KEYSIZE=128\nPT=00\nI=1\nKEY=00\nCT=00\nKEYSIZE=192\nI=1\nKEY=00\nCT=00\n").is_err());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        kat::parse("COUNT = 0\nKEY = 0g\n"),
+        Err(KatError::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        kat::parse("COUNT = 0\nKEY = 00\nPLAINTEXT = 00\n"),
+        Err(KatError::Parse { line: 1, .. })
+    ));
+    assert!(matches!(
+        kat::parse("COUNT = 0\nKEY1 = 00\nPLAINTEXT = 00\nCIPHERTEXT = 00\n"),
+        Err(KatError::Parse { .. })
+    ));
+}
+
+#[test]
+fn test_mode_from_file_name() {
+    assert_eq!(KatMode::from_file_name("CBCVarTxt128.rsp"), Some(KatMode::Cbc));
+    assert_eq!(KatMode::from_file_name("CFB8GFSbox256.rsp"), Some(KatMode::Cfb8));
+    assert_eq!(KatMode::from_file_name("CFB128VarKey192.rsp"), Some(KatMode::Cfb));
+    assert_eq!(KatMode::from_file_name("TCFB64MMT3.rsp"), Some(KatMode::Cfb));
+    assert_eq!(KatMode::from_file_name("TECBvartext.rsp"), Some(KatMode::Ecb));
+    assert_eq!(KatMode::from_file_name("ecb_tbl.txt"), Some(KatMode::Ecb));
+    assert_eq!(KatMode::from_file_name("XTSGenAES128.rsp"), None);
+}
+
+#[test]
+fn test_mismatch_is_reported() {
+    let text = "COUNT = 0\nKEYs = 0101010101010101\nPLAINTEXT = 8000000000000000\nCIPHERTEXT = 0000000000000000\n";
+    let report = kat::run(text, KatMode::Ecb, &tdes).unwrap();
+    assert_eq!(report.checked, 2);
+    assert_eq!(report.failures.len(), 2);
+    match &report.failures[0] {
+        KatError::Mismatch { direction, actual, .. } => {
+            assert_eq!(*direction, Direction::Encrypt);
+            assert_eq!(actual, &[0x95, 0xf8, 0xa5, 0xe5, 0xdd, 0x31, 0xd9, 0x00]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
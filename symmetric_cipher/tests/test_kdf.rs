@@ -0,0 +1,174 @@
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::container::{
+    AlgorithmId, ContainerHeader, ContainerReader, ContainerWriter, builtin_algorithm,
+};
+use symmetric_cipher::crypto::deal::DEAL;
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+use symmetric_cipher::crypto::kdf::{KdfParams, SALT_SIZE, key_len_for, pbkdf2_hmac_sha256, scrypt};
+use symmetric_cipher::crypto::sha256::{Sha256, sha256};
+
+/// Вспомогалка: разбирает hex-строку в байты (пробелы игнорируются)
+fn hex(s: &str) -> Vec<u8> {
+    let s: String = s.split_whitespace().collect();
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn des() -> DES {
+    DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation))
+}
+
+#[test]
+fn test_sha256_vectors() {
+    assert_eq!(
+        sha256(b"").to_vec(),
+        hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    );
+    assert_eq!(
+        sha256(b"abc").to_vec(),
+        hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+
+    // Два блока; подаём кусками, не совпадающими с границей блока.
+    let message = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    let mut hasher = Sha256::new();
+    for piece in message.chunks(7) {
+        hasher.update(piece);
+    }
+    assert_eq!(
+        hasher.finalize().to_vec(),
+        hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+    );
+}
+
+/// Векторы PBKDF2-HMAC-SHA256 из RFC 7914, раздел 11.
+#[test]
+fn test_pbkdf2_hmac_sha256_vectors() {
+    assert_eq!(
+        pbkdf2_hmac_sha256(b"passwd", b"salt", 1, 64),
+        hex("55 ac 04 6e 56 e3 08 9f ec 16 91 c2 25 44 b6 05
+             f9 41 85 21 6d de 04 65 e6 8b 9d 57 c2 0d ac bc
+             49 ca 9c cc f1 79 b6 45 99 16 64 b3 9d 77 ef 31
+             7c 71 b8 45 b1 e3 0b d5 09 11 20 41 d3 a1 97 83")
+    );
+    assert_eq!(
+        pbkdf2_hmac_sha256(b"Password", b"NaCl", 80000, 64),
+        hex("4d dc d8 f6 0b 98 be 21 83 0c ee 5e f2 27 01 f9
+             64 1a 44 18 d0 4c 04 14 ae ff 08 87 6b 34 ab 56
+             a1 d4 25 a1 22 58 33 54 9a db 84 1b 51 c9 b3 17
+             6a 27 2b de bb a1 d0 78 47 8f 62 b3 97 f3 3c 8d")
+    );
+}
+
+/// Векторы scrypt из RFC 7914, раздел 12.
+#[test]
+fn test_scrypt_vectors() {
+    assert_eq!(
+        scrypt(b"", b"", 4, 1, 1, 64).unwrap(),
+        hex("77 d6 57 62 38 65 7b 20 3b 19 ca 42 c1 8a 04 97
+             f1 6b 48 44 e3 07 4a e8 df df fa 3f ed e2 14 42
+             fc d0 06 9d ed 09 48 f8 32 6a 75 3a 0f c8 1f 17
+             e8 d3 e0 fb 2e 0d 36 28 cf 35 e2 0c 38 d1 89 06")
+    );
+    assert_eq!(
+        scrypt(b"password", b"NaCl", 10, 8, 16, 64).unwrap(),
+        hex("fd ba be 1c 9d 34 72 00 78 56 e7 19 0d 01 e9 fe
+             7c 6a d7 cb c8 23 78 30 e7 73 76 63 4b 37 31 62
+             2e af 30 d9 2e 22 a3 88 6f f1 09 27 9d 98 30 da
+             c7 27 af b9 4a 83 ee 6d 83 60 cb df a2 cc 06 40")
+    );
+
+    assert!(scrypt(b"", b"", 0, 1, 1, 32).is_err());
+    assert!(scrypt(b"", b"", 16, 1, 1, 32).is_err());
+    assert!(scrypt(b"", b"", 4, 0, 1, 32).is_err());
+}
+
+#[test]
+fn test_key_length_follows_cipher() {
+    assert_eq!(key_len_for(&des()).unwrap(), 8);
+    assert_eq!(key_len_for(&DEAL::new()).unwrap(), 32);
+    assert_eq!(key_len_for(&DEAL::new_toy()).unwrap(), 24);
+
+    let kdf = KdfParams::pbkdf2(10);
+    match &kdf {
+        KdfParams::Pbkdf2HmacSha256 { salt, .. } => assert_eq!(salt.len(), SALT_SIZE),
+        _ => unreachable!(),
+    }
+    assert_ne!(KdfParams::pbkdf2(10), kdf, "salt must be random");
+}
+
+#[tokio::test]
+async fn test_context_from_passphrase() {
+    let kdf = KdfParams::scrypt(8, 8, 1);
+    let data = b"encrypt me with a password".to_vec();
+    let context = |passphrase: &[u8]| {
+        CipherContext::with_passphrase(
+            Box::new(des()),
+            CipherMode::CBC,
+            PaddingMode::PKCS7,
+            Some(vec![0; 8]),
+            passphrase,
+            &kdf,
+        )
+        .unwrap()
+    };
+
+    let mut ciphertext = CipherOutput::Buffer(Box::default());
+    context(b"correct horse").encrypt(CipherInput::Bytes(data.clone()), &mut ciphertext).await.unwrap();
+
+    let mut decrypted = CipherOutput::Buffer(Box::default());
+    context(b"correct horse")
+        .decrypt(CipherInput::Bytes(ciphertext.as_buffer().clone()), &mut decrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted.as_buffer(), &data);
+
+    let mut wrong = CipherOutput::Buffer(Box::default());
+    let result = context(b"battery staple")
+        .decrypt(CipherInput::Bytes(ciphertext.as_buffer().clone()), &mut wrong)
+        .await;
+    assert!(result.is_err() || wrong.as_buffer() != &data);
+}
+
+#[tokio::test]
+async fn test_container_with_passphrase() {
+    let mut header = ContainerHeader::new(AlgorithmId::Deal, CipherMode::EAX, PaddingMode::PKCS7, Some(vec![3; 8]));
+    header.kdf = Some(KdfParams::pbkdf2(1000));
+    let writer = ContainerWriter::with_passphrase(header, b"hunter2", &builtin_algorithm).unwrap();
+
+    let mut sealed = CipherOutput::Buffer(Box::default());
+    writer.encrypt(CipherInput::Bytes(b"top secret".to_vec()), &mut sealed).await.unwrap();
+
+    // Соль и число итераций берутся из заголовка.
+    let reader = ContainerReader::open(CipherInput::Bytes(sealed.as_buffer().clone())).await.unwrap();
+    let mut opened = CipherOutput::Buffer(Box::default());
+    reader.decrypt_with_passphrase(b"hunter2", &builtin_algorithm, &mut opened).await.unwrap();
+    assert_eq!(opened.as_buffer(), b"top secret");
+
+    let reader = ContainerReader::open(CipherInput::Bytes(sealed.as_buffer().clone())).await.unwrap();
+    let mut opened = CipherOutput::Buffer(Box::default());
+    assert!(reader.decrypt_with_passphrase(b"hunter3", &builtin_algorithm, &mut opened).await.is_err());
+}
+
+#[tokio::test]
+async fn test_triple_des_container_with_passphrase() {
+    // Выход KDF почти никогда не имеет нечётной чётности, биты чётности
+    // выставляются перед set_key.
+    let mut header = ContainerHeader::new(AlgorithmId::TripleDes, CipherMode::CBC, PaddingMode::PKCS7, Some(vec![5; 8]));
+    header.kdf = Some(KdfParams::pbkdf2(1000));
+    let writer = ContainerWriter::with_passphrase(header, b"legacy", &builtin_algorithm).unwrap();
+
+    let mut sealed = CipherOutput::Buffer(Box::default());
+    writer.encrypt(CipherInput::Bytes(b"payment batch".to_vec()), &mut sealed).await.unwrap();
+
+    let reader = ContainerReader::open(CipherInput::Bytes(sealed.as_buffer().clone())).await.unwrap();
+    let mut opened = CipherOutput::Buffer(Box::default());
+    reader.decrypt_with_passphrase(b"legacy", &builtin_algorithm, &mut opened).await.unwrap();
+    assert_eq!(opened.as_buffer(), b"payment batch");
+}
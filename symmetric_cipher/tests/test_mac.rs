@@ -0,0 +1,129 @@
+use rand::{RngCore, rng};
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::deal::DEAL;
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+use symmetric_cipher::crypto::mac::{Cmac, HmacSha256};
+
+/// Вспомогалка: разбирает hex-строку в байты
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    rng().fill_bytes(&mut buf);
+    buf
+}
+
+fn des() -> DES {
+    DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation))
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new(key);
+    mac.update(data);
+    mac.finalize().to_vec()
+}
+
+/// Векторы HMAC-SHA256 из RFC 4231 (случаи 1, 2 и 6).
+#[test]
+fn test_hmac_sha256_rfc4231() {
+    assert_eq!(
+        hmac(&[0x0b; 20], b"Hi There"),
+        hex("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7")
+    );
+    assert_eq!(
+        hmac(b"Jefe", b"what do ya want for nothing?"),
+        hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+    );
+    assert_eq!(
+        hmac(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First"),
+        hex("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+    );
+}
+
+#[test]
+fn test_hmac_incremental_and_verify() {
+    let key = random_bytes(32);
+    let data = random_bytes(1000);
+    let tag = hmac(&key, &data);
+
+    let mut mac = HmacSha256::new(&key);
+    for piece in data.chunks(77) {
+        mac.update(piece);
+    }
+    assert!(mac.clone().verify(&tag).is_ok());
+    assert!(mac.clone().verify(&tag[..16]).is_ok(), "truncated tag");
+    assert!(matches!(mac.clone().verify(&tag[..3]), Err(CipherError::AuthenticationFailed)));
+
+    let mut forged = tag.clone();
+    forged[31] ^= 1;
+    assert!(matches!(mac.verify(&forged), Err(CipherError::AuthenticationFailed)));
+}
+
+#[test]
+fn test_cmac_64_bit_blocks() {
+    let mut des = des();
+    des.set_key(&[0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]).unwrap();
+    let mut deal = DEAL::new_toy();
+    deal.set_key(&random_bytes(24)).unwrap();
+
+    for cipher in [&des as &dyn SymmetricCipherWithRounds, &deal] {
+        // Длины вокруг границы блока: K1 для полного последнего блока, K2 для неполного.
+        for len in [0, 1, 7, 8, 9, 16, 29] {
+            let data = random_bytes(len);
+            let mut whole = Cmac::new(cipher).unwrap();
+            whole.update(&data);
+            let tag = whole.finalize();
+            assert_eq!(tag.len(), 8);
+
+            let mut pieces = Cmac::new(cipher).unwrap();
+            for piece in data.chunks(3) {
+                pieces.update(piece);
+            }
+            assert!(pieces.verify(&tag).is_ok(), "len {}", len);
+        }
+
+        let mut padded = Cmac::new(cipher).unwrap();
+        padded.update(&[0x80]);
+        let mut empty = Cmac::new(cipher).unwrap();
+        empty.update(&[]);
+        assert_ne!(padded.finalize(), empty.finalize(), "10* padding must not collide");
+    }
+}
+
+/// Encrypt-then-MAC поверх CBC из CipherContext: тег считается по IV и
+/// шифртексту и проверяется до расшифровки.
+#[tokio::test]
+async fn test_encrypt_then_mac_over_cbc() {
+    let enc_key = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+    let mac_key = random_bytes(32);
+    let iv = random_bytes(8);
+    let mut ctx = CipherContext::new(Box::new(des()), CipherMode::CBC, PaddingMode::PKCS7, Some(iv.clone()), Vec::new());
+    ctx.set_key(&enc_key).unwrap();
+
+    let mut ciphertext = CipherOutput::Buffer(Box::default());
+    ctx.encrypt(CipherInput::Bytes(b"pay 100 to alice".to_vec()), &mut ciphertext).await.unwrap();
+    let mut ciphertext = ciphertext.as_buffer().clone();
+    let tag = hmac(&mac_key, &[iv.as_slice(), &ciphertext].concat());
+
+    let mut mac = HmacSha256::new(&mac_key);
+    mac.update(&iv);
+    mac.update(&ciphertext);
+    mac.verify(&tag).unwrap();
+
+    ciphertext[0] ^= 1;
+    let mut mac = HmacSha256::new(&mac_key);
+    mac.update(&iv);
+    mac.update(&ciphertext);
+    assert!(matches!(mac.verify(&tag), Err(CipherError::AuthenticationFailed)));
+}
@@ -0,0 +1,139 @@
+use rand::{RngCore, rng};
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::{CipherAlgorithm, SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+
+const BLOCK: usize = 8;
+
+/// Дешёвая, но не тождественная перестановка блока: сдвиг на байт и XOR.
+/// Нужна, чтобы гонять многомегабайтные данные через параллельный путь без DES.
+struct RotateCipher;
+
+impl SymmetricCipher for RotateCipher {
+    fn set_key(&mut self, _: &[u8]) -> Result<(), CipherError> {
+        Ok(())
+    }
+}
+
+impl CipherAlgorithm for RotateCipher {
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        data.to_vec()
+    }
+    fn decrypt(&self, data: &[u8]) -> Vec<u8> {
+        data.to_vec()
+    }
+}
+
+impl SymmetricCipherWithRounds for RotateCipher {
+    fn set_key_with_rounds(&mut self, _key: &[u8]) {}
+
+    fn encrypt_block(&self, block: &[u8], _round_key: &[u8]) -> Vec<u8> {
+        let mut out = block.to_vec();
+        out.rotate_left(1);
+        out.iter_mut().for_each(|b| *b ^= 0xA5);
+        out
+    }
+
+    fn decrypt_block(&self, block: &[u8], _round_key: &[u8]) -> Vec<u8> {
+        let mut out: Vec<u8> = block.iter().map(|b| b ^ 0xA5).collect();
+        out.rotate_right(1);
+        out
+    }
+
+    fn block_size(&self) -> usize {
+        BLOCK
+    }
+
+    fn export_round_keys(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+fn context(mode: CipherMode) -> CipherContext {
+    CipherContext::new(
+        Box::new(RotateCipher),
+        mode,
+        PaddingMode::PKCS7,
+        Some((1..=BLOCK as u8).collect()),
+        Vec::new(),
+    )
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    rng().fill_bytes(&mut buf);
+    buf
+}
+
+/// Последовательный CBC по определению, для сверки.
+fn reference_cbc_decrypt(ciphertext: &[u8], iv: &[u8]) -> Vec<u8> {
+    let mut prev = iv.to_vec();
+    let mut out = Vec::with_capacity(ciphertext.len());
+    for block in ciphertext.chunks(BLOCK) {
+        let decrypted = RotateCipher.decrypt_block(block, &[]);
+        out.extend(decrypted.iter().zip(&prev).map(|(d, p)| d ^ p));
+        prev = block.to_vec();
+    }
+    out
+}
+
+#[tokio::test]
+async fn test_parallel_cbc_cfb_decrypt_round_trip() {
+    // 5 МБ больше порога, после которого данные делятся между потоками.
+    for len in [0, 7, 8, 1000, 5 * 1024 * 1024 + 13] {
+        for mode in [CipherMode::CBC, CipherMode::CFB] {
+            let ctx = context(mode);
+            let data = random_bytes(len);
+
+            let mut ciphertext = CipherOutput::Buffer(Box::default());
+            ctx.encrypt(CipherInput::Bytes(data.clone()), &mut ciphertext).await.unwrap();
+
+            let mut decrypted = CipherOutput::Buffer(Box::default());
+            ctx.decrypt(CipherInput::Bytes(ciphertext.as_buffer().clone()), &mut decrypted)
+                .await
+                .unwrap();
+            assert_eq!(decrypted.as_buffer(), &data, "mode {:?} len {}", mode, len);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_parallel_cbc_decrypt_matches_reference() {
+    let ctx = context(CipherMode::CBC);
+    let data = random_bytes(5 * 1024 * 1024);
+
+    let mut ciphertext = CipherOutput::Buffer(Box::default());
+    ctx.encrypt(CipherInput::Bytes(data.clone()), &mut ciphertext).await.unwrap();
+    let ciphertext = ciphertext.as_buffer().clone();
+
+    let mut decryptor = ctx.decryptor().unwrap();
+    let mut decrypted = decryptor.update(&ciphertext);
+    decrypted.extend(decryptor.finalize().unwrap());
+    assert_eq!(decrypted, data);
+
+    let mut expected = reference_cbc_decrypt(&ciphertext, &(1..=BLOCK as u8).collect::<Vec<_>>());
+    expected.truncate(data.len());
+    assert_eq!(decrypted, expected);
+}
+
+#[test]
+fn test_parallel_decrypt_keeps_chain_across_updates() {
+    for mode in [CipherMode::CBC, CipherMode::CFB] {
+        let ctx = context(mode);
+        let data = random_bytes(64 * 1024 + 5);
+
+        let mut encryptor = ctx.encryptor().unwrap();
+        let mut ciphertext = encryptor.update(&data);
+        ciphertext.extend(encryptor.finalize().unwrap());
+
+        // Куски не кратны ни блоку, ни размеру параллельной порции.
+        let mut decryptor = ctx.decryptor().unwrap();
+        let mut decrypted = Vec::new();
+        for piece in ciphertext.chunks(1000 + 3) {
+            decrypted.extend(decryptor.update(piece));
+        }
+        decrypted.extend(decryptor.finalize().unwrap());
+        assert_eq!(decrypted, data, "mode {:?}", mode);
+    }
+}
@@ -0,0 +1,79 @@
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::registry::{CipherDescriptor, CipherRegistry};
+use symmetric_cipher::crypto::triple_des::adjust_parity;
+
+#[test]
+fn test_builtin_ciphers_are_listed() {
+    let registry = CipherRegistry::with_builtin();
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["des", "triple-des", "deal"]);
+
+    let deal = registry.get("DEAL").expect("lookup ignores case");
+    assert_eq!(deal.block_size, 16);
+    assert_eq!(deal.key_sizes, &[16, 24, 32]);
+    assert_eq!(deal.default_rounds, 6);
+}
+
+#[test]
+fn test_create_returns_keyed_cipher() {
+    let registry = CipherRegistry::with_builtin();
+    let des = registry.create("des", &hex_literal::hex!("13 34 57 79 9B BC DF F1")).unwrap();
+
+    let mut block = hex_literal::hex!("01 23 45 67 89 AB CD EF");
+    des.encrypt_block_inplace(&mut block);
+    assert_eq!(block, hex_literal::hex!("85 E8 13 54 0F 0A B4 05"));
+}
+
+#[test]
+fn test_create_rejects_unknown_name_and_bad_key() {
+    let registry = CipherRegistry::with_builtin();
+    assert!(matches!(registry.create("rot13", &[0; 16]), Err(CipherError::InvalidParameter(_))));
+    assert!(matches!(
+        registry.create("deal", &[0; 20]),
+        Err(CipherError::InvalidKeyLength { expected: &[16, 24, 32], actual: 20 })
+    ));
+    // Слабый ключ отклоняет уже сам конструктор
+    assert!(matches!(registry.create("triple-des", &[0x01; 8]), Err(CipherError::InvalidParameter(_))));
+}
+
+#[test]
+fn test_register_rejects_duplicates() {
+    let mut registry = CipherRegistry::new();
+    assert_eq!(registry.algorithms().len(), 0);
+
+    let des: CipherDescriptor = *CipherRegistry::with_builtin().get("des").unwrap();
+    registry.register(des).unwrap();
+    assert!(matches!(
+        registry.register(CipherDescriptor { name: "DES", ..des }),
+        Err(CipherError::InvalidParameter(_))
+    ));
+    assert_eq!(registry.algorithms().len(), 1);
+}
+
+#[tokio::test]
+async fn test_registry_cipher_in_context() {
+    let registry = CipherRegistry::with_builtin();
+    let data: Vec<u8> = (0..200).collect();
+
+    for descriptor in registry.algorithms() {
+        let mut key: Vec<u8> = (1..=*descriptor.key_sizes.last().unwrap() as u8).map(|b| b.wrapping_mul(37)).collect();
+        adjust_parity(&mut key);
+        let cipher = registry.create(descriptor.name, &key).unwrap();
+        let ctx = CipherContext::new(
+            cipher,
+            CipherMode::CBC,
+            PaddingMode::PKCS7,
+            Some(vec![0x5A; descriptor.block_size]),
+            vec![],
+        );
+
+        let mut encrypted = CipherOutput::Buffer(Box::default());
+        ctx.encrypt(CipherInput::Bytes(data.clone()), &mut encrypted).await.unwrap();
+        let mut decrypted = CipherOutput::Buffer(Box::default());
+        ctx.decrypt(CipherInput::Bytes(encrypted.as_buffer().clone()), &mut decrypted)
+            .await
+            .unwrap();
+        assert_eq!(decrypted.as_buffer(), &data, "{}", descriptor.name);
+    }
+}
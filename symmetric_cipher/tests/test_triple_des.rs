@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use symmetric_cipher::crypto::cipher_context::CipherContext;
+use symmetric_cipher::crypto::cipher_error::CipherError;
+use symmetric_cipher::crypto::cipher_traits::{SymmetricCipher, SymmetricCipherWithRounds};
+use symmetric_cipher::crypto::cipher_types::{CipherInput, CipherMode, CipherOutput, PaddingMode};
+use symmetric_cipher::crypto::des::DES;
+use symmetric_cipher::crypto::des_key_expansion::DesKeyExpansion;
+use symmetric_cipher::crypto::des_transformation::DesTransformation;
+use symmetric_cipher::crypto::triple_des::{KeyingOption, TripleDes, adjust_parity, has_odd_parity, is_weak_key};
+
+const K1: [u8; 8] = hex_literal::hex!("0123456789ABCDEF");
+const K2: [u8; 8] = hex_literal::hex!("23456789ABCDEF01");
+const K3: [u8; 8] = hex_literal::hex!("456789ABCDEF0123");
+
+fn triple_des(key: &[u8]) -> Result<TripleDes, CipherError> {
+    let mut cipher = TripleDes::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+    cipher.set_key(key)?;
+    Ok(cipher)
+}
+
+fn ecb(cipher: &TripleDes, data: &[u8], encrypt: bool) -> Vec<u8> {
+    data.chunks(8)
+        .flat_map(|block| if encrypt { cipher.encrypt(block) } else { cipher.decrypt(block) })
+        .collect()
+}
+
+/// Пример TDEA-ECB из NIST SP 800-67 Rev. 2, приложение B.
+#[test]
+fn test_sp800_67_example() {
+    let cipher = triple_des(&[K1, K2, K3].concat()).unwrap();
+    assert_eq!(cipher.keying_option(), Some(KeyingOption::Option1));
+
+    let plaintext = b"The qufck brown fox jump";
+    let expected = hex_literal::hex!("A826FD8CE53B855F CCE21C8112256FE6 68D5C05DD9B6B900");
+
+    let ciphertext = ecb(&cipher, plaintext, true);
+    assert_eq!(ciphertext, expected);
+    assert_eq!(ecb(&cipher, &ciphertext, false), plaintext);
+
+    let mut block = *b"The qufc";
+    cipher.encrypt_block_inplace(&mut block);
+    assert_eq!(block, expected[..8]);
+    cipher.decrypt_block_inplace(&mut block);
+    assert_eq!(&block, b"The qufc");
+}
+
+#[test]
+fn test_keying_options_2_and_3() {
+    let block = hex_literal::hex!("0123456789ABCDEF");
+
+    // Вариант 2 — это вариант 1 с K3 = K1.
+    let option2 = triple_des(&[K1, K2].concat()).unwrap();
+    assert_eq!(option2.keying_option(), Some(KeyingOption::Option2));
+    let option1 = triple_des(&[K1, K2, K1].concat()).unwrap();
+    assert_eq!(option2.encrypt(&block), option1.encrypt(&block));
+
+    // Вариант 3 вырождается в одиночный DES.
+    let option3 = triple_des(&K1).unwrap();
+    let mut des = DES::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+    des.set_key(&K1).unwrap();
+    assert_eq!(option3.encrypt(&block), des.encrypt(&block));
+}
+
+#[test]
+fn test_key_validation() {
+    assert!(matches!(triple_des(&[0u8; 12]), Err(CipherError::InvalidKeyLength { actual: 12, .. })));
+
+    // Чётность: 0x00 — ноль единичных битов.
+    let mut key = [K1, K2, K3].concat();
+    key[5] ^= 1;
+    assert!(!has_odd_parity(&key));
+    assert!(matches!(triple_des(&key), Err(CipherError::InvalidParameter(_))));
+    adjust_parity(&mut key);
+    assert!(has_odd_parity(&key));
+    assert!(triple_des(&key).is_ok());
+
+    // Слабый и полуслабый ключи как компоненты.
+    let weak = hex_literal::hex!("FEFEFEFEFEFEFEFE");
+    let semi_weak = hex_literal::hex!("01E001E001F101F1");
+    assert!(is_weak_key(&weak) && is_weak_key(&semi_weak) && !is_weak_key(&K1));
+    assert!(triple_des(&[K1, weak, K3].concat()).is_err());
+    assert!(triple_des(&[semi_weak, K2].concat()).is_err());
+
+    // K1 = K2 или K2 = K3 сводит 3DES к одиночному DES.
+    assert!(triple_des(&[K1, K1, K3].concat()).is_err());
+    assert!(triple_des(&[K1, K2, K2].concat()).is_err());
+    assert!(triple_des(&[K1, K1].concat()).is_err());
+}
+
+#[tokio::test]
+async fn test_triple_des_in_cipher_context() {
+    let data: Vec<u8> = (0..100u8).collect();
+    for mode in [CipherMode::CBC, CipherMode::CTR, CipherMode::EAX] {
+        let cipher = TripleDes::new(Arc::new(DesKeyExpansion), Arc::new(DesTransformation));
+        let mut ctx = CipherContext::new(Box::new(cipher), mode, PaddingMode::PKCS7, Some(vec![7; 8]), Vec::new());
+        ctx.set_key(&[K1, K2, K3].concat()).unwrap();
+
+        let mut ciphertext = CipherOutput::Buffer(Box::default());
+        ctx.encrypt(CipherInput::Bytes(data.clone()), &mut ciphertext).await.unwrap();
+        let mut decrypted = CipherOutput::Buffer(Box::default());
+        ctx.decrypt(CipherInput::Bytes(ciphertext.as_buffer().clone()), &mut decrypted).await.unwrap();
+        assert_eq!(decrypted.as_buffer(), &data, "mode {:?}", mode);
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use bitvec::prelude::*;
+    use symmetric_cipher::crypto::cipher_error::CipherError;
     use symmetric_cipher::crypto::cipher_types::PaddingMode;
     use symmetric_cipher::crypto::utils::*;
 
@@ -100,7 +101,7 @@ mod tests {
     #[test]
     fn test_remove_padding_zeros() {
         let padded = vec![1, 2, 3, 0, 0];
-        let unpadded = remove_padding(padded, PaddingMode::Zeros);
+        let unpadded = remove_padding(padded, PaddingMode::Zeros).unwrap();
         assert_eq!(unpadded, vec![1, 2, 3]);
     }
 
@@ -108,7 +109,7 @@ mod tests {
     fn test_remove_padding_pkcs7() {
         let data = vec![1, 2, 3];
         let padded = apply_padding(data.clone(), 8, PaddingMode::PKCS7);
-        let unpadded = remove_padding(padded, PaddingMode::PKCS7);
+        let unpadded = remove_padding(padded, PaddingMode::PKCS7).unwrap();
         assert_eq!(unpadded, data);
     }
 
@@ -116,7 +117,7 @@ mod tests {
     fn test_remove_padding_ansi_x923() {
         let data = vec![1, 2, 3];
         let padded = apply_padding(data.clone(), 8, PaddingMode::ANSI_X923);
-        let unpadded = remove_padding(padded, PaddingMode::ANSI_X923);
+        let unpadded = remove_padding(padded, PaddingMode::ANSI_X923).unwrap();
         assert_eq!(unpadded, data);
     }
 
@@ -124,7 +125,7 @@ mod tests {
     fn test_remove_padding_iso10126() {
         let data = vec![1, 2, 3];
         let padded = apply_padding(data.clone(), 8, PaddingMode::ISO10126);
-        let unpadded = remove_padding(padded, PaddingMode::ISO10126);
+        let unpadded = remove_padding(padded, PaddingMode::ISO10126).unwrap();
         assert_eq!(unpadded, data);
     }
 
@@ -161,7 +162,7 @@ mod tests {
                 .all(|&b| b == pad_byte)
         );
 
-        let unpadded = remove_padding(padded, PaddingMode::PKCS7);
+        let unpadded = remove_padding(padded, PaddingMode::PKCS7).unwrap();
         assert_eq!(unpadded, data);
     }
 
@@ -181,7 +182,7 @@ mod tests {
         );
         assert_eq!(padded[padded.len() - 1], pad_len as u8);
 
-        let unpadded = remove_padding(padded, PaddingMode::ANSI_X923);
+        let unpadded = remove_padding(padded, PaddingMode::ANSI_X923).unwrap();
         assert_eq!(unpadded, data);
     }
 
@@ -195,7 +196,38 @@ mod tests {
         let pad_len = padded.last().copied().unwrap() as usize;
         assert!(pad_len > 0 && pad_len <= block_size);
 
-        let unpadded = remove_padding(padded, PaddingMode::ISO10126);
+        let unpadded = remove_padding(padded, PaddingMode::ISO10126).unwrap();
         assert_eq!(unpadded, data);
     }
+
+    #[test]
+    fn test_apply_padding_aligned_adds_block() {
+        let data = vec![1u8; 8];
+        for padding in [PaddingMode::PKCS7, PaddingMode::ANSI_X923, PaddingMode::ISO10126] {
+            let padded = apply_padding(data.clone(), 8, padding);
+            assert_eq!(padded.len(), 16);
+            assert_eq!(padded[15], 8);
+        }
+        assert_eq!(apply_padding(data.clone(), 8, PaddingMode::Zeros), data);
+    }
+
+    #[test]
+    fn test_remove_padding_rejects_malformed() {
+        assert!(matches!(
+            remove_padding(vec![1, 2, 3, 4, 5, 6, 7, 0], PaddingMode::PKCS7),
+            Err(CipherError::InvalidPadding)
+        ));
+        assert!(matches!(
+            remove_padding(vec![1, 2, 3, 4, 5, 6, 2, 3], PaddingMode::PKCS7),
+            Err(CipherError::InvalidPadding)
+        ));
+        assert!(matches!(
+            remove_padding(vec![1, 2, 3, 4, 5, 1, 0, 3], PaddingMode::ANSI_X923),
+            Err(CipherError::InvalidPadding)
+        ));
+        assert!(matches!(
+            remove_padding(vec![1, 2, 3, 4, 5, 6, 7, 9], PaddingMode::ISO10126),
+            Err(CipherError::InvalidPadding)
+        ));
+    }
 }
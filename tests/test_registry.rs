@@ -0,0 +1,43 @@
+use cryptography::registry;
+use symmetric_cipher::crypto::triple_des::adjust_parity;
+
+#[test]
+fn test_all_workspace_ciphers_are_registered() {
+    let names: Vec<_> = registry().names().collect();
+    for expected in ["des", "triple-des", "deal", "rijndael-128", "rijndael-160", "rijndael-192", "rijndael-224", "rijndael-256", "twofish", "loki97"] {
+        assert!(names.contains(&expected), "{} missing from {:?}", expected, names);
+    }
+}
+
+#[test]
+fn test_every_cipher_round_trips_with_every_key_size() {
+    let registry = registry();
+    for descriptor in registry.algorithms() {
+        for &key_len in descriptor.key_sizes {
+            // Нечётная чётность и разные K1/K2/K3 нужны Triple DES
+            let mut key: Vec<u8> = (0..key_len as u8).map(|i| 0x1F ^ i.wrapping_mul(29)).collect();
+            adjust_parity(&mut key);
+            let cipher = registry.create(descriptor.name, &key).unwrap();
+            assert_eq!(cipher.block_size(), descriptor.block_size, "{}", descriptor.name);
+
+            let plaintext: Vec<u8> = (0..descriptor.block_size as u8).collect();
+            let mut block = plaintext.clone();
+            cipher.encrypt_block_inplace(&mut block);
+            assert_ne!(block, plaintext, "{} with a {}-byte key", descriptor.name, key_len);
+            cipher.decrypt_block_inplace(&mut block);
+            assert_eq!(block, plaintext, "{} with a {}-byte key", descriptor.name, key_len);
+        }
+    }
+}
+
+#[test]
+fn test_resolve_algorithm_rejects_reducible_rijndael_modulus() {
+    use cryptography::resolve_algorithm;
+    use symmetric_cipher::crypto::container::AlgorithmId;
+
+    assert!(resolve_algorithm(AlgorithmId::Rijndael { block_size: 16, poly: 0x11B }).is_some());
+    // x^8 + 1 = (x + 1)^8, полином степени 4, лишние старшие биты
+    for poly in [0x101, 0x013, 0x31B, 0xFFFF] {
+        assert!(resolve_algorithm(AlgorithmId::Rijndael { block_size: 16, poly }).is_none(), "{:#x}", poly);
+    }
+}